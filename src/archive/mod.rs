@@ -0,0 +1,303 @@
+//! Chronicle archive module
+//!
+//! Helpers for the chronicle files in `output_dir`, which are named
+//! `chronicle-YYYY-MM-DD.md`.
+
+use chrono::NaiveDate;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::Result;
+use crate::models::{Chronicle, ChronicleStats};
+
+/// A chronicle file in the output directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivedChronicle {
+    pub date: NaiveDate,
+    pub path: PathBuf,
+}
+
+/// File name for the chronicle of a given date
+pub fn file_name(date: NaiveDate) -> String {
+    format!("chronicle-{}.md", date.format("%Y-%m-%d"))
+}
+
+/// Parse the date from a chronicle file name, if it is one
+pub fn parse_file_name(name: &str) -> Option<NaiveDate> {
+    let date = name.strip_prefix("chronicle-")?.strip_suffix(".md")?;
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+/// List chronicle files in the output directory, oldest first
+///
+/// Returns an empty list if the directory does not exist.
+pub fn list(output_dir: &Path) -> Result<Vec<ArchivedChronicle>> {
+    if !output_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut chronicles = Vec::new();
+
+    for entry in fs::read_dir(output_dir)? {
+        let path = entry?.path();
+
+        if !path.is_file() {
+            continue;
+        }
+
+        if let Some(date) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(parse_file_name)
+        {
+            chronicles.push(ArchivedChronicle { date, path });
+        }
+    }
+
+    chronicles.sort_by_key(|chronicle| chronicle.date);
+
+    Ok(chronicles)
+}
+
+/// The most recent chronicle dated before `date`
+pub fn previous(output_dir: &Path, date: NaiveDate) -> Result<Option<ArchivedChronicle>> {
+    Ok(list(output_dir)?
+        .into_iter()
+        .rev()
+        .find(|chronicle| chronicle.date < date))
+}
+
+/// Number of consecutive days with a chronicle, ending at `date`
+///
+/// Returns 0 if there is no chronicle for `date` itself.
+pub fn streak(output_dir: &Path, date: NaiveDate) -> Result<usize> {
+    let dates: HashSet<NaiveDate> = list(output_dir)?
+        .into_iter()
+        .map(|chronicle| chronicle.date)
+        .collect();
+
+    Ok(std::iter::successors(Some(date), |day| day.pred_opt())
+        .take_while(|day| dates.contains(day))
+        .count())
+}
+
+/// Read summary stats back from a chronicle's Summary table
+///
+/// Returns None if the file has no Summary section.
+pub fn read_stats(path: &Path) -> Result<Option<ChronicleStats>> {
+    let content = fs::read_to_string(path)?;
+    Ok(parse_summary(&content).map(|rows| ChronicleStats::from_rows(&rows)))
+}
+
+/// The chronicle's JSON sidecar (`chronicle-YYYY-MM-DD.json`, a serialized
+/// [`Chronicle`]), if present and readable
+///
+/// Unlike the Summary table, it has the line counts and which repositories
+/// were active.
+pub fn read_sidecar(chronicle: &ArchivedChronicle) -> Option<Chronicle> {
+    let json = fs::read_to_string(chronicle.path.with_extension("json")).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// Shift every Markdown heading one level down (`#` → `##`), leaving
+/// fenced code blocks (` ``` ` or `~~~`) alone
+///
+/// Used to nest a whole chronicle under a heading of its own.
+pub fn demote_headings(markdown: &str) -> String {
+    let mut fence: Option<&str> = None;
+
+    markdown
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            fence = match fence {
+                Some(marker) if trimmed.starts_with(marker) => None,
+                Some(marker) => Some(marker),
+                None => ["```", "~~~"]
+                    .into_iter()
+                    .find(|marker| trimmed.starts_with(marker)),
+            };
+            if fence.is_none() && line.starts_with('#') {
+                format!("#{}", line)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parse `| Label | Count |` rows from the Summary section
+fn parse_summary(markdown: &str) -> Option<HashMap<String, usize>> {
+    let section = markdown.split("\n## Summary\n").nth(1)?;
+    let mut rows = HashMap::new();
+
+    for line in section.lines().skip_while(|line| !line.starts_with('|')) {
+        let Some(row) = line.strip_prefix('|').and_then(|l| l.strip_suffix('|')) else {
+            break;
+        };
+
+        if let Some((label, count)) = row.split_once('|') {
+            if let Ok(count) = count.trim().replace(',', "").parse() {
+                rows.insert(label.trim().to_string(), count);
+            }
+        }
+    }
+
+    Some(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_file_name_roundtrip() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 9).unwrap();
+        assert_eq!(file_name(date), "chronicle-2024-03-09.md");
+        assert_eq!(parse_file_name(&file_name(date)), Some(date));
+    }
+
+    #[test]
+    fn test_parse_file_name_rejects_other_files() {
+        assert_eq!(parse_file_name("chronicle-2024-13-01.md"), None);
+        assert_eq!(parse_file_name("chronicle-latest.md"), None);
+        assert_eq!(parse_file_name("chronicle-2024-03-09.txt"), None);
+        assert_eq!(parse_file_name("notes-2024-03-09.md"), None);
+    }
+
+    #[test]
+    fn test_list_sorts_by_date() {
+        let temp_dir = TempDir::new().unwrap();
+        for name in [
+            "chronicle-2024-03-10.md",
+            "chronicle-2024-01-05.md",
+            "README.md",
+        ] {
+            fs::write(temp_dir.path().join(name), "").unwrap();
+        }
+        fs::create_dir(temp_dir.path().join("chronicle-2024-02-01.md")).unwrap();
+
+        let chronicles = list(temp_dir.path()).unwrap();
+
+        let dates: Vec<_> = chronicles.iter().map(|c| c.date.to_string()).collect();
+        assert_eq!(dates, vec!["2024-01-05", "2024-03-10"]);
+    }
+
+    #[test]
+    fn test_parse_summary() {
+        let markdown = "# Chronicle: 2024-01-15\n\n## Summary\n\n| Category | Count |\n\
+                        |----------|-------|\n| Commits | 5 |\n| Words Written | 1,234 |\n\n\
+                        ## TODOs\n\n| Not | 9 |\n";
+
+        let rows = parse_summary(markdown).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows["Commits"], 5);
+        assert_eq!(rows["Words Written"], 1234);
+        assert!(parse_summary("# Chronicle\n\nNo summary").is_none());
+    }
+
+    #[test]
+    fn test_demote_headings() {
+        let markdown = "# Chronicle\n\n## Summary\n\n```sh\n# comment\n```\n\n### repo";
+
+        assert_eq!(
+            demote_headings(markdown),
+            "## Chronicle\n\n### Summary\n\n```sh\n# comment\n```\n\n#### repo"
+        );
+
+        let markdown = "## Notes\n\n~~~\n# Heading in a note\n```\n## Still code\n~~~\n## After";
+        assert_eq!(
+            demote_headings(markdown),
+            "### Notes\n\n~~~\n# Heading in a note\n```\n## Still code\n~~~\n### After"
+        );
+    }
+
+    #[test]
+    fn test_read_sidecar() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("chronicle-2024-01-15.md");
+        fs::write(&path, "# Chronicle\n").unwrap();
+        let chronicle = ArchivedChronicle {
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            path: path.clone(),
+        };
+        assert!(read_sidecar(&chronicle).is_none());
+
+        // An unreadable sidecar is ignored
+        fs::write(path.with_extension("json"), "not json").unwrap();
+        assert!(read_sidecar(&chronicle).is_none());
+
+        let sidecar = Chronicle {
+            date: chronicle.date,
+            since: chrono::Utc::now(),
+            until: None,
+            generated_at: chrono::Utc::now(),
+            host: None,
+            repositories: vec![],
+            todos: vec![],
+            todo_totals: crate::models::TodoTotals { open: 3, done: 1 },
+            notes: vec![],
+            notes_omitted: 0,
+            events: vec![],
+            warnings: vec![],
+        };
+        fs::write(
+            path.with_extension("json"),
+            serde_json::to_string(&sidecar).unwrap(),
+        )
+        .unwrap();
+        let found = read_sidecar(&chronicle).unwrap();
+        assert_eq!(found.todo_totals, sidecar.todo_totals);
+    }
+
+    #[test]
+    fn test_previous() {
+        let temp_dir = TempDir::new().unwrap();
+        for name in ["chronicle-2024-01-10.md", "chronicle-2024-01-14.md"] {
+            fs::write(temp_dir.path().join(name), "").unwrap();
+        }
+
+        let date = |d: &str| NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap();
+
+        let found = previous(temp_dir.path(), date("2024-01-15")).unwrap();
+        assert_eq!(found.map(|c| c.date), Some(date("2024-01-14")));
+
+        let found = previous(temp_dir.path(), date("2024-01-14")).unwrap();
+        assert_eq!(found.map(|c| c.date), Some(date("2024-01-10")));
+
+        assert!(previous(temp_dir.path(), date("2024-01-10"))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_streak() {
+        let temp_dir = TempDir::new().unwrap();
+        for name in [
+            "chronicle-2024-02-27.md",
+            "chronicle-2024-02-28.md",
+            "chronicle-2024-02-29.md",
+            "chronicle-2024-03-01.md",
+            "chronicle-2024-02-25.md",
+        ] {
+            fs::write(temp_dir.path().join(name), "").unwrap();
+        }
+
+        let date = |d: &str| NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap();
+
+        assert_eq!(streak(temp_dir.path(), date("2024-03-01")).unwrap(), 4);
+        assert_eq!(streak(temp_dir.path(), date("2024-02-28")).unwrap(), 2);
+        assert_eq!(streak(temp_dir.path(), date("2024-02-25")).unwrap(), 1);
+        assert_eq!(streak(temp_dir.path(), date("2024-03-02")).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_list_missing_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(list(&temp_dir.path().join("missing")).unwrap().is_empty());
+    }
+}
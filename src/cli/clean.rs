@@ -0,0 +1,96 @@
+use chrono::Duration;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::archive::{self, ArchivedChronicle};
+use crate::config;
+use crate::display::log;
+use crate::error::{ChronicleError, Result};
+
+/// Delete chronicle files outside the retention window
+pub fn run(
+    config_path: Option<PathBuf>,
+    profile: Option<String>,
+    older_than: Option<String>,
+    keep: Option<usize>,
+    dry_run: bool,
+) -> Result<()> {
+    let config_path = config::resolve_path(config_path);
+
+    // Load configuration
+    let config = config::load_profile(&config_path, profile.as_deref())?;
+
+    let chronicles = archive::list(&config.output_dir)?;
+
+    let expired: Vec<ArchivedChronicle> = match (older_than, keep) {
+        (Some(age), _) => {
+            let cutoff = config
+                .today()
+                .checked_sub_signed(parse_age(&age)?)
+                .ok_or_else(|| {
+                    ChronicleError::Config(format!("Invalid age '{}': too far back", age))
+                })?;
+            chronicles
+                .into_iter()
+                .filter(|chronicle| chronicle.date < cutoff)
+                .collect()
+        }
+        (None, Some(keep)) => {
+            let expired_count = chronicles.len().saturating_sub(keep);
+            chronicles.into_iter().take(expired_count).collect()
+        }
+        (None, None) => {
+            return Err(ChronicleError::Config(
+                "Specify --older-than or --keep".to_string(),
+            ))
+        }
+    };
+
+    if expired.is_empty() {
+        log::info("No chronicle files to remove.");
+        return Ok(());
+    }
+
+    for chronicle in &expired {
+        // Along with the data written by `gen --format json` and the like
+        let sidecars =
+            ["json", "toml", "yaml"].map(|extension| chronicle.path.with_extension(extension));
+        let paths = std::iter::once(&chronicle.path).chain(sidecars.iter().filter(|p| p.exists()));
+        for path in paths {
+            if dry_run {
+                println!("Would remove: {}", path.display());
+            } else {
+                fs::remove_file(path)?;
+                log::detail(&format!("Removed: {}", path.display()));
+            }
+        }
+    }
+
+    if !dry_run {
+        log::info(&format!("Removed {} chronicle file(s).", expired.len()));
+    }
+
+    Ok(())
+}
+
+/// Parse a retention age like `90d` or `12w`
+fn parse_age(age: &str) -> Result<Duration> {
+    let invalid = || {
+        ChronicleError::Config(format!(
+            "Invalid age '{}' (expected a number of days or weeks, e.g. 90d or 12w)",
+            age
+        ))
+    };
+
+    let age = age.trim();
+    let (count, days_per_unit) = if let Some(count) = age.strip_suffix('d') {
+        (count, 1)
+    } else if let Some(count) = age.strip_suffix('w') {
+        (count, 7)
+    } else {
+        return Err(invalid());
+    };
+
+    let count: u32 = count.parse().map_err(|_| invalid())?;
+    Ok(Duration::days(i64::from(count) * days_per_unit))
+}
@@ -0,0 +1,13 @@
+use clap::CommandFactory;
+use clap_complete::Shell;
+use std::io;
+
+use crate::error::Result;
+
+/// Print the completion script for `shell` to stdout
+pub fn run(shell: Shell) -> Result<()> {
+    let mut command = crate::Cli::command();
+    clap_complete::generate(shell, &mut command, "chronicle", &mut io::stdout());
+
+    Ok(())
+}
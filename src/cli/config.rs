@@ -1,41 +1,255 @@
-use crate::config::{self, Config};
-use crate::error::Result;
+use crate::config::{self, Config, Discovered, RepoConfig, Severity, TodoFile, TodoFormat};
+use crate::display::log;
+use crate::error::{ChronicleError, Result};
+use dialoguer::{Input, MultiSelect};
+use directories::BaseDirs;
 use std::fs;
+use std::io::IsTerminal;
 use std::path::PathBuf;
+use std::process::Command;
 
 /// Initialize chronicle.toml configuration file
-pub fn init(path: Option<PathBuf>) -> Result<()> {
+///
+/// With `interactive`, repositories, TODO files, and notes directories found
+/// below the current directory (and in common home locations) are offered
+/// for selection instead of writing an empty default config.
+pub fn init(path: Option<PathBuf>, interactive: bool) -> Result<()> {
     let config_path = path.unwrap_or_else(|| PathBuf::from("chronicle.toml"));
 
     // Check if file already exists
     if config_path.exists() {
-        eprintln!(
+        log::warn(&format!(
             "Configuration file already exists at: {}",
             config_path.display()
-        );
-        eprintln!("Remove it first if you want to reinitialize.");
+        ));
+        log::warn("Remove it first if you want to reinitialize.");
         return Ok(());
     }
 
-    // Create default configuration
-    let config = Config::default();
+    // Create default configuration, or ask which sources to track
+    let config = if interactive {
+        interactive_config()?
+    } else {
+        Config::default()
+    };
 
     // Create output directory if it doesn't exist
     if !config.output_dir.exists() {
         fs::create_dir_all(&config.output_dir)?;
-        println!("Created output directory: {}", config.output_dir.display());
+        log::info(&format!(
+            "Created output directory: {}",
+            config.output_dir.display()
+        ));
     }
 
     // Save configuration
     config::save(&config, &config_path)?;
 
-    println!("Configuration file created: {}", config_path.display());
-    println!("\nNext steps:");
-    println!(
+    log::info(&format!(
+        "Configuration file created: {}",
+        config_path.display()
+    ));
+    log::info("\nNext steps:");
+    log::info(&format!(
         "1. Edit {} to configure your repositories and files",
         config_path.display()
-    );
-    println!("2. Run 'chronicle gen' to generate your first chronicle");
+    ));
+    log::info("2. Run 'chronicle gen' to generate your first chronicle");
+
+    Ok(())
+}
+
+/// Build a configuration from the sources the user picks
+fn interactive_config() -> Result<Config> {
+    if !std::io::stdin().is_terminal() {
+        return Err(ChronicleError::Config(
+            "'config init --interactive' needs a terminal; run it without --interactive and edit the file instead".to_string(),
+        ));
+    }
+
+    let root = std::env::current_dir()?;
+    let home = BaseDirs::new().map(|dirs| dirs.home_dir().to_path_buf());
+    log::info(&format!("Looking for sources in {}...", root.display()));
+    let Discovered {
+        repos,
+        todo_files,
+        notes_dirs,
+    } = config::discover(&root, home.as_deref());
+
+    let defaults = Config::default();
+    let repos = choose("Git repositories to track", repos)?;
+    let todo_files = choose("TODO files to parse", todo_files)?;
+    let notes_dirs = choose("Notes directories", notes_dirs)?;
+    let output_dir: String = Input::new()
+        .with_prompt("Output directory")
+        .default(defaults.output_dir.display().to_string())
+        .interact_text()
+        .map_err(prompt_error)?;
+
+    Ok(Config {
+        output_dir: PathBuf::from(output_dir),
+        repos: repos.into_iter().map(RepoConfig::from).collect(),
+        todo_files: todo_files.into_iter().map(todo_file).collect(),
+        notes_dirs,
+        ..defaults
+    })
+}
+
+/// A discovered TODO file, read as todo.txt or org when it is named that way
+fn todo_file(path: PathBuf) -> TodoFile {
+    let format = if path.file_name().is_some_and(|name| name == "todo.txt") {
+        TodoFormat::TodoTxt
+    } else if path.extension().is_some_and(|ext| ext == "org") {
+        TodoFormat::Org
+    } else {
+        TodoFormat::Markdown
+    };
+    TodoFile { path, format }
+}
+
+/// Let the user pick from `paths` (all selected by default)
+fn choose(prompt: &str, paths: Vec<PathBuf>) -> Result<Vec<PathBuf>> {
+    if paths.is_empty() {
+        log::info(&format!("{}: none found", prompt));
+        return Ok(paths);
+    }
+
+    let items: Vec<String> = paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect();
+    let picked = MultiSelect::new()
+        .with_prompt(format!("{} (space to toggle, enter to confirm)", prompt))
+        .items(&items)
+        .defaults(&vec![true; items.len()])
+        .interact()
+        .map_err(prompt_error)?;
+
+    Ok(picked
+        .into_iter()
+        .map(|index| paths[index].clone())
+        .collect())
+}
+
+fn prompt_error(error: dialoguer::Error) -> ChronicleError {
+    ChronicleError::Config(format!("Prompt failed: {}", error))
+}
+
+/// Print the effective configuration (file values merged with defaults and
+/// the selected profile)
+pub fn show(config_path: Option<PathBuf>, profile: Option<String>) -> Result<()> {
+    let config_path = config::resolve_path(config_path);
+
+    let mut config = config::load_profile(&config_path, profile.as_deref())?;
+    // Profiles aren't in effect themselves; the selected one is merged in
+    config.profiles.clear();
+    let toml = toml::to_string_pretty(&config)
+        .map_err(|e| ChronicleError::Config(format!("Failed to serialize config: {}", e)))?;
+
+    log::info(&format!(
+        "# Effective configuration: {}\n",
+        config_path.display()
+    ));
+    print!("{}", toml);
+
+    Ok(())
+}
+
+/// Validate the configuration file without generating anything
+///
+/// Prints each problem found; fails if any of them is an error. Every
+/// profile is checked to load, and the selected one (if any) is validated.
+pub fn check(config_path: Option<PathBuf>, profile: Option<String>) -> Result<()> {
+    let config_path = config::resolve_path(config_path);
+
+    let config = config::load_profile(&config_path, profile.as_deref())?;
+    for name in config.profiles.keys() {
+        config::load_profile(&config_path, Some(name))
+            .map_err(|e| ChronicleError::Config(format!("Profile '{}' is invalid: {}", name, e)))?;
+    }
+
+    let mut problems = config.validate();
+    if let Err(e) = crate::display::check_theme(&config.display.theme) {
+        let message = match e {
+            ChronicleError::Config(message) => message,
+            e => e.to_string(),
+        };
+        problems.push(config::Problem {
+            severity: Severity::Error,
+            message,
+        });
+    }
+
+    for problem in &problems {
+        println!("{}", problem);
+    }
+
+    let errors = problems
+        .iter()
+        .filter(|problem| problem.severity == Severity::Error)
+        .count();
+    if errors > 0 {
+        return Err(ChronicleError::Config(format!(
+            "{} has {} error{}",
+            config_path.display(),
+            errors,
+            if errors == 1 { "" } else { "s" }
+        )));
+    }
+
+    println!("OK: {}", config_path.display());
+    Ok(())
+}
+
+/// Open the configuration file in `$VISUAL`/`$EDITOR`, then re-validate it
+pub fn edit(config_path: Option<PathBuf>) -> Result<()> {
+    let config_path = config::resolve_path(config_path);
+
+    if !config_path.exists() {
+        return Err(ChronicleError::Config(format!(
+            "Config file does not exist: {}. Run 'chronicle config init' to create one.",
+            config_path.display()
+        )));
+    }
+
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| default_editor().to_string());
+
+    // Allow editors with arguments, e.g. EDITOR="code --wait"
+    let mut parts = editor.split_whitespace();
+    let program = parts
+        .next()
+        .ok_or_else(|| ChronicleError::Config("Editor command is empty".to_string()))?;
+
+    let status = Command::new(program)
+        .args(parts)
+        .arg(&config_path)
+        .status()
+        .map_err(|e| ChronicleError::Config(format!("Cannot launch editor '{}': {}", editor, e)))?;
+
+    if !status.success() {
+        return Err(ChronicleError::Config(format!(
+            "Editor '{}' exited with {}",
+            editor, status
+        )));
+    }
+
+    // Re-validate the edited file
+    config::load(&config_path)?;
+    log::info(&format!(
+        "Configuration is valid: {}",
+        config_path.display()
+    ));
 
     Ok(())
 }
+
+/// Editor used when neither `$VISUAL` nor `$EDITOR` is set
+fn default_editor() -> &'static str {
+    if cfg!(windows) {
+        "notepad"
+    } else {
+        "vi"
+    }
+}
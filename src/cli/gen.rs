@@ -1,118 +1,828 @@
-use chrono::{Local, NaiveDate, Utc};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::collections::HashSet;
 use std::fs;
-use std::path::PathBuf;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Instant;
 
-use crate::collectors::{GitCollector, NotesCollector, TodoCollector};
-use crate::config;
-use crate::error::Result;
-use crate::models::Chronicle;
-use crate::renderer::Renderer;
+use crate::archive;
+use crate::collectors::{
+    CalendarCollector, Collected, GitCollector, NotesCollector, Source, TodoCollector, Window,
+};
+use crate::config::{self, Config, RepoConfig};
+use crate::display::{log, opener};
+use crate::error::{ChronicleError, Result};
+use crate::models::{Chronicle, ChronicleStats, CollectionWarning, WarningKind};
+use crate::renderer::{export, Renderer, View};
 use crate::state;
 
-/// Generate a daily chronicle
-pub fn run(
-    config_path: Option<PathBuf>,
-    date: Option<String>,
-    since: Option<String>,
-    only: Option<String>,
-    dry_run: bool,
-) -> Result<()> {
-    let config_path = config_path.unwrap_or_else(|| PathBuf::from("chronicle.toml"));
+/// Source names accepted by `--only` and `--skip`
+const SOURCES: &[&str] = &["git", "todos", "notes", "calendar"];
+
+/// How long the sources must stay quiet before `--watch` regenerates
+const WATCH_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Options for `chronicle gen`, as given on the command line
+#[derive(Clone)]
+pub struct GenOptions {
+    pub config_path: Option<PathBuf>,
+    pub profile: Option<String>,
+    pub date: Option<String>,
+    pub since: Option<String>,
+    pub since_last_run: bool,
+    pub today: bool,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub only: Option<String>,
+    pub skip: Option<String>,
+    pub author_me: bool,
+    pub repos: Vec<PathBuf>,
+    pub limit_commits: Option<usize>,
+    pub limit_notes: Option<usize>,
+    pub formats: Vec<OutputFormat>,
+    pub view: View,
+    pub dry_run: bool,
+    pub fail_on_empty: bool,
+    pub strict: bool,
+    pub append: bool,
+    pub watch: bool,
+    pub save: bool,
+    pub open: bool,
+}
+
+/// A format `gen` writes the chronicle in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// The rendered chronicle, `chronicle-YYYY-MM-DD.md`
+    Markdown,
+    /// The collected data as JSON, `chronicle-YYYY-MM-DD.json`
+    Json,
+    /// The collected data as TOML, `chronicle-YYYY-MM-DD.toml`
+    Toml,
+    /// The collected data as YAML, `chronicle-YYYY-MM-DD.yaml`
+    Yaml,
+}
+
+impl OutputFormat {
+    /// File extension and contents of a data export; None for Markdown
+    fn export(self, chronicle: &Chronicle) -> Result<Option<(&'static str, String)>> {
+        Ok(match self {
+            OutputFormat::Markdown => None,
+            OutputFormat::Json => Some(("json", export::to_json(chronicle)?)),
+            OutputFormat::Toml => Some(("toml", export::to_toml(chronicle)?)),
+            OutputFormat::Yaml => Some(("yaml", export::to_yaml(chronicle)?)),
+        })
+    }
+}
+
+/// Generate a daily chronicle, once or on every change with `--watch`
+pub fn run(options: GenOptions) -> Result<()> {
+    if options.watch {
+        return watch(options);
+    }
+
+    generate(options, false)
+}
+
+/// Generate a daily chronicle, printing it even when written if
+/// `always_print` is set
+fn generate(options: GenOptions, always_print: bool) -> Result<()> {
+    let GenOptions {
+        config_path,
+        profile,
+        date,
+        since,
+        since_last_run,
+        today,
+        from,
+        to,
+        only,
+        skip,
+        author_me,
+        repos,
+        limit_commits,
+        limit_notes,
+        formats,
+        view,
+        dry_run,
+        fail_on_empty,
+        strict,
+        append,
+        watch: _,
+        save: _,
+        open,
+    } = options;
+    let config_path = config::resolve_path(config_path);
+
+    // Determine which collectors to run
+    let selected = selected_sources(only.as_deref(), skip.as_deref())?;
 
     // Load configuration
-    let config = config::load(&config_path)?;
+    let mut config = config::load_profile(&config_path, profile.as_deref())?;
+    override_repos(&mut config, &repos);
+
+    // Command-line options win over the config for this run
+    if author_me {
+        config.author_me = true;
+    }
+    if let Some(max_commits) = limit_commits {
+        config.limits.max_commits = max_commits;
+        for repo in &mut config.repos {
+            repo.max_commits = None;
+        }
+    }
+    if let Some(max_note_files) = limit_notes {
+        config.limits.max_note_files = max_note_files;
+    }
 
     // Load state
-    let mut state = state::load(&config.state_file)?;
+    let state_file = config.state_path(&config_path);
+    let mut state = state::load(&state_file)?;
+
+    // Start of a day in the configured timezone (local time when unset)
+    let day_start = |date: NaiveDate| match config.timezone {
+        Some(timezone) => start_of_day(date, &timezone),
+        None => start_of_day(date, &Local),
+    };
+
+    // Explicit date range, if given
+    let from = from.as_deref().map(parse_date).transpose()?;
+    let to = to.as_deref().map(parse_date).transpose()?;
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return Err(ChronicleError::Config(format!(
+                "--from {} is after --to {}",
+                from, to
+            )));
+        }
+    }
+
+    // Parse date (default to the end of the range, or today)
+    let chronicle_date = match (to, date) {
+        (Some(to), _) => to,
+        (None, Some(date_str)) => parse_date(&date_str)?,
+        (None, None) => config.today(),
+    };
 
-    // Parse date (default to today)
-    let chronicle_date = if let Some(date_str) = date {
-        NaiveDate::parse_from_str(&date_str, "%Y-%m-%d").map_err(|e| {
-            crate::error::ChronicleError::Config(format!("Invalid date format: {}", e))
-        })?
+    // The day's chronicle, and whether this run adds to it
+    let output_path = config.output_dir.join(archive::file_name(chronicle_date));
+    let appending = append && output_path.exists();
+    if append
+        && formats
+            .iter()
+            .any(|format| *format != OutputFormat::Markdown)
+    {
+        return Err(ChronicleError::Config(
+            "--append only works with --format md".to_string(),
+        ));
+    }
+
+    // Previous run time, when requested (an update covers what's new since
+    // the last run unless told otherwise)
+    let since_last_run =
+        since_last_run || (appending && since.is_none() && from.is_none() && !today);
+    let last_run = if since_last_run {
+        let last_run = state::last_run(&state);
+        if last_run.is_none() {
+            log::detail("No previous run recorded, using default window");
+        }
+        last_run
     } else {
-        Local::now().date_naive()
+        None
     };
 
+    // Per-source `git_since` / `notes_since` only replace the default window
+    let default_window = since.is_none() && last_run.is_none() && from.is_none() && !today;
+
     // Parse since timestamp
     let since_time = if let Some(since_str) = since {
-        chrono::DateTime::parse_from_rfc3339(&since_str)
-            .map(|dt| dt.with_timezone(&Utc))
-            .map_err(|e| {
-                crate::error::ChronicleError::Config(format!("Invalid since timestamp: {}", e))
-            })?
+        config::parse_since(&since_str, Utc::now())?
+    } else if let Some(last_run) = last_run {
+        last_run
+    } else if let Some(from) = from {
+        day_start(from)
+    } else if today {
+        day_start(chronicle_date)
     } else {
         // Default to 24 hours ago
         Utc::now() - chrono::Duration::hours(24)
     };
 
-    // Determine which collectors to run
-    let run_git = only.as_deref().is_none_or(|s| s.contains("git"));
-    let run_todos = only.as_deref().is_none_or(|s| s.contains("todos"));
-    let run_notes = only.as_deref().is_none_or(|s| s.contains("notes"));
-
-    // Run collectors
-    let repositories = if run_git {
-        let collector = GitCollector::new(&config);
-        collector.collect(&mut state, since_time)?
-    } else {
-        vec![]
-    };
+    // End of the range: the last instant of `to`
+    let until_time = to.map(|to| day_start(to + Duration::days(1)) - Duration::nanoseconds(1));
 
-    let todos = if run_todos {
-        let collector = TodoCollector::new(&config);
-        collector.collect(&mut state)?
-    } else {
-        vec![]
-    };
+    // Sources checked from here on are the ones this run updates
+    let run_started = Utc::now();
 
-    let notes = if run_notes {
-        let collector = NotesCollector::new(&config);
-        collector.collect(&mut state, since_time)?
-    } else {
-        vec![]
+    // Run the selected collectors, in `SOURCES` order
+    let sources: Vec<Box<dyn Source + '_>> = vec![
+        Box::new(GitCollector::new(&config)),
+        Box::new(TodoCollector::new(&config)),
+        Box::new(NotesCollector::new(&config)),
+        Box::new(CalendarCollector::new(&config)),
+    ];
+    let window = Window {
+        since: since_time,
+        until: until_time,
     };
+    let mut collected = Collected::default();
+    for source in sources.iter().filter(|s| selected.contains(s.name())) {
+        let window = match source_since(&config, source.name()).filter(|_| default_window) {
+            Some(since) => {
+                let since = config::parse_since(since, run_started).map_err(|e| {
+                    ChronicleError::Config(format!("{}_since: {}", source.name(), e))
+                })?;
+                log::detail(&format!("{}: collecting since {}", source.name(), since));
+                Window { since, ..window }
+            }
+            None => window,
+        };
+        source.collect_into(&mut state, window, &mut collected)?;
+
+        // Nothing has been written yet, so failing leaves state untouched;
+        // sources read with a fallback are still collected
+        if strict {
+            let skipped: Vec<String> = collected
+                .warnings
+                .iter()
+                .filter(|warning| warning.kind == WarningKind::Skipped)
+                .map(CollectionWarning::as_failure)
+                .collect();
+            if !skipped.is_empty() {
+                return Err(ChronicleError::Collector(format!(
+                    "{} (--strict)",
+                    skipped.join("; ")
+                )));
+            }
+        }
+    }
+
+    report_warnings(&collected.warnings);
+
+    let host = config
+        .include_host
+        .then(|| gethostname::gethostname().to_string_lossy().into_owned());
 
     // Build chronicle
-    let chronicle = Chronicle {
+    let mut chronicle = Chronicle {
         date: chronicle_date,
         since: since_time,
+        until: until_time,
         generated_at: Utc::now(),
-        repositories,
-        todos,
-        notes,
+        host,
+        repositories: collected.repositories,
+        todos: collected.todos,
+        todo_totals: collected.todo_totals,
+        notes: collected.notes,
+        notes_omitted: collected.notes_omitted,
+        events: collected.events,
+        warnings: collected.warnings,
     };
 
     // Check if there's any activity
     if !chronicle.has_activity() {
-        println!("No activity to report.");
-        return Ok(());
+        if fail_on_empty {
+            return Err(ChronicleError::NoActivity);
+        }
+        if !config.write_empty || appending {
+            log::info("No activity to report.");
+            return Ok(());
+        }
+    }
+
+    // Bound the output size on heavy days
+    let omitted = config
+        .limits
+        .max_total_items
+        .map_or(0, |max| chronicle.truncate(max));
+    if omitted > 0 {
+        log::detail(&format!("Omitted {} items over max_total_items", omitted));
     }
 
     // Render to Markdown
-    let renderer = Renderer::new(&config);
-    let markdown = renderer.render(&chronicle);
+    let mut renderer = Renderer::new(&config).with_view(view).with_omitted(omitted);
+    if config.display.show_deltas {
+        match previous_stats(&config.output_dir, chronicle_date) {
+            Ok(Some((date, stats))) => renderer = renderer.with_previous(date, stats),
+            Ok(None) => log::detail("No previous chronicle to compare with"),
+            Err(e) => log::warn(&format!("Cannot read previous chronicle: {}", e)),
+        }
+    }
+    if config.display.show_streak {
+        // This chronicle extends the streak ending the day before
+        match archive::streak(&config.output_dir, chronicle_date - Duration::days(1)) {
+            Ok(days) => renderer = renderer.with_streak(days + 1),
+            Err(e) => log::warn(&format!("Cannot compute streak: {}", e)),
+        }
+    }
+    if let Some(command) = config
+        .summary_command
+        .as_ref()
+        .filter(|_| chronicle.has_activity())
+    {
+        let timeout = std::time::Duration::from_secs(config.summary_timeout_secs as u64);
+        match run_summary_command(command, &chronicle, timeout) {
+            Ok(text) if !text.is_empty() => renderer = renderer.with_summary_text(text),
+            Ok(_) => log::detail("summary_command printed nothing"),
+            Err(e) => log::warn(&format!("Leaving out the summary paragraph: {}", e)),
+        }
+    }
+    // Each format is produced from the same collected chronicle
+    let markdown = formats.contains(&OutputFormat::Markdown).then(|| {
+        if appending {
+            renderer.render_update(&chronicle)
+        } else {
+            renderer.render(&chronicle)
+        }
+    });
+    let mut exports = Vec::new();
+    for format in &formats {
+        if let Some((extension, data)) = format.export(&chronicle)? {
+            exports.push((output_path.with_extension(extension), data));
+        }
+    }
 
-    if dry_run {
+    if dry_run || always_print {
         // Print to stdout with rich terminal formatting (if supported)
-        crate::display::print_markdown(&markdown);
-    } else {
-        // Write to file
-        let filename = format!("chronicle-{}.md", chronicle_date.format("%Y-%m-%d"));
-        let output_path = config.output_dir.join(filename);
+        if let Some(markdown) = &markdown {
+            crate::display::print_markdown(markdown, &config.display);
+        }
+        for (_, data) in &exports {
+            println!("{}", data);
+        }
+    }
 
+    if dry_run {
+        // Describe the skipped side effects on stderr, away from the Markdown
+        let updated = state
+            .sources
+            .values()
+            .filter(|source| source.last_checked() >= run_started)
+            .count();
+        let mut writes = Vec::new();
+        if markdown.is_some() {
+            writes.push(format!(
+                "{} {}",
+                if appending { "append to" } else { "write" },
+                output_path.display()
+            ));
+        }
+        for (path, _) in &exports {
+            writes.push(format!("write {}", path.display()));
+        }
+        log::note(&format!(
+            "dry run: would {} and update {} source{}",
+            writes.join(", "),
+            updated,
+            if updated == 1 { "" } else { "s" }
+        ));
+        if open {
+            log::warn("Nothing to open: --dry-run writes no file");
+        }
+    } else {
         // Ensure output directory exists
         if !config.output_dir.exists() {
             fs::create_dir_all(&config.output_dir)?;
         }
 
-        fs::write(&output_path, markdown)?;
-
-        println!("Chronicle written to: {}", output_path.display());
+        if let Some(markdown) = markdown {
+            if appending {
+                let mut file = fs::OpenOptions::new().append(true).open(&output_path)?;
+                write!(file, "\n\n{}", markdown)?;
+                log::info(&format!("Chronicle appended to: {}", output_path.display()));
+            } else {
+                fs::write(&output_path, markdown)?;
+                log::info(&format!("Chronicle written to: {}", output_path.display()));
+            }
+        }
+        for (path, data) in &exports {
+            fs::write(path, data)?;
+            log::info(&format!("Chronicle written to: {}", path.display()));
+        }
 
         // Save state
-        state::save(&state, &config.state_file)?;
+        state.last_generated = Some(chronicle.generated_at);
+        state.last_host = chronicle.host.clone();
+        state::save(&state, &state_file, config.compress_state)?;
+
+        if open {
+            // The Markdown when written, else the first data export
+            let written = formats
+                .contains(&OutputFormat::Markdown)
+                .then_some(&output_path)
+                .or(exports.first().map(|(path, _)| path));
+            if let Some(path) = written {
+                opener::open(path);
+            }
+        }
     }
 
     Ok(())
 }
+
+/// Run `summary_command` with the chronicle as JSON on stdin and return
+/// what it prints (trimmed)
+///
+/// The command runs through the shell, so pipes and arguments work. It is
+/// killed if it doesn't finish within `timeout`.
+fn run_summary_command(
+    command: &str,
+    chronicle: &Chronicle,
+    timeout: std::time::Duration,
+) -> Result<String> {
+    let fail = |message: String| ChronicleError::SummaryCommand(message);
+
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let mut child = Command::new(shell)
+        .args([flag, command])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|e| fail(format!("cannot run '{}': {}", command, e)))?;
+
+    // Feed stdin and drain stdout on their own threads, so a command that
+    // doesn't read its input (or writes a lot) can't block the other side
+    let json = serde_json::to_vec(chronicle)?;
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    thread::spawn(move || {
+        // A command may exit without reading everything
+        let _ = stdin.write_all(&json);
+    });
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let reader = thread::spawn(move || {
+        let mut output = String::new();
+        stdout.read_to_string(&mut output).map(|_| output)
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(fail(format!(
+                "'{}' did not finish within {}s",
+                command,
+                timeout.as_secs()
+            )));
+        }
+        thread::sleep(std::time::Duration::from_millis(20));
+    };
+
+    if !status.success() {
+        return Err(fail(format!("'{}' exited with {}", command, status)));
+    }
+
+    let output = reader
+        .join()
+        .map_err(|_| fail("cannot read its output".to_string()))?
+        .map_err(|e| fail(format!("cannot read its output: {}", e)))?;
+    Ok(output.trim().to_string())
+}
+
+/// What the `--watch` loop reacts to
+enum WatchSignal {
+    /// A watched source changed
+    Changed,
+    /// Ctrl-C
+    Stop,
+}
+
+/// The configured `git_since` / `notes_since` for a source, if any
+fn source_since<'c>(config: &'c Config, source: &str) -> Option<&'c str> {
+    match source {
+        "git" => config.git_since.as_deref(),
+        "notes" => config.notes_since.as_deref(),
+        _ => None,
+    }
+}
+
+/// Replace the configured repositories with `--repo` paths, if any were
+/// given
+fn override_repos(config: &mut Config, repos: &[PathBuf]) {
+    if !repos.is_empty() {
+        config.repos = repos.iter().cloned().map(RepoConfig::from).collect();
+    }
+}
+
+/// Regenerate on every change to the configured sources until Ctrl-C
+///
+/// Each run prints the chronicle; it is only written (and state updated)
+/// with `--save`. A failed run is reported and watching continues.
+fn watch(options: GenOptions) -> Result<()> {
+    let config_path = config::resolve_path(options.config_path.clone());
+    let mut config = config::load_profile(&config_path, options.profile.as_deref())?;
+    override_repos(&mut config, &options.repos);
+    let targets = WatchTargets::new(&config, &config.state_path(&config_path));
+
+    let (sender, receiver) = mpsc::channel();
+
+    let stop = sender.clone();
+    ctrlc::set_handler(move || {
+        let _ = stop.send(WatchSignal::Stop);
+    })
+    .map_err(|e| ChronicleError::Watch(format!("Cannot handle Ctrl-C: {}", e)))?;
+
+    let relevant = targets.clone();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        // Reads (including chronicle's own) show up as access events
+        if let Ok(event) = event {
+            let writes = matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+            );
+            if writes && event.paths.iter().any(|path| relevant.is_relevant(path)) {
+                let _ = sender.send(WatchSignal::Changed);
+            }
+        }
+    })
+    .map_err(|e| ChronicleError::Watch(e.to_string()))?;
+
+    for (path, mode) in targets.watches() {
+        if let Err(e) = watcher.watch(&path, mode) {
+            log::warn(&format!("Cannot watch '{}': {}", path.display(), e));
+        }
+    }
+
+    loop {
+        let run_options = GenOptions {
+            dry_run: !options.save,
+            ..options.clone()
+        };
+        if let Err(e) = generate(run_options, true) {
+            log::warn(&e.to_string());
+        }
+        let _ = std::io::stdout().flush();
+
+        log::note("Watching for changes (Ctrl-C to stop)...");
+        if !wait_for_change(&receiver) {
+            return Ok(());
+        }
+    }
+}
+
+/// Block until a change has been followed by `WATCH_DEBOUNCE` of quiet;
+/// false on Ctrl-C
+fn wait_for_change(receiver: &mpsc::Receiver<WatchSignal>) -> bool {
+    match receiver.recv() {
+        Ok(WatchSignal::Changed) => {}
+        Ok(WatchSignal::Stop) | Err(_) => return false,
+    }
+
+    loop {
+        match receiver.recv_timeout(WATCH_DEBOUNCE) {
+            Ok(WatchSignal::Changed) => continue,
+            Ok(WatchSignal::Stop) | Err(RecvTimeoutError::Disconnected) => return false,
+            Err(RecvTimeoutError::Timeout) => return true,
+        }
+    }
+}
+
+/// Paths `--watch` listens to for the configured sources
+///
+/// Repositories are watched through their refs and `HEAD`, so new commits
+/// register without following every file in the working tree. TODO and
+/// calendar files are watched through their directory, as editors often
+/// replace the file.
+#[derive(Clone)]
+struct WatchTargets {
+    /// Directories whose contents count as changes
+    dirs: Vec<(PathBuf, RecursiveMode)>,
+    /// Single files whose changes count
+    files: Vec<PathBuf>,
+    /// chronicle's own output directory and state file, which `--save`
+    /// writes on every run and may sit inside a notes directory
+    own: Vec<PathBuf>,
+}
+
+impl WatchTargets {
+    fn new(config: &config::Config, state_file: &Path) -> Self {
+        let mut dirs = Vec::new();
+
+        for repo in &config.repos {
+            match git2::Repository::open(&repo.path) {
+                Ok(git_repo) => {
+                    // HEAD and the index live in the (worktree's) git dir;
+                    // branches and packed-refs in the common dir
+                    dirs.push((absolute(git_repo.path()), RecursiveMode::NonRecursive));
+                    let common_dir = absolute(git_repo.commondir());
+                    dirs.push((common_dir.join("refs"), RecursiveMode::Recursive));
+                    dirs.push((common_dir, RecursiveMode::NonRecursive));
+                }
+                Err(e) => log::warn(&format!(
+                    "Not watching repository '{}': {}",
+                    repo.path.display(),
+                    e
+                )),
+            }
+        }
+
+        let notes_mode = if config.notes_max_depth > 1 {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        for dir in &config.notes_dirs {
+            dirs.push((absolute(dir), notes_mode));
+        }
+
+        let files = config
+            .todo_files
+            .iter()
+            .map(|file| &file.path)
+            .chain(&config.calendar_files)
+            .map(|file| absolute(file))
+            .collect();
+
+        let own = vec![absolute(&config.output_dir), absolute(state_file)];
+
+        Self { dirs, files, own }
+    }
+
+    /// Paths to register with the watcher, without duplicates
+    fn watches(&self) -> Vec<(PathBuf, RecursiveMode)> {
+        let mut watches: Vec<(PathBuf, RecursiveMode)> = Vec::new();
+
+        let todo_dirs = self
+            .files
+            .iter()
+            .filter_map(|file| file.parent())
+            .map(|dir| (dir.to_path_buf(), RecursiveMode::NonRecursive));
+        for (path, mode) in self.dirs.iter().cloned().chain(todo_dirs) {
+            if !watches.iter().any(|(watched, _)| *watched == path) {
+                watches.push((path, mode));
+            }
+        }
+
+        watches
+    }
+
+    /// Whether an event on `path` should trigger a new chronicle
+    ///
+    /// Git lock files come and go during every Git command and are ignored,
+    /// as are chronicle's own writes, which would otherwise trigger another
+    /// run after every saved one.
+    fn is_relevant(&self, path: &Path) -> bool {
+        if path.extension().is_some_and(|ext| ext == "lock")
+            || self.own.iter().any(|own| path.starts_with(own))
+        {
+            return false;
+        }
+
+        self.files.iter().any(|file| file == path)
+            || self.dirs.iter().any(|(dir, _)| path.starts_with(dir))
+    }
+}
+
+/// `path` made absolute and canonical, to compare with the absolute paths
+/// in watch events
+///
+/// A path that doesn't exist yet (like an output directory before the first
+/// `--save`) is resolved through its closest existing ancestor.
+fn absolute(path: &Path) -> PathBuf {
+    if let Ok(canonical) = path.canonicalize() {
+        return canonical;
+    }
+
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) if !parent.as_os_str().is_empty() => absolute(parent).join(name),
+        _ => std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf()),
+    }
+}
+
+/// Parse a `YYYY-MM-DD` date argument
+pub(crate) fn parse_date(value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|e| ChronicleError::Config(format!("Invalid date format: {}", e)))
+}
+
+/// Midnight at the start of `date` in `tz`, as UTC
+///
+/// Where midnight falls into a DST gap, the first valid local time after it
+/// is used.
+fn start_of_day<Tz: TimeZone>(date: NaiveDate, tz: &Tz) -> DateTime<Utc> {
+    let midnight = date.and_time(NaiveTime::MIN);
+
+    (0..24)
+        .find_map(|hours| {
+            tz.from_local_datetime(&(midnight + Duration::hours(hours)))
+                .earliest()
+        })
+        .map(|start| start.with_timezone(&Utc))
+        .unwrap_or_else(|| midnight.and_utc())
+}
+
+/// Date and summary stats of the chronicle before `date`, if there is one
+fn previous_stats(
+    output_dir: &std::path::Path,
+    date: NaiveDate,
+) -> Result<Option<(NaiveDate, ChronicleStats)>> {
+    let Some(previous) = archive::previous(output_dir, date)? else {
+        return Ok(None);
+    };
+
+    Ok(archive::read_stats(&previous.path)?.map(|stats| (previous.date, stats)))
+}
+
+/// Print collection warnings: each one with `--verbose`, otherwise a count
+fn report_warnings(warnings: &[CollectionWarning]) {
+    if log::verbosity() >= log::Verbosity::Verbose {
+        for warning in warnings {
+            log::warn(&warning.message);
+        }
+    } else if !warnings.is_empty() {
+        log::warn(&format!(
+            "{} source{} skipped or partly read (run with --verbose for details)",
+            warnings.len(),
+            if warnings.len() == 1 { "" } else { "s" }
+        ));
+    }
+}
+
+/// Sources to collect from, given `--only` or `--skip`
+fn selected_sources(only: Option<&str>, skip: Option<&str>) -> Result<HashSet<&'static str>> {
+    match (only, skip) {
+        (Some(_), Some(_)) => Err(ChronicleError::Config(
+            "--only and --skip cannot be used together".to_string(),
+        )),
+        (Some(only), None) => parse_sources(only),
+        (None, Some(skip)) => {
+            let skipped = parse_sources(skip)?;
+            Ok(SOURCES
+                .iter()
+                .copied()
+                .filter(|source| !skipped.contains(source))
+                .collect())
+        }
+        (None, None) => Ok(SOURCES.iter().copied().collect()),
+    }
+}
+
+/// Parse a comma-separated list of source names, rejecting unknown ones
+fn parse_sources(list: &str) -> Result<HashSet<&'static str>> {
+    let mut sources = HashSet::new();
+
+    for name in list
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    {
+        let source = SOURCES
+            .iter()
+            .find(|source| source.eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                ChronicleError::Config(format!(
+                    "Unknown source '{}' (expected one of: {})",
+                    name,
+                    SOURCES.join(", ")
+                ))
+            })?;
+        sources.insert(*source);
+    }
+
+    if sources.is_empty() {
+        return Err(ChronicleError::Config(format!(
+            "No sources given (expected one of: {})",
+            SOURCES.join(", ")
+        )));
+    }
+
+    Ok(sources)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_watch_ignores_own_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let notes = temp_dir.path().join("notes");
+        fs::create_dir(&notes).unwrap();
+        let config = Config {
+            repos: vec![],
+            notes_dirs: vec![notes.clone()],
+            output_dir: notes.join("chronicles"),
+            ..Config::default()
+        };
+
+        let targets = WatchTargets::new(&config, &notes.join(".chronicle-state.json"));
+        let notes = absolute(&notes);
+        assert!(targets.is_relevant(&notes.join("idea.md")));
+        assert!(!targets.is_relevant(&notes.join("chronicles/chronicle-2024-01-15.md")));
+        assert!(!targets.is_relevant(&notes.join(".chronicle-state.json")));
+        assert!(!targets.is_relevant(&notes.join("index.lock")));
+    }
+}
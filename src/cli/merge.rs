@@ -0,0 +1,117 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::archive;
+use crate::cli::gen::parse_date;
+use crate::config;
+use crate::display::log;
+use crate::error::{ChronicleError, Result};
+use crate::models::ChronicleStats;
+use crate::renderer::render_stats_table;
+
+/// Combine the chronicles from `from` to `to` into one document, each day
+/// under its own heading after a Summary of the whole range
+pub fn run(
+    config_path: Option<PathBuf>,
+    profile: Option<String>,
+    from: String,
+    to: String,
+    out: Option<PathBuf>,
+) -> Result<()> {
+    let config_path = config::resolve_path(config_path);
+
+    // Load configuration
+    let config = config::load_profile(&config_path, profile.as_deref())?;
+
+    let from = parse_date(&from)?;
+    let to = parse_date(&to)?;
+    if from > to {
+        return Err(ChronicleError::Config(format!(
+            "--from {} is after --to {}",
+            from, to
+        )));
+    }
+
+    let chronicles: Vec<_> = archive::list(&config.output_dir)?
+        .into_iter()
+        .filter(|chronicle| chronicle.date >= from && chronicle.date <= to)
+        .collect();
+    if chronicles.is_empty() {
+        log::info(&format!("No chronicles from {} to {}.", from, to));
+        return Ok(());
+    }
+
+    let mut stats = ChronicleStats::default();
+    let mut repositories = HashSet::new();
+    // Days counted from their Summary table, which has no line counts
+    let mut without_sidecar = 0;
+    let mut days = String::new();
+    for chronicle in &chronicles {
+        let day_stats = match archive::read_sidecar(chronicle) {
+            Some(day) => {
+                repositories.extend(day.repositories.iter().map(|repo| repo.path.clone()));
+                Some(day.stats(&config.completed_statuses))
+            }
+            None => {
+                let day_stats = archive::read_stats(&chronicle.path)?;
+                without_sidecar += usize::from(day_stats.is_some());
+                day_stats
+            }
+        };
+        match day_stats {
+            Some(day_stats) => stats.add(&day_stats),
+            None => log::detail(&format!(
+                "No Summary in '{}', leaving it out of the totals",
+                chronicle.path.display()
+            )),
+        }
+
+        // The day's heading replaces its own title
+        let content = fs::read_to_string(&chronicle.path)?;
+        let body = match content.split_once('\n') {
+            Some((title, body)) if title.starts_with("# ") => body,
+            _ => content.as_str(),
+        };
+        days.push_str(&format!(
+            "\n\n## {}\n\n{}",
+            chronicle.date.format("%Y-%m-%d"),
+            archive::demote_headings(body.trim())
+        ));
+    }
+
+    // A repository active on several days counts once
+    stats.repo_count = stats.repo_count.max(repositories.len());
+    let mut summary = render_stats_table(&stats, without_sidecar == 0);
+    if without_sidecar > 0 {
+        summary.push_str(&format!(
+            "\n\n*{} day{} without a JSON sidecar: Lines Changed left out, \
+             Repositories at least as shown*",
+            without_sidecar,
+            if without_sidecar == 1 { "" } else { "s" }
+        ));
+    }
+
+    let markdown = format!(
+        "# Chronicles: {} – {}\n\n## Summary\n\n**Days:** {}\n\n{}{}\n",
+        from.format("%Y-%m-%d"),
+        to.format("%Y-%m-%d"),
+        chronicles.len(),
+        summary,
+        days
+    );
+
+    match out {
+        Some(out) => {
+            fs::write(&out, markdown)?;
+            log::info(&format!(
+                "Merged {} chronicle(s) into: {}",
+                chronicles.len(),
+                out.display()
+            ));
+        }
+        None => print!("{}", markdown),
+    }
+
+    Ok(())
+}
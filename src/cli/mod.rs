@@ -2,11 +2,21 @@
 //!
 //! Implements all CLI commands using clap:
 //! - config init: Initialize configuration file
+//! - config show/edit: Inspect or edit the configuration
+//! - config check: Validate the configuration
 //! - gen: Generate daily chronicle
+//! - clean: Prune old chronicle files
+//! - merge: Combine a date range of chronicles into one document
+//! - completions: Print shell completion scripts (hidden)
 //! - show latest: Display most recent chronicle
+//! - show streak: Display consecutive days with a chronicle
+//! - state show: Display state tracking details
 //! - state reset: Reset state tracking
 
+pub mod clean;
+pub mod completions;
 pub mod config;
 pub mod gen;
+pub mod merge;
 pub mod show;
 pub mod state;
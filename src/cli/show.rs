@@ -1,22 +1,56 @@
 use std::fs;
 use std::path::PathBuf;
 
+use crate::archive;
 use crate::config;
+use crate::display::opener;
 use crate::error::{ChronicleError, Result};
 
-/// Display the most recent chronicle
-pub fn latest(config_path: Option<PathBuf>) -> Result<()> {
-    let config_path = config_path.unwrap_or_else(|| PathBuf::from("chronicle.toml"));
+/// Display the most recent chronicle, or open it in the default
+/// application with `open`
+pub fn latest(config_path: Option<PathBuf>, profile: Option<String>, open: bool) -> Result<()> {
+    let config_path = config::resolve_path(config_path);
 
     // Load configuration
-    let config = config::load(&config_path)?;
+    let config = config::load_profile(&config_path, profile.as_deref())?;
 
     // Find latest chronicle file
     let latest_file = find_latest_chronicle(&config.output_dir)?;
+    if open {
+        opener::open(&latest_file);
+        return Ok(());
+    }
 
-    // Read and display with rich terminal formatting (if supported)
+    // Read and display with rich terminal formatting (if supported), paged on a TTY
     let content = fs::read_to_string(&latest_file)?;
-    crate::display::print_markdown(&content);
+    crate::display::page_markdown(&content, &config.display);
+
+    Ok(())
+}
+
+/// Print the number of consecutive days with a chronicle
+///
+/// A streak stays alive through today until the day's chronicle is written,
+/// so it counts back from yesterday when there is none for today yet.
+pub fn streak(config_path: Option<PathBuf>, profile: Option<String>) -> Result<()> {
+    let config_path = config::resolve_path(config_path);
+
+    // Load configuration
+    let config = config::load_profile(&config_path, profile.as_deref())?;
+
+    let today = config.today();
+    let mut days = archive::streak(&config.output_dir, today)?;
+    if days == 0 {
+        if let Some(yesterday) = today.pred_opt() {
+            days = archive::streak(&config.output_dir, yesterday)?;
+        }
+    }
+
+    match days {
+        0 => println!("No current streak. Run 'chronicle gen' to start one."),
+        1 => println!("Current streak: 1 day"),
+        days => println!("Current streak: {} days", days),
+    }
 
     Ok(())
 }
@@ -30,32 +64,13 @@ fn find_latest_chronicle(output_dir: &std::path::Path) -> Result<PathBuf> {
         )));
     }
 
-    let mut chronicles = Vec::new();
-
-    for entry in fs::read_dir(output_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-
-        if path.is_file() {
-            if let Some(filename) = path.file_name() {
-                if let Some(name) = filename.to_str() {
-                    if name.starts_with("chronicle-") && name.ends_with(".md") {
-                        chronicles.push(path);
-                    }
-                }
-            }
-        }
-    }
-
-    if chronicles.is_empty() {
-        return Err(ChronicleError::Config(
-            "No chronicle files found. Run 'chronicle gen' first.".to_string(),
-        ));
-    }
-
-    // Sort by filename (which includes date)
-    chronicles.sort();
-
-    // Return the last one (most recent)
-    Ok(chronicles.last().unwrap().clone())
+    // Listed oldest first, so the last one is the most recent
+    archive::list(output_dir)?
+        .pop()
+        .map(|chronicle| chronicle.path)
+        .ok_or_else(|| {
+            ChronicleError::Config(
+                "No chronicle files found. Run 'chronicle gen' first.".to_string(),
+            )
+        })
 }
@@ -1,23 +1,86 @@
 use crate::config;
+use crate::display::log;
 use crate::error::Result;
+use crate::state::{self, SourceState};
 use std::fs;
 use std::path::PathBuf;
 
 /// Reset state tracking by deleting the state file
-pub fn reset(config_path: Option<PathBuf>) -> Result<()> {
-    let config_path = config_path.unwrap_or_else(|| PathBuf::from("chronicle.toml"));
+pub fn reset(config_path: Option<PathBuf>, profile: Option<String>) -> Result<()> {
+    let config_path = config::resolve_path(config_path);
 
     // Load config to get state file path
-    let config = config::load(&config_path)?;
+    let config = config::load_profile(&config_path, profile.as_deref())?;
+    let state_file = config.state_path(&config_path);
 
     // Delete state file if it exists
-    if config.state_file.exists() {
-        fs::remove_file(&config.state_file)?;
-        println!("State file deleted: {}", config.state_file.display());
-        println!("Next 'chronicle gen' will generate a full chronicle.");
+    if state_file.exists() {
+        fs::remove_file(&state_file)?;
+        log::info(&format!("State file deleted: {}", state_file.display()));
+        log::info("Next 'chronicle gen' will generate a full chronicle.");
     } else {
-        println!("State file does not exist: {}", config.state_file.display());
-        println!("Nothing to reset.");
+        log::info(&format!(
+            "State file does not exist: {}",
+            state_file.display()
+        ));
+        log::info("Nothing to reset.");
+    }
+
+    Ok(())
+}
+
+/// Show state tracking details: last generation time and tracked sources
+pub fn show(config_path: Option<PathBuf>, profile: Option<String>) -> Result<()> {
+    let config_path = config::resolve_path(config_path);
+
+    // Load config to get state file path
+    let config = config::load_profile(&config_path, profile.as_deref())?;
+    let state_file = config.state_path(&config_path);
+
+    if !state_file.exists() {
+        log::info(&format!(
+            "State file does not exist: {}",
+            state_file.display()
+        ));
+        log::info("Run 'chronicle gen' to start tracking.");
+        return Ok(());
+    }
+
+    let state = state::load(&state_file)?;
+
+    println!("State file: {}", state_file.display());
+    println!("Version: {}", state.version);
+    println!(
+        "Last updated: {}",
+        state.last_updated.format("%Y-%m-%d %H:%M:%S UTC")
+    );
+    println!(
+        "Last generated: {}",
+        state
+            .last_generated
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+            .unwrap_or_else(|| "never".to_string())
+    );
+    if let Some(host) = &state.last_host {
+        println!("Last host: {}", host);
+    }
+
+    let mut sources: Vec<_> = state.sources.iter().collect();
+    sources.sort_by_key(|(name, _)| name.as_str());
+
+    println!("Sources: {}", sources.len());
+    for (name, source) in sources {
+        let kind = match source {
+            SourceState::Git { .. } => "git",
+            SourceState::Todo { .. } => "todo",
+            SourceState::Notes { .. } => "notes",
+        };
+        println!(
+            "  [{}] {} (last checked {})",
+            kind,
+            state::key_path(name).display(),
+            source.last_checked().format("%Y-%m-%d %H:%M:%S UTC")
+        );
     }
 
     Ok(())
@@ -0,0 +1,340 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use ical::parser::ical::component::IcalEvent;
+use ical::property::Property;
+use ical::IcalParser;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::time::Instant;
+
+use crate::collectors::{Collected, Collector, Source, Warnings, Window};
+use crate::config::Config;
+use crate::display::log;
+use crate::error::{ChronicleError, Result};
+use crate::models::{CollectionWarning, Event};
+use crate::state::State;
+
+/// Calendar collector for reading events from ICS files
+pub struct CalendarCollector<'a> {
+    config: &'a Config,
+    warnings: Warnings,
+}
+
+impl<'a> CalendarCollector<'a> {
+    pub fn new(config: &'a Config) -> Self {
+        Self {
+            config,
+            warnings: Warnings::new("calendar"),
+        }
+    }
+
+    /// Collect events from all configured files that start between `since`
+    /// and `until` (now when `until` is None), earliest first
+    pub fn collect_between(
+        &self,
+        since: DateTime<Utc>,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Event>> {
+        let until = until.unwrap_or_else(Utc::now);
+        let mut all_events = Vec::new();
+
+        for calendar_file in &self.config.calendar_files {
+            match self.collect_file(calendar_file) {
+                Ok(events) => {
+                    all_events.extend(
+                        events
+                            .into_iter()
+                            .filter(|event| event.start >= since && event.start <= until),
+                    );
+                }
+                Err(e) => {
+                    self.warnings.skipped(format!(
+                        "Skipping calendar file '{}': {}",
+                        calendar_file.display(),
+                        e
+                    ));
+                }
+            }
+        }
+
+        all_events.sort_by_key(|event| event.start);
+        Ok(all_events)
+    }
+
+    /// Read every event in one ICS file
+    fn collect_file(&self, path: &Path) -> Result<Vec<Event>> {
+        let file = File::open(path).map_err(|e| {
+            ChronicleError::Collector(format!(
+                "Cannot read calendar file '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        parse_events(BufReader::new(file), path, self.config.timezone)
+    }
+}
+
+impl Collector for CalendarCollector<'_> {
+    type Item = Event;
+
+    fn collect(&self, _state: &mut State, window: Window) -> Result<Vec<Event>> {
+        // Events are chosen by time alone, so there's nothing to track
+        self.collect_between(window.since, window.until)
+    }
+
+    fn take_warnings(&self) -> Vec<CollectionWarning> {
+        self.warnings.take()
+    }
+}
+
+impl Source for CalendarCollector<'_> {
+    fn name(&self) -> &'static str {
+        "calendar"
+    }
+
+    fn collect_into(
+        &self,
+        state: &mut State,
+        window: Window,
+        collected: &mut Collected,
+    ) -> Result<()> {
+        let started = Instant::now();
+        let events = self.collect(state, window)?;
+        log::detail(&format!(
+            "calendar: read {} files, found {} events ({:.0?})",
+            self.config.calendar_files.len(),
+            events.len(),
+            started.elapsed()
+        ));
+
+        collected.events.extend(events);
+        collected.warnings.extend(self.take_warnings());
+        Ok(())
+    }
+}
+
+/// Events from ICS content, leaving out cancelled ones and ones without a
+/// start
+///
+/// Times without a zone are read in `timezone`, or UTC when None.
+/// Recurring events are not expanded.
+fn parse_events<B: BufRead>(reader: B, path: &Path, timezone: Option<Tz>) -> Result<Vec<Event>> {
+    let mut events = Vec::new();
+
+    for calendar in IcalParser::new(reader) {
+        let calendar = calendar.map_err(|e| {
+            ChronicleError::Collector(format!(
+                "Cannot parse calendar file '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        for event in &calendar.events {
+            if property(event, "STATUS").is_some_and(|status| status == "CANCELLED") {
+                continue;
+            }
+            let Some((start, all_day)) = event
+                .properties
+                .iter()
+                .find(|p| p.name == "DTSTART")
+                .and_then(|p| parse_time(p, timezone))
+            else {
+                continue;
+            };
+            let end = event
+                .properties
+                .iter()
+                .find(|p| p.name == "DTEND")
+                .and_then(|p| parse_time(p, timezone))
+                .map(|(end, _)| end);
+
+            events.push(Event {
+                file: path.to_path_buf(),
+                summary: property(event, "SUMMARY")
+                    .map(|summary| unescape(&summary))
+                    .unwrap_or_else(|| "(no title)".to_string()),
+                start,
+                end,
+                all_day,
+                location: property(event, "LOCATION")
+                    .map(|location| unescape(&location))
+                    .filter(|location| !location.is_empty()),
+            });
+        }
+    }
+
+    Ok(events)
+}
+
+/// Value of an event's property `name`, if set
+fn property(event: &IcalEvent, name: &str) -> Option<String> {
+    event
+        .properties
+        .iter()
+        .find(|p| p.name == name)
+        .and_then(|p| p.value.clone())
+}
+
+/// A `DTSTART`/`DTEND` value and whether it is a whole-day date
+///
+/// UTC times end in `Z`; other times are in their `TZID` zone, or
+/// `timezone` for floating times and zones chrono-tz doesn't know. Without
+/// a `timezone` those are UTC, the zone the renderer shows them in.
+fn parse_time(property: &Property, timezone: Option<Tz>) -> Option<(DateTime<Utc>, bool)> {
+    let value = property.value.as_deref()?.trim();
+    let zone = property
+        .params
+        .iter()
+        .flatten()
+        .find(|(name, _)| name == "TZID")
+        .and_then(|(_, values)| values.first())
+        .and_then(|tzid| tzid.parse::<Tz>().ok())
+        .or(timezone)
+        .unwrap_or(Tz::UTC);
+
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y%m%d") {
+        return localize(date.and_hms_opt(0, 0, 0)?, zone).map(|start| (start, true));
+    }
+    if let Some(utc) = value.strip_suffix('Z') {
+        let time = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some((time.and_utc(), false));
+    }
+    let time = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    localize(time, zone).map(|start| (start, false))
+}
+
+/// A wall-clock time in `zone` as UTC
+fn localize(time: NaiveDateTime, zone: Tz) -> Option<DateTime<Utc>> {
+    zone.from_local_datetime(&time)
+        .earliest()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Undo ICS text escaping (`\,`, `\;`, `\n`, `\\`)
+fn unescape(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => output.push(' '),
+            Some(other) => output.push(other),
+            None => output.push('\\'),
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const CALENDAR: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+BEGIN:VEVENT\r
+UID:1\r
+DTSTART:20240115T090000Z\r
+DTEND:20240115T093000Z\r
+SUMMARY:Standup\r
+LOCATION:Room 1\\, 2nd floor\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:2\r
+DTSTART;TZID=Europe/Berlin:20240115T140000\r
+SUMMARY:Design review\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:3\r
+DTSTART;VALUE=DATE:20240116\r
+SUMMARY:Offsite\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:4\r
+DTSTART:20240115T100000Z\r
+SUMMARY:Cancelled sync\r
+STATUS:CANCELLED\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+    fn utc(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_events() {
+        let events =
+            parse_events(CALENDAR.as_bytes(), Path::new("work.ics"), Some(Tz::UTC)).unwrap();
+
+        assert_eq!(events.len(), 3);
+
+        assert_eq!(events[0].summary, "Standup");
+        assert_eq!(events[0].start, utc("2024-01-15T09:00:00Z"));
+        assert_eq!(events[0].end, Some(utc("2024-01-15T09:30:00Z")));
+        assert_eq!(events[0].location.as_deref(), Some("Room 1, 2nd floor"));
+        assert!(!events[0].all_day);
+
+        // Zoned times convert to UTC
+        assert_eq!(events[1].start, utc("2024-01-15T13:00:00Z"));
+
+        // Dates are all-day events starting at midnight in `timezone`
+        assert_eq!(events[2].start, utc("2024-01-16T00:00:00Z"));
+        assert!(events[2].all_day);
+    }
+
+    #[test]
+    fn test_floating_times_default_to_utc() {
+        let calendar = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VEVENT\r\n\
+                        DTSTART:20240115T090000\r\nSUMMARY:Standup\r\nEND:VEVENT\r\n\
+                        END:VCALENDAR\r\n";
+
+        let events = parse_events(calendar.as_bytes(), Path::new("work.ics"), None).unwrap();
+        assert_eq!(events[0].start, utc("2024-01-15T09:00:00Z"));
+
+        let berlin = Some("Europe/Berlin".parse().unwrap());
+        let events = parse_events(calendar.as_bytes(), Path::new("work.ics"), berlin).unwrap();
+        assert_eq!(events[0].start, utc("2024-01-15T08:00:00Z"));
+    }
+
+    #[test]
+    fn test_collect_filters_to_window() {
+        let temp_dir = TempDir::new().unwrap();
+        let calendar_file = temp_dir.path().join("work.ics");
+        fs::write(&calendar_file, CALENDAR).unwrap();
+
+        let config = Config {
+            calendar_files: vec![calendar_file, temp_dir.path().join("missing.ics")],
+            timezone: Some(Tz::UTC),
+            ..Config::default()
+        };
+        let collector = CalendarCollector::new(&config);
+
+        let since = utc("2024-01-15T00:00:00Z");
+        let events = collector
+            .collect_between(since, Some(since + Duration::hours(24)))
+            .unwrap();
+
+        let summaries: Vec<&str> = events.iter().map(|e| e.summary.as_str()).collect();
+        assert_eq!(summaries, vec!["Standup", "Design review", "Offsite"]);
+
+        let warnings = collector.take_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.starts_with("Skipping calendar file"));
+    }
+
+    #[test]
+    fn test_unescape() {
+        assert_eq!(unescape("a\\, b\\; c\\nd\\\\e"), "a, b; c d\\e");
+    }
+}
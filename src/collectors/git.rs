@@ -1,39 +1,147 @@
 use chrono::{DateTime, TimeZone, Utc};
-use git2::{BranchType, Oid, Repository as Git2Repository};
-use std::collections::{HashMap, HashSet};
+use git2::{BranchType, DiffDelta, Oid, Repository as Git2Repository, Sort, Status, StatusOptions};
+use regex::RegexSet;
+use std::collections::HashMap;
+use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 
-use crate::config::Config;
+use crate::collectors::{Collected, Collector, Source, Warnings, Window};
+use crate::config::{Config, Limits, RepoConfig};
+use crate::display::log;
 use crate::error::{ChronicleError, Result};
-use crate::models::{Branch, ChangeKind, Commit, Repository};
+use crate::models::{Branch, ChangeKind, CollectionWarning, Commit, Repository, WorkInProgress};
 use crate::state::{self, BranchState, SourceState, State};
 
+/// Branch name reported for the history of a detached HEAD
+const DETACHED_HEAD: &str = "(detached HEAD)";
+
+/// Compiled `commit_include_patterns` and `commit_exclude_patterns`
+struct CommitFilter {
+    /// None when every commit is included
+    include: Option<RegexSet>,
+    exclude: RegexSet,
+}
+
+impl CommitFilter {
+    fn new(config: &Config) -> Result<Self> {
+        let include = if config.commit_include_patterns.is_empty() {
+            None
+        } else {
+            Some(regex_set(
+                "commit_include_patterns",
+                &config.commit_include_patterns,
+            )?)
+        };
+
+        Ok(Self {
+            include,
+            exclude: regex_set("commit_exclude_patterns", &config.commit_exclude_patterns)?,
+        })
+    }
+
+    /// Whether a commit with the full `message` is chronicled
+    fn matches(&self, message: &str) -> bool {
+        self.include
+            .as_ref()
+            .is_none_or(|set| set.is_match(message))
+            && !self.exclude.is_match(message)
+    }
+}
+
+/// Compile the regexes of config option `name`
+fn regex_set(name: &str, patterns: &[String]) -> Result<RegexSet> {
+    RegexSet::new(patterns).map_err(|e| ChronicleError::Config(format!("{}: {}", name, e)))
+}
+
+/// What a commit changed
+struct CommitChanges {
+    files: Vec<PathBuf>,
+    binary_files: Vec<PathBuf>,
+    additions: usize,
+    deletions: usize,
+}
+
+/// Which commits to collect from a repository's branches
+struct CommitScope<'f> {
+    since: DateTime<Utc>,
+    until: Option<DateTime<Utc>>,
+    limits: Limits,
+    filter: &'f CommitFilter,
+    /// Only commits by this identity, with `author_me`
+    author: Option<Identity>,
+}
+
+/// The user's `user.name` and `user.email` from a repository's Git config
+struct Identity {
+    name: Option<String>,
+    email: Option<String>,
+}
+
+impl Identity {
+    /// Identity configured for `repo`, including global and system config
+    fn of(repo: &Git2Repository) -> Option<Self> {
+        let config = repo.config().ok()?;
+        let name = config.get_string("user.name").ok();
+        let email = config.get_string("user.email").ok();
+        (name.is_some() || email.is_some()).then_some(Self { name, email })
+    }
+
+    /// Whether a commit `signature` is this identity's, by email or name
+    fn matches(&self, signature: &git2::Signature) -> bool {
+        let same_email = self.email.as_deref().is_some_and(|email| {
+            signature
+                .email()
+                .is_some_and(|other| other.eq_ignore_ascii_case(email))
+        });
+        same_email || self.name.is_some() && signature.name() == self.name.as_deref()
+    }
+}
+
 /// Git collector for extracting commits and branch information
 pub struct GitCollector<'a> {
     config: &'a Config,
+    warnings: Warnings,
 }
 
 impl<'a> GitCollector<'a> {
     pub fn new(config: &'a Config) -> Self {
-        Self { config }
+        Self {
+            config,
+            warnings: Warnings::new("git"),
+        }
     }
 
-    /// Collect Git activity from all configured repositories
-    pub fn collect(&self, state: &mut State, since: DateTime<Utc>) -> Result<Vec<Repository>> {
+    /// Collect Git activity from all configured repositories, committed
+    /// between `since` and `until` (open-ended when `until` is None)
+    pub fn collect_between(
+        &self,
+        state: &mut State,
+        since: DateTime<Utc>,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Repository>> {
         let mut repositories = Vec::new();
-
-        for repo_path in &self.config.repos {
-            match self.collect_repository(repo_path, state, since) {
+        let filter = CommitFilter::new(self.config)?;
+
+        for repo in &self.config.repos {
+            let scope = CommitScope {
+                since,
+                until,
+                limits: repo.limits(&self.config.limits),
+                filter: &filter,
+                author: None,
+            };
+            match self.collect_repository(repo, state, scope) {
                 Ok(Some(repo)) => repositories.push(repo),
                 Ok(None) => {
                     // No activity in this repository
                 }
                 Err(e) => {
-                    eprintln!(
-                        "Warning: Skipping repository '{}': {}",
-                        repo_path.display(),
+                    self.warnings.skipped(format!(
+                        "Skipping repository '{}': {}",
+                        repo.path.display(),
                         e
-                    );
+                    ));
                 }
             }
         }
@@ -44,14 +152,39 @@ impl<'a> GitCollector<'a> {
     /// Collect activity from a single repository
     fn collect_repository(
         &self,
-        repo_path: &Path,
+        repo: &RepoConfig,
         state: &mut State,
-        since: DateTime<Utc>,
+        scope: CommitScope,
     ) -> Result<Option<Repository>> {
+        let repo_path = repo.path.as_path();
         let git_repo = self.open_repository(repo_path)?;
 
-        // Derive repository name from path, handling relative paths like "."
-        let repo_name = if repo_path == Path::new(".") {
+        // Resolved per repository, as identities can differ between them
+        let author = if self.config.author_me {
+            let identity = Identity::of(&git_repo);
+            if identity.is_none() {
+                self.warnings.degraded(format!(
+                    "No user.name or user.email in the Git config of '{}', including every commit",
+                    repo_path.display()
+                ));
+            }
+            identity
+        } else {
+            None
+        };
+        let scope = &CommitScope { author, ..scope };
+
+        // Derive repository name from the working directory, so linked
+        // worktrees are named after themselves and a path to `.git` after
+        // the project; bare repositories fall back to the configured path
+        let workdir_name = git_repo
+            .workdir()
+            .and_then(|workdir| workdir.file_name())
+            .and_then(|name| name.to_str())
+            .map(String::from);
+        let repo_name = if let Some(name) = workdir_name {
+            name
+        } else if repo_path == Path::new(".") {
             // For ".", use the current directory name
             std::env::current_dir()
                 .ok()
@@ -66,6 +199,7 @@ impl<'a> GitCollector<'a> {
         } else {
             "unknown".to_string()
         };
+        let description = repository_description(&git_repo);
 
         // Get default branch (HEAD reference)
         let head = git_repo.head().map_err(|e| {
@@ -76,15 +210,58 @@ impl<'a> GitCollector<'a> {
             )))
         })?;
 
-        let default_branch = if head.is_branch() {
-            head.shorthand().unwrap_or("main").to_string()
-        } else {
-            "main".to_string()
+        // A detached HEAD is not on any branch
+        let current_branch = head
+            .is_branch()
+            .then(|| head.shorthand().unwrap_or("main").to_string());
+
+        // Branches seen on previous runs, if any
+        let source_key = state::path_key(repo_path);
+        let branch_states = match state::get_source(state, &source_key) {
+            Some(SourceState::Git { branches, .. }) => Some(branches),
+            _ => None,
         };
 
+        // The repository's main line, as opposed to the branch checked out
+        let configured_branch = repo.default_branch.as_deref().filter(|name| {
+            let exists = git_repo.find_branch(name, BranchType::Local).is_ok();
+            if !exists {
+                self.warnings.degraded(format!(
+                    "Configured default_branch '{}' not found in '{}', detecting it instead",
+                    name,
+                    repo_path.display()
+                ));
+            }
+            exists
+        });
+        let default_branch = configured_branch
+            .map(String::from)
+            .or_else(|| remote_default_branch(&git_repo))
+            .or_else(|| current_branch.clone());
+
         // Collect branches with commits
-        let branches =
-            self.collect_branches(&git_repo, &default_branch, state, since, repo_path)?;
+        let mut branches =
+            self.collect_branches(&git_repo, default_branch.as_deref(), branch_states, scope)?;
+
+        // Report the checked-out history of a detached HEAD on its own
+        let current_branch = match current_branch {
+            Some(branch) => branch,
+            None => {
+                if let Some(head_oid) = head.target() {
+                    let commits = self.collect_commits(&git_repo, head_oid, scope)?;
+                    branches.push(Branch {
+                        name: DETACHED_HEAD.to_string(),
+                        change: self.determine_branch_change(DETACHED_HEAD, branch_states),
+                        ahead: 0,
+                        behind: 0,
+                        first_seen: branch_first_seen(DETACHED_HEAD, branch_states),
+                        commits,
+                    });
+                }
+                DETACHED_HEAD.to_string()
+            }
+        };
+        let default_branch = default_branch.unwrap_or_else(|| current_branch.clone());
 
         // Filter out branches with no commits
         let branches: Vec<Branch> = branches
@@ -93,18 +270,84 @@ impl<'a> GitCollector<'a> {
             .collect();
 
         if branches.is_empty() {
-            return Ok(None);
+            if !self.config.include_wip {
+                return Ok(None);
+            }
+
+            // No new commits: fall back to uncommitted changes, if any
+            let wip = self.collect_work_in_progress(&git_repo)?;
+            return Ok((!wip.is_empty()).then(|| Repository {
+                path: repo_path.to_path_buf(),
+                name: repo_name,
+                description,
+                default_branch,
+                current_branch,
+                branches,
+                wip: Some(wip),
+                lines_added: 0,
+                lines_removed: 0,
+            }));
         }
 
         // Update state
         self.update_state(state, repo_path, &default_branch, &branches);
 
-        Ok(Some(Repository {
+        let mut repository = Repository {
             path: repo_path.to_path_buf(),
             name: repo_name,
+            description,
             default_branch,
+            current_branch,
             branches,
-        }))
+            wip: None,
+            lines_added: 0,
+            lines_removed: 0,
+        };
+        repository.tally_lines();
+
+        Ok(Some(repository))
+    }
+
+    /// Count uncommitted changes in the working tree and index
+    ///
+    /// Bare repositories have no working tree and report no changes.
+    fn collect_work_in_progress(&self, repo: &Git2Repository) -> Result<WorkInProgress> {
+        let mut wip = WorkInProgress::default();
+
+        if repo.is_bare() {
+            return Ok(wip);
+        }
+
+        let mut options = StatusOptions::new();
+        options
+            .include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .include_ignored(false);
+
+        let statuses = repo.statuses(Some(&mut options)).map_err(|e| {
+            ChronicleError::Collector(format!("Failed to read working tree status: {}", e))
+        })?;
+
+        for entry in statuses.iter() {
+            let status = entry.status();
+
+            if status.intersects(Status::INDEX_NEW | Status::WT_NEW) {
+                wip.added += 1;
+            } else if status.intersects(Status::INDEX_DELETED | Status::WT_DELETED) {
+                wip.deleted += 1;
+            } else if status.intersects(
+                Status::INDEX_MODIFIED
+                    | Status::WT_MODIFIED
+                    | Status::INDEX_RENAMED
+                    | Status::WT_RENAMED
+                    | Status::INDEX_TYPECHANGE
+                    | Status::WT_TYPECHANGE,
+            ) {
+                wip.modified += 1;
+            }
+        }
+
+        Ok(wip)
     }
 
     /// Open a Git repository
@@ -119,26 +362,19 @@ impl<'a> GitCollector<'a> {
     }
 
     /// Collect branches and their commits
+    ///
+    /// Without a `default_branch` (detached HEAD and no `origin/HEAD`),
+    /// branches with no new commits are skipped and ahead/behind counts are
+    /// not computed.
     fn collect_branches(
         &self,
         repo: &Git2Repository,
-        default_branch: &str,
-        state: &State,
-        since: DateTime<Utc>,
-        repo_path: &Path,
+        default_branch: Option<&str>,
+        branch_states: Option<&HashMap<String, BranchState>>,
+        scope: &CommitScope,
     ) -> Result<Vec<Branch>> {
         let mut branches = Vec::new();
 
-        // Get source state for this repository
-        let source_key = repo_path.to_string_lossy().to_string();
-        let source_state = state::get_source(state, &source_key);
-
-        // Get branch states if available
-        let branch_states = match source_state {
-            Some(SourceState::Git { branches, .. }) => Some(branches),
-            _ => None,
-        };
-
         // Iterate through all local branches
         let git_branches = repo
             .branches(Some(BranchType::Local))
@@ -163,21 +399,23 @@ impl<'a> GitCollector<'a> {
             })?;
 
             // Collect commits for this branch
-            let commits = self.collect_commits(repo, branch_oid, since)?;
+            let commits = self.collect_commits(repo, branch_oid, scope)?;
 
-            if commits.is_empty() && branch_name != default_branch {
+            if commits.is_empty() && default_branch != Some(branch_name.as_str()) {
                 // Skip branches with no new commits (except default branch)
                 continue;
             }
 
             // Determine if this is a new branch
             let change = self.determine_branch_change(&branch_name, branch_states);
+            let first_seen = branch_first_seen(&branch_name, branch_states);
 
             // Calculate ahead/behind relative to default branch
-            let (ahead, behind) = if branch_name != default_branch {
-                self.calculate_ahead_behind(repo, default_branch, &branch_name)?
-            } else {
-                (0, 0)
+            let (ahead, behind) = match default_branch {
+                Some(default_branch) if branch_name != default_branch => {
+                    self.calculate_ahead_behind(repo, default_branch, &branch_name)?
+                }
+                _ => (0, 0),
             };
 
             branches.push(Branch {
@@ -185,6 +423,7 @@ impl<'a> GitCollector<'a> {
                 change,
                 ahead,
                 behind,
+                first_seen,
                 commits,
             });
         }
@@ -192,12 +431,13 @@ impl<'a> GitCollector<'a> {
         Ok(branches)
     }
 
-    /// Collect commits from a branch since a specific time
+    /// Collect commits from a branch within the scope's time window,
+    /// skipping those its message filters reject
     fn collect_commits(
         &self,
         repo: &Git2Repository,
         branch_oid: Oid,
-        since: DateTime<Utc>,
+        scope: &CommitScope,
     ) -> Result<Vec<Commit>> {
         let mut revwalk = repo
             .revwalk()
@@ -207,11 +447,19 @@ impl<'a> GitCollector<'a> {
             ChronicleError::Collector(format!("Failed to push branch to revwalk: {}", e))
         })?;
 
+        // Newest first across all parents, so the first commit older than
+        // `since` ends the walk even through merges of older-dated branches
+        revwalk
+            .set_sorting(Sort::TIME)
+            .map_err(|e| ChronicleError::Collector(format!("Failed to sort revwalk: {}", e)))?;
+
         let mut commits = Vec::new();
-        let mut seen_files = HashSet::new();
+
+        // Canonical author identities from .mailmap (empty if there is none)
+        let mailmap = repo.mailmap().ok();
 
         for oid_result in revwalk {
-            if commits.len() >= self.config.limits.max_commits {
+            if commits.len() >= scope.limits.max_commits {
                 break;
             }
 
@@ -229,47 +477,75 @@ impl<'a> GitCollector<'a> {
                 .single()
                 .ok_or_else(|| ChronicleError::Collector("Invalid commit timestamp".to_string()))?;
 
-            if commit_time < since {
+            if commit_time < scope.since {
                 break;
             }
 
+            // Newer than the window: keep walking back towards it
+            if scope.until.is_some_and(|until| commit_time > until) {
+                continue;
+            }
+
+            let full_message = git_commit.message().unwrap_or("(no message)");
+            if !scope.filter.matches(full_message) {
+                continue;
+            }
+            if let Some(me) = &scope.author {
+                let mailmapped = mailmap
+                    .as_ref()
+                    .and_then(|mailmap| git_commit.author_with_mailmap(mailmap).ok());
+                if !me.matches(&git_commit.author())
+                    && !mailmapped.is_some_and(|signature| me.matches(&signature))
+                {
+                    continue;
+                }
+            }
+
             // Extract commit information
             let hash = format!("{:.7}", oid);
-            let message = git_commit
-                .message()
-                .unwrap_or("(no message)")
-                .lines()
-                .next()
-                .unwrap_or("")
-                .chars()
-                .take(72)
-                .collect();
+            let (message, body) = split_message(full_message);
 
-            let author = git_commit.author().name().unwrap_or("Unknown").to_string();
+            let author = mailmap
+                .as_ref()
+                .and_then(|mailmap| git_commit.author_with_mailmap(mailmap).ok())
+                .and_then(|signature| signature.name().map(String::from))
+                .or_else(|| git_commit.author().name().map(String::from))
+                .unwrap_or_else(|| "Unknown".to_string());
 
-            // Collect changed files
-            let files = self.collect_commit_files(repo, &git_commit, &mut seen_files)?;
+            // Collect changed files and line counts
+            let changes = self.collect_commit_files(repo, &git_commit)?;
+
+            // Unsigned commits (and repos without signing) have no signature
+            let signed = repo.extract_signature(&git_commit.id(), None).is_ok();
 
             commits.push(Commit {
                 hash,
                 message,
+                body,
                 author,
                 timestamp: commit_time,
-                files,
+                files: changes.files,
+                binary_files: changes.binary_files,
+                additions: changes.additions,
+                deletions: changes.deletions,
+                signed,
             });
         }
 
         Ok(commits)
     }
 
-    /// Collect files changed in a commit
+    /// Collect all files changed in a commit, with its line counts
+    ///
+    /// Nothing is capped here: `limits.max_changed_files` bounds the list
+    /// shown per branch at render time, so the "more files" count is exact.
     fn collect_commit_files(
         &self,
         repo: &Git2Repository,
         commit: &git2::Commit,
-        seen_files: &mut HashSet<PathBuf>,
-    ) -> Result<Vec<PathBuf>> {
+    ) -> Result<CommitChanges> {
         let mut files = Vec::new();
+        let mut binary_files = Vec::new();
 
         let commit_tree = commit
             .tree()
@@ -283,14 +559,11 @@ impl<'a> GitCollector<'a> {
 
         diff.foreach(
             &mut |delta, _| {
-                if seen_files.len() >= self.config.limits.max_changed_files {
-                    return true; // Stop iterating
-                }
-
                 if let Some(path) = delta.new_file().path() {
-                    let path_buf = path.to_path_buf();
-                    if seen_files.insert(path_buf.clone()) {
-                        files.push(path_buf);
+                    if is_binary(repo, &delta) {
+                        binary_files.push(path.to_path_buf());
+                    } else {
+                        files.push(path.to_path_buf());
                     }
                 }
                 true
@@ -301,10 +574,22 @@ impl<'a> GitCollector<'a> {
         )
         .map_err(|e| ChronicleError::Collector(format!("Failed to iterate diff: {}", e)))?;
 
-        Ok(files)
+        let stats = diff
+            .stats()
+            .map_err(|e| ChronicleError::Collector(format!("Failed to count lines: {}", e)))?;
+
+        Ok(CommitChanges {
+            files,
+            binary_files,
+            additions: stats.insertions(),
+            deletions: stats.deletions(),
+        })
     }
 
     /// Calculate commits ahead and behind between two branches
+    ///
+    /// A base branch that doesn't exist (or has no commits) is reported as
+    /// a warning with (0, 0), so one missing branch doesn't fail the repo.
     fn calculate_ahead_behind(
         &self,
         repo: &Git2Repository,
@@ -312,17 +597,20 @@ impl<'a> GitCollector<'a> {
         compare_branch: &str,
     ) -> Result<(usize, usize)> {
         // Get OIDs for both branches
-        let base_ref = repo
+        let base_oid = match repo
             .find_branch(base_branch, BranchType::Local)
-            .map_err(|e| {
-                ChronicleError::Collector(format!(
-                    "Failed to find base branch {}: {}",
-                    base_branch, e
-                ))
-            })?;
-        let base_oid = base_ref.get().target().ok_or_else(|| {
-            ChronicleError::Collector(format!("Base branch {} has no target", base_branch))
-        })?;
+            .ok()
+            .and_then(|base_ref| base_ref.get().target())
+        {
+            Some(oid) => oid,
+            None => {
+                self.warnings.degraded(format!(
+                    "Base branch {} not found, skipping ahead/behind for {}",
+                    base_branch, compare_branch
+                ));
+                return Ok((0, 0));
+            }
+        };
 
         let compare_ref = repo
             .find_branch(compare_branch, BranchType::Local)
@@ -372,7 +660,13 @@ impl<'a> GitCollector<'a> {
         default_branch: &str,
         branches: &[Branch],
     ) {
-        let source_key = repo_path.to_string_lossy().to_string();
+        let source_key = state::path_key(repo_path);
+
+        // Branch states from the previous run, to carry first_seen forward
+        let previous = match state::get_source(state, &source_key) {
+            Some(SourceState::Git { branches, .. }) => Some(branches),
+            _ => None,
+        };
 
         // Build branch states map
         let mut branch_states = HashMap::new();
@@ -386,8 +680,8 @@ impl<'a> GitCollector<'a> {
             let first_seen = if branch.change == ChangeKind::New {
                 Some(Utc::now())
             } else {
-                // Try to preserve existing first_seen
-                None
+                // Preserve the first_seen recorded when the branch appeared
+                branch_first_seen(&branch.name, previous)
             };
 
             branch_states.insert(
@@ -410,6 +704,147 @@ impl<'a> GitCollector<'a> {
     }
 }
 
+impl Collector for GitCollector<'_> {
+    type Item = Repository;
+
+    fn collect(&self, state: &mut State, window: Window) -> Result<Vec<Repository>> {
+        self.collect_between(state, window.since, window.until)
+    }
+
+    fn take_warnings(&self) -> Vec<CollectionWarning> {
+        self.warnings.take()
+    }
+}
+
+impl Source for GitCollector<'_> {
+    fn name(&self) -> &'static str {
+        "git"
+    }
+
+    fn collect_into(
+        &self,
+        state: &mut State,
+        window: Window,
+        collected: &mut Collected,
+    ) -> Result<()> {
+        let started = Instant::now();
+        let repositories = self.collect(state, window)?;
+        log::detail(&format!(
+            "git: scanned {} repositories, found {} commits in {} active ({:.0?})",
+            self.config.repos.len(),
+            repositories.iter().map(|r| r.commit_count()).sum::<usize>(),
+            repositories.len(),
+            started.elapsed()
+        ));
+
+        collected.repositories.extend(repositories);
+        collected.warnings.extend(self.take_warnings());
+        Ok(())
+    }
+}
+
+/// Local branch that `origin/HEAD` points at, if the clone recorded one
+fn remote_default_branch(repo: &Git2Repository) -> Option<String> {
+    let origin_head = repo.find_reference("refs/remotes/origin/HEAD").ok()?;
+    let name = origin_head
+        .symbolic_target()?
+        .strip_prefix("refs/remotes/origin/")?;
+    repo.find_branch(name, BranchType::Local)
+        .is_ok()
+        .then(|| name.to_string())
+}
+
+/// When a branch was first seen on a previous run, if it was
+fn branch_first_seen(
+    branch_name: &str,
+    branch_states: Option<&HashMap<String, BranchState>>,
+) -> Option<DateTime<Utc>> {
+    branch_states
+        .and_then(|branches| branches.get(branch_name))
+        .and_then(|branch_state| branch_state.first_seen)
+}
+
+/// Whether a changed file is binary, loading its blob when the diff has not
+/// decided yet
+fn is_binary(repo: &Git2Repository, delta: &DiffDelta) -> bool {
+    if delta.flags().is_binary() {
+        return true;
+    }
+    if delta.flags().is_not_binary() {
+        return false;
+    }
+
+    // Deleted files only have content on the old side
+    let file = if delta.new_file().id().is_zero() {
+        delta.old_file()
+    } else {
+        delta.new_file()
+    };
+    repo.find_blob(file.id())
+        .map(|blob| blob.is_binary())
+        .unwrap_or(false)
+}
+
+/// Description Git keeps for the repository, else the title of its README
+fn repository_description(repo: &Git2Repository) -> Option<String> {
+    // `git init` writes a placeholder asking to be edited
+    let from_git = fs::read_to_string(repo.path().join("description"))
+        .ok()
+        .and_then(|content| first_line(&content))
+        .filter(|line| !line.starts_with("Unnamed repository"));
+
+    from_git.or_else(|| {
+        let workdir = repo.workdir()?;
+        ["README.md", "README", "README.txt", "readme.md"]
+            .iter()
+            .find_map(|name| fs::read_to_string(workdir.join(name)).ok())
+            .and_then(|content| readme_title(&content))
+    })
+}
+
+/// First non-empty line of `content`
+fn first_line(content: &str) -> Option<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(String::from)
+}
+
+/// Title of a README: its first line of prose, without heading marks
+///
+/// Front matter, badges, images, HTML and rules are skipped, so a README
+/// opening with a row of badges still yields its heading.
+fn readme_title(content: &str) -> Option<String> {
+    let mut lines = content.lines().map(str::trim).peekable();
+
+    // YAML front matter runs up to the next `---`
+    if lines.peek() == Some(&"---") {
+        lines.next();
+        lines.by_ref().find(|line| *line == "---")?;
+    }
+
+    lines
+        .map(|line| line.trim_start_matches('#').trim())
+        .find(|line| {
+            !line.is_empty()
+                && !["[", "<", "!", "---", "==="]
+                    .iter()
+                    .any(|prefix| line.starts_with(prefix))
+        })
+        .map(String::from)
+}
+
+/// Split a commit message into its subject line and body, if any
+fn split_message(message: &str) -> (String, Option<String>) {
+    let (subject, body) = message.split_once('\n').unwrap_or((message, ""));
+    let body = body.trim();
+    (
+        subject.trim_end().to_string(),
+        (!body.is_empty()).then(|| body.to_string()),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -481,13 +916,16 @@ mod tests {
 
     #[test]
     fn test_collect_from_empty_config() {
-        let mut config = Config::default();
-        config.repos = vec![]; // Override default to test empty repos
+        // Override default to test empty repos
+        let config = Config {
+            repos: vec![],
+            ..Config::default()
+        };
         let collector = GitCollector::new(&config);
         let mut state = State::default();
         let since = Utc::now();
 
-        let result = collector.collect(&mut state, since);
+        let result = collector.collect_between(&mut state, since, None);
         assert!(result.is_ok());
         assert_eq!(result.unwrap().len(), 0);
     }
@@ -496,14 +934,17 @@ mod tests {
     fn test_collect_repository_with_commits() {
         let (_temp_dir, repo_path) = create_test_repo();
 
-        let mut config = Config::default();
-        config.repos = vec![repo_path.clone()]; // Only test repo, not default "."
+        // Only test repo, not default "."
+        let config = Config {
+            repos: vec![repo_path.clone().into()],
+            ..Config::default()
+        };
 
         let collector = GitCollector::new(&config);
         let mut state = State::default();
         let since = Utc::now() - chrono::Duration::hours(1);
 
-        let result = collector.collect(&mut state, since);
+        let result = collector.collect_between(&mut state, since, None);
         assert!(result.is_ok());
 
         let repos = result.unwrap();
@@ -511,4 +952,767 @@ mod tests {
         assert_eq!(repos[0].branches.len(), 1);
         assert!(!repos[0].branches[0].commits.is_empty());
     }
+
+    #[test]
+    fn test_collect_respects_per_repo_max_commits() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        for i in 0..3 {
+            std::fs::write(repo_path.join("test.txt"), format!("change {}", i)).unwrap();
+            Command::new("git")
+                .args(["commit", "-am", &format!("Change {}", i)])
+                .current_dir(&repo_path)
+                .output()
+                .unwrap();
+        }
+
+        let config = Config {
+            repos: vec![RepoConfig {
+                max_commits: Some(2),
+                ..RepoConfig::from(repo_path.clone())
+            }],
+            ..Config::default()
+        };
+
+        let collector = GitCollector::new(&config);
+        let mut state = State::default();
+        let since = Utc::now() - chrono::Duration::hours(1);
+
+        let repos = collector.collect_between(&mut state, since, None).unwrap();
+        assert_eq!(repos[0].commit_count(), 2);
+    }
+
+    #[test]
+    fn test_collect_resolves_authors_with_mailmap() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        std::fs::write(
+            repo_path.join(".mailmap"),
+            "Canonical Name <test@example.com>\n",
+        )
+        .unwrap();
+
+        let config = Config {
+            repos: vec![repo_path.clone().into()],
+            ..Config::default()
+        };
+
+        let collector = GitCollector::new(&config);
+        let mut state = State::default();
+        let since = Utc::now() - chrono::Duration::hours(1);
+
+        let repos = collector.collect_between(&mut state, since, None).unwrap();
+        assert_eq!(repos[0].branches[0].commits[0].author, "Canonical Name");
+
+        // Without a mailmap the raw name is used
+        std::fs::remove_file(repo_path.join(".mailmap")).unwrap();
+        let repos = collector
+            .collect_between(&mut State::default(), since, None)
+            .unwrap();
+        assert_eq!(repos[0].branches[0].commits[0].author, "Test User");
+    }
+
+    /// Commit all changes in `repo_path` with author and committer date `date`
+    fn commit_at(repo_path: &Path, message: &str, date: DateTime<Utc>) {
+        let date = date.to_rfc3339();
+        Command::new("git")
+            .args(["commit", "-qam", message])
+            .env("GIT_AUTHOR_DATE", &date)
+            .env("GIT_COMMITTER_DATE", &date)
+            .current_dir(repo_path)
+            .output()
+            .unwrap();
+    }
+
+    fn commit_messages(repos: &[Repository]) -> Vec<String> {
+        repos
+            .iter()
+            .flat_map(|repo| &repo.branches)
+            .flat_map(|branch| &branch.commits)
+            .map(|commit| commit.message.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_calculate_ahead_behind_tolerates_missing_base() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        Command::new("git")
+            .args(["branch", "feature"])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+
+        let config = Config::default();
+        let collector = GitCollector::new(&config);
+        let repo = collector.open_repository(&repo_path).unwrap();
+
+        let result = collector.calculate_ahead_behind(&repo, "does-not-exist", "feature");
+        assert_eq!(result.unwrap(), (0, 0));
+        let warnings = collector.take_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].source, "git");
+
+        // A missing compare branch is still an error
+        assert!(collector
+            .calculate_ahead_behind(&repo, "feature", "does-not-exist")
+            .is_err());
+    }
+
+    #[test]
+    fn test_update_state_preserves_first_seen() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        let config = Config {
+            repos: vec![repo_path.clone().into()],
+            ..Config::default()
+        };
+        let collector = GitCollector::new(&config);
+        let mut state = State::default();
+        let since = Utc::now() - chrono::Duration::hours(1);
+
+        let first_seen =
+            |state: &State| match state::get_source(state, &repo_path.to_string_lossy()) {
+                Some(SourceState::Git { branches, .. }) => branches
+                    .values()
+                    .next()
+                    .and_then(|branch| branch.first_seen),
+                _ => None,
+            };
+
+        collector.collect_between(&mut state, since, None).unwrap();
+        let first = first_seen(&state);
+        assert!(first.is_some());
+
+        // The branch is known on the second run and keeps its first_seen
+        let repos = collector.collect_between(&mut state, since, None).unwrap();
+        assert_eq!(repos[0].branches[0].change, ChangeKind::Modified);
+        assert_eq!(first_seen(&state), first);
+    }
+
+    #[test]
+    fn test_collect_from_linked_worktree() {
+        let (temp_dir, repo_path) = create_test_repo();
+        let worktree_dir = TempDir::new().unwrap();
+        let worktree_path = worktree_dir.path().join("hotfix");
+
+        Command::new("git")
+            .args(["worktree", "add", "-q", "-b", "hotfix"])
+            .arg(&worktree_path)
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        std::fs::write(worktree_path.join("test.txt"), "hotfix").unwrap();
+        Command::new("git")
+            .args(["commit", "-qam", "Fix in worktree"])
+            .current_dir(&worktree_path)
+            .output()
+            .unwrap();
+
+        let config = Config {
+            repos: vec![worktree_path.clone().into()],
+            ..Config::default()
+        };
+        let mut state = State::default();
+        let repos = GitCollector::new(&config)
+            .collect_between(&mut state, Utc::now() - chrono::Duration::hours(1), None)
+            .unwrap();
+
+        assert_eq!(repos.len(), 1);
+        assert_eq!(repos[0].name, "hotfix");
+        assert_eq!(repos[0].default_branch, "hotfix");
+        assert_eq!(repos[0].current_branch, "hotfix");
+        let branch = repos[0]
+            .branches
+            .iter()
+            .find(|branch| branch.name == "hotfix")
+            .unwrap();
+        assert_eq!(branch.commits[0].message, "Fix in worktree");
+
+        // The main checkout, given by its .git directory, is named after
+        // the project rather than ".git"
+        let config = Config {
+            repos: vec![repo_path.join(".git").into()],
+            ..Config::default()
+        };
+        let repos = GitCollector::new(&config)
+            .collect_between(&mut state, Utc::now() - chrono::Duration::hours(1), None)
+            .unwrap();
+        assert_eq!(
+            repos[0].name,
+            temp_dir.path().file_name().unwrap().to_str().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_collect_default_branch_from_origin_head() {
+        let (temp_dir, origin_path) = create_test_repo();
+        let clone_path = temp_dir.path().join("clone");
+        Command::new("git")
+            .args(["clone", "-q"])
+            .arg(&origin_path)
+            .arg(&clone_path)
+            .output()
+            .unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(&clone_path)
+                .output()
+                .unwrap()
+        };
+        let main_branch = String::from_utf8(git(&["branch", "--show-current"]).stdout).unwrap();
+        let main_branch = main_branch.trim();
+
+        // Work on a feature branch, so HEAD is not the default branch
+        git(&["config", "user.name", "Test User"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["checkout", "-qb", "feature"]);
+        std::fs::write(clone_path.join("test.txt"), "feature work").unwrap();
+        git(&["commit", "-qam", "Feature work"]);
+
+        let config = Config {
+            repos: vec![clone_path.clone().into()],
+            ..Config::default()
+        };
+        let repos = GitCollector::new(&config)
+            .collect_between(
+                &mut State::default(),
+                Utc::now() - chrono::Duration::hours(1),
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(repos[0].default_branch, main_branch);
+        assert_eq!(repos[0].current_branch, "feature");
+        let feature = repos[0]
+            .branches
+            .iter()
+            .find(|branch| branch.name == "feature")
+            .unwrap();
+        assert_eq!((feature.ahead, feature.behind), (1, 0));
+    }
+
+    #[test]
+    fn test_collect_configured_default_branch() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(&repo_path)
+                .output()
+                .unwrap();
+        };
+        git(&["checkout", "-qb", "develop"]);
+        git(&["checkout", "-qb", "feature"]);
+        std::fs::write(repo_path.join("test.txt"), "feature work").unwrap();
+        git(&["commit", "-qam", "Feature work"]);
+
+        let collect = |default_branch: &str| {
+            let config = Config {
+                repos: vec![RepoConfig {
+                    default_branch: Some(default_branch.to_string()),
+                    ..RepoConfig::from(repo_path.clone())
+                }],
+                ..Config::default()
+            };
+            GitCollector::new(&config)
+                .collect_between(
+                    &mut State::default(),
+                    Utc::now() - chrono::Duration::hours(1),
+                    None,
+                )
+                .unwrap()
+        };
+
+        let repos = collect("develop");
+        assert_eq!(repos[0].default_branch, "develop");
+        assert_eq!(repos[0].current_branch, "feature");
+        let feature = repos[0]
+            .branches
+            .iter()
+            .find(|branch| branch.name == "feature")
+            .unwrap();
+        assert_eq!((feature.ahead, feature.behind), (1, 0));
+
+        // A branch that doesn't exist falls back to detection
+        let repos = collect("missing");
+        assert_eq!(repos[0].default_branch, "feature");
+    }
+
+    #[test]
+    fn test_collect_detached_head() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(&repo_path)
+                .output()
+                .unwrap();
+        };
+
+        // A side branch, then check out the initial commit directly
+        git(&["checkout", "-qb", "feature"]);
+        std::fs::write(repo_path.join("test.txt"), "feature work").unwrap();
+        git(&["commit", "-qam", "Feature work"]);
+        git(&["checkout", "-q", "--detach", "HEAD~1"]);
+
+        let config = Config {
+            repos: vec![repo_path.clone().into()],
+            ..Config::default()
+        };
+        let repos = GitCollector::new(&config)
+            .collect_between(
+                &mut State::default(),
+                Utc::now() - chrono::Duration::hours(1),
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(repos.len(), 1);
+        assert_eq!(repos[0].default_branch, DETACHED_HEAD);
+        assert_eq!(repos[0].current_branch, DETACHED_HEAD);
+
+        let detached = repos[0]
+            .branches
+            .iter()
+            .find(|branch| branch.name == DETACHED_HEAD)
+            .unwrap();
+        assert_eq!(detached.commits.len(), 1);
+        assert_eq!(detached.commits[0].message, "Initial commit");
+
+        let feature = repos[0]
+            .branches
+            .iter()
+            .find(|branch| branch.name == "feature")
+            .unwrap();
+        assert_eq!((feature.ahead, feature.behind), (0, 0));
+        assert_eq!(feature.commits.len(), 2);
+    }
+
+    #[test]
+    fn test_collect_separates_binary_files() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        std::fs::write(
+            repo_path.join("logo.png"),
+            [0x89, b'P', b'N', b'G', 0, 0, 1],
+        )
+        .unwrap();
+        std::fs::write(repo_path.join("notes.txt"), "plain text").unwrap();
+        Command::new("git")
+            .args(["add", "."])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        Command::new("git")
+            .args(["commit", "-m", "Add assets"])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+
+        let config = Config {
+            repos: vec![repo_path.clone().into()],
+            ..Config::default()
+        };
+        let repos = GitCollector::new(&config)
+            .collect_between(
+                &mut State::default(),
+                Utc::now() - chrono::Duration::hours(1),
+                None,
+            )
+            .unwrap();
+
+        let commit = &repos[0].branches[0].commits[0];
+        assert_eq!(commit.files, vec![PathBuf::from("notes.txt")]);
+        assert_eq!(commit.binary_files, vec![PathBuf::from("logo.png")]);
+    }
+
+    #[test]
+    fn test_collect_keeps_all_files_beyond_max_changed_files() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        let now = Utc::now();
+
+        std::fs::write(repo_path.join("test.txt"), "first").unwrap();
+        commit_at(&repo_path, "First", now);
+        std::fs::write(repo_path.join("test.txt"), "second").unwrap();
+        std::fs::write(repo_path.join("other.txt"), "other").unwrap();
+        Command::new("git")
+            .args(["add", "other.txt"])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        commit_at(&repo_path, "Second", now);
+
+        let mut config = Config {
+            repos: vec![repo_path.clone().into()],
+            ..Config::default()
+        };
+        config.limits.max_changed_files = 1;
+        let collector = GitCollector::new(&config);
+
+        let repos = collector
+            .collect_between(
+                &mut State::default(),
+                now - chrono::Duration::hours(1),
+                None,
+            )
+            .unwrap();
+        let commits = &repos[0].branches[0].commits;
+        let second = commits.iter().find(|c| c.message == "Second").unwrap();
+
+        // The cap is applied when rendering, not while collecting
+        assert_eq!(second.files.len(), 2);
+        assert_eq!(repos[0].files_changed(), 2);
+    }
+
+    #[test]
+    fn test_collect_counts_lines() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        let now = Utc::now();
+
+        std::fs::write(repo_path.join("test.txt"), "one\ntwo\nthree\n").unwrap();
+        commit_at(&repo_path, "Rewrite", now);
+
+        let config = Config {
+            repos: vec![repo_path.clone().into()],
+            ..Config::default()
+        };
+        let collector = GitCollector::new(&config);
+
+        let repos = collector
+            .collect_between(
+                &mut State::default(),
+                now - chrono::Duration::hours(1),
+                None,
+            )
+            .unwrap();
+        let commits = &repos[0].branches[0].commits;
+        let rewrite = commits.iter().find(|c| c.message == "Rewrite").unwrap();
+        assert_eq!((rewrite.additions, rewrite.deletions), (3, 1));
+
+        let added: usize = commits.iter().map(|c| c.additions).sum();
+        let removed: usize = commits.iter().map(|c| c.deletions).sum();
+        assert_eq!(
+            (repos[0].lines_added, repos[0].lines_removed),
+            (added, removed)
+        );
+    }
+
+    #[test]
+    fn test_collect_repository_description() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        let config = Config {
+            repos: vec![repo_path.clone().into()],
+            ..Config::default()
+        };
+        let collector = GitCollector::new(&config);
+        let description = || {
+            collector
+                .collect_between(
+                    &mut State::default(),
+                    Utc::now() - chrono::Duration::hours(1),
+                    None,
+                )
+                .unwrap()[0]
+                .description
+                .clone()
+        };
+
+        // Git's placeholder description doesn't count
+        assert_eq!(description(), None);
+
+        fs::write(repo_path.join("README.md"), "\n# My App\n\nDoes things.\n").unwrap();
+        assert_eq!(description(), Some("My App".to_string()));
+
+        let git_dir = git2::Repository::open(&repo_path)
+            .unwrap()
+            .path()
+            .to_path_buf();
+        fs::write(git_dir.join("description"), "Internal tools\n").unwrap();
+        assert_eq!(description(), Some("Internal tools".to_string()));
+    }
+
+    #[test]
+    fn test_readme_title() {
+        assert_eq!(readme_title("# My App\n"), Some("My App".to_string()));
+        assert_eq!(
+            readme_title("My App\n======\n\nDoes things.\n"),
+            Some("My App".to_string())
+        );
+        assert_eq!(
+            readme_title(
+                "[![CI](https://ci.example.com/badge.svg)](https://ci.example.com)\n\
+                 ![Logo](logo.png)\n\
+                 <p align=\"center\">\n\
+                 ---\n\
+                 ## My App\n"
+            ),
+            Some("My App".to_string())
+        );
+        assert_eq!(
+            readme_title("---\ntitle: My App\n---\n\n# Usage\n"),
+            Some("Usage".to_string())
+        );
+        assert_eq!(readme_title("<img src=\"logo.png\">\n"), None);
+    }
+
+    #[test]
+    fn test_collect_author_me() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        std::fs::write(repo_path.join("test.txt"), "their change").unwrap();
+        Command::new("git")
+            .args([
+                "commit",
+                "-am",
+                "Their commit",
+                "--author",
+                "Someone Else <else@example.com>",
+            ])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+
+        let mut config = Config {
+            repos: vec![repo_path.clone().into()],
+            ..Config::default()
+        };
+        let collect = |config: &Config| {
+            let repos = GitCollector::new(config)
+                .collect_between(
+                    &mut State::default(),
+                    Utc::now() - chrono::Duration::hours(1),
+                    None,
+                )
+                .unwrap();
+            let mut messages = commit_messages(&repos);
+            messages.sort();
+            messages
+        };
+
+        assert_eq!(collect(&config), vec!["Initial commit", "Their commit"]);
+
+        // The repository's user.email is "test@example.com"
+        config.author_me = true;
+        assert_eq!(collect(&config), vec!["Initial commit"]);
+    }
+
+    #[test]
+    fn test_collect_detects_signed_commits() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        // A signed commit on top of HEAD; the signature isn't checked, so
+        // no key is needed
+        let repo = git2::Repository::open(&repo_path).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let author = repo.signature().unwrap();
+        let buffer = repo
+            .commit_create_buffer(&author, &author, "Signed", &head.tree().unwrap(), &[&head])
+            .unwrap();
+        let signed = repo
+            .commit_signed(
+                buffer.as_str().unwrap(),
+                "-----BEGIN PGP SIGNATURE-----\n\nabc\n-----END PGP SIGNATURE-----",
+                None,
+            )
+            .unwrap();
+        let branch = repo.head().unwrap().name().unwrap().to_string();
+        repo.reference(&branch, signed, true, "signed commit")
+            .unwrap();
+
+        let config = Config {
+            repos: vec![repo_path.clone().into()],
+            ..Config::default()
+        };
+        let collector = GitCollector::new(&config);
+
+        let repos = collector
+            .collect_between(
+                &mut State::default(),
+                Utc::now() - chrono::Duration::hours(1),
+                None,
+            )
+            .unwrap();
+        let commits = &repos[0].branches[0].commits;
+        let signed: Vec<(&str, bool)> = commits
+            .iter()
+            .map(|c| (c.message.as_str(), c.signed))
+            .collect();
+        assert_eq!(signed, vec![("Signed", true), ("Initial commit", false)]);
+    }
+
+    #[test]
+    fn test_collect_applies_commit_message_filters() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        let now = Utc::now();
+
+        for message in [
+            "feat: Add export",
+            "chore(deps): Bump serde",
+            "fix: Handle empty input\n\n[skip ci]",
+            "fix: Parse dates",
+        ] {
+            std::fs::write(repo_path.join("test.txt"), message).unwrap();
+            commit_at(&repo_path, message, now);
+        }
+
+        let config = Config {
+            repos: vec![repo_path.clone().into()],
+            commit_include_patterns: vec!["^(feat|fix):".to_string()],
+            commit_exclude_patterns: vec![r"\[skip ci\]".to_string()],
+            ..Config::default()
+        };
+        let collector = GitCollector::new(&config);
+
+        let repos = collector
+            .collect_between(
+                &mut State::default(),
+                now - chrono::Duration::hours(1),
+                None,
+            )
+            .unwrap();
+        let mut messages = commit_messages(&repos);
+        messages.sort();
+        assert_eq!(messages, vec!["feat: Add export", "fix: Parse dates"]);
+    }
+
+    #[test]
+    fn test_collect_rejects_invalid_commit_pattern() {
+        let config = Config {
+            commit_exclude_patterns: vec!["[skip".to_string()],
+            ..Config::default()
+        };
+        let collector = GitCollector::new(&config);
+
+        let result = collector.collect_between(&mut State::default(), Utc::now(), None);
+        assert!(matches!(result, Err(ChronicleError::Config(_))));
+    }
+
+    #[test]
+    fn test_collect_respects_until() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        let now = Utc::now();
+
+        std::fs::write(repo_path.join("test.txt"), "old").unwrap();
+        commit_at(&repo_path, "Two days ago", now - chrono::Duration::days(2));
+        std::fs::write(repo_path.join("test.txt"), "new").unwrap();
+        commit_at(&repo_path, "Just now", now);
+
+        let config = Config {
+            repos: vec![repo_path.clone().into()],
+            ..Config::default()
+        };
+        let collector = GitCollector::new(&config);
+        let since = now - chrono::Duration::days(3);
+
+        let repos = collector
+            .collect_between(
+                &mut State::default(),
+                since,
+                Some(now - chrono::Duration::days(1)),
+            )
+            .unwrap();
+        assert_eq!(commit_messages(&repos), vec!["Two days ago"]);
+    }
+
+    #[test]
+    fn test_collect_walks_past_older_merged_parents() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        let now = Utc::now();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(&repo_path)
+                .output()
+                .unwrap();
+        };
+
+        // A recent commit on a side branch...
+        git(&["checkout", "-qb", "side"]);
+        std::fs::write(repo_path.join("side.txt"), "side").unwrap();
+        git(&["add", "side.txt"]);
+        commit_at(
+            &repo_path,
+            "Recent side work",
+            now - chrono::Duration::minutes(30),
+        );
+
+        // ...merged behind an older-dated first parent
+        git(&["checkout", "-q", "-"]);
+        std::fs::write(repo_path.join("test.txt"), "old").unwrap();
+        commit_at(&repo_path, "Old main work", now - chrono::Duration::days(5));
+        let date = (now - chrono::Duration::minutes(10)).to_rfc3339();
+        Command::new("git")
+            .args(["merge", "-q", "--no-edit", "side"])
+            .env("GIT_AUTHOR_DATE", &date)
+            .env("GIT_COMMITTER_DATE", &date)
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+
+        let config = Config {
+            repos: vec![repo_path.clone().into()],
+            ..Config::default()
+        };
+        let repos = GitCollector::new(&config)
+            .collect_between(
+                &mut State::default(),
+                now - chrono::Duration::hours(1),
+                None,
+            )
+            .unwrap();
+
+        let messages = commit_messages(&repos);
+        assert!(messages.contains(&"Recent side work".to_string()));
+        assert!(!messages.contains(&"Old main work".to_string()));
+    }
+
+    #[test]
+    fn test_collect_work_in_progress_without_commits() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        std::fs::write(repo_path.join("test.txt"), "edited").unwrap();
+        std::fs::write(repo_path.join("new.txt"), "new").unwrap();
+
+        let mut config = Config {
+            repos: vec![repo_path.clone().into()],
+            ..Config::default()
+        };
+        let since = Utc::now() + chrono::Duration::hours(1);
+
+        // Disabled by default: no commits means no repository
+        let repos = GitCollector::new(&config)
+            .collect_between(&mut State::default(), since, None)
+            .unwrap();
+        assert!(repos.is_empty());
+
+        config.include_wip = true;
+        let repos = GitCollector::new(&config)
+            .collect_between(&mut State::default(), since, None)
+            .unwrap();
+        assert_eq!(repos.len(), 1);
+        assert!(repos[0].branches.is_empty());
+        assert_eq!(
+            repos[0].wip,
+            Some(WorkInProgress {
+                modified: 1,
+                added: 1,
+                deleted: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_split_message() {
+        assert_eq!(
+            split_message("Fix the bug\n"),
+            ("Fix the bug".to_string(), None)
+        );
+        assert_eq!(
+            split_message("Fix the bug\n\nIt was off by one.\n"),
+            (
+                "Fix the bug".to_string(),
+                Some("It was off by one.".to_string())
+            )
+        );
+    }
 }
@@ -4,11 +4,106 @@
 //! - GitCollector: Extract commits and branches from Git repositories
 //! - TodoCollector: Parse TODO/Inbox markdown files
 //! - NotesCollector: Scan note directories for modified files
+//! - CalendarCollector: Read events from ICS calendar files
+//!
+//! Each implements [`Collector`] for its item type and [`Source`] so
+//! `chronicle gen` can run whichever sources are selected.
 
+pub mod calendar;
 pub mod git;
 pub mod notes;
 pub mod todo;
 
+pub use calendar::CalendarCollector;
 pub use git::GitCollector;
 pub use notes::NotesCollector;
 pub use todo::TodoCollector;
+
+use chrono::{DateTime, Utc};
+use std::cell::RefCell;
+
+use crate::error::Result;
+use crate::models::{CollectionWarning, Event, Note, Repository, Todo, TodoTotals};
+use crate::state::State;
+
+/// Time range a chronicle covers
+#[derive(Debug, Clone, Copy)]
+pub struct Window {
+    pub since: DateTime<Utc>,
+    /// Open-ended when None
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// A source of chronicle items that tracks what it has seen in [`State`]
+pub trait Collector {
+    /// What the source yields, e.g. repositories or TODOs
+    type Item;
+
+    /// New and changed items in `window`, updating `state`
+    fn collect(&self, state: &mut State, window: Window) -> Result<Vec<Self::Item>>;
+
+    /// Sources skipped so far, leaving none behind
+    fn take_warnings(&self) -> Vec<CollectionWarning>;
+}
+
+/// Warnings a collector records for the chronicle while collecting
+#[derive(Debug)]
+pub struct Warnings {
+    /// Source named in each warning, e.g. `git`
+    source: &'static str,
+    recorded: RefCell<Vec<CollectionWarning>>,
+}
+
+impl Warnings {
+    pub fn new(source: &'static str) -> Self {
+        Self {
+            source,
+            recorded: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Record a skipped source
+    pub fn skipped(&self, message: String) {
+        self.recorded
+            .borrow_mut()
+            .push(CollectionWarning::new(self.source, message));
+    }
+
+    /// Record a source read with a fallback or without some detail
+    pub fn degraded(&self, message: String) {
+        self.recorded
+            .borrow_mut()
+            .push(CollectionWarning::degraded(self.source, message));
+    }
+
+    /// Warnings recorded so far, leaving none behind
+    pub fn take(&self) -> Vec<CollectionWarning> {
+        self.recorded.take()
+    }
+}
+
+/// Everything the selected sources found, ready to become a chronicle
+#[derive(Debug, Default)]
+pub struct Collected {
+    pub repositories: Vec<Repository>,
+    pub todos: Vec<Todo>,
+    pub todo_totals: TodoTotals,
+    pub notes: Vec<Note>,
+    pub notes_omitted: usize,
+    pub events: Vec<Event>,
+    pub warnings: Vec<CollectionWarning>,
+}
+
+/// A collector of any item type, as run by `chronicle gen`
+pub trait Source {
+    /// Name used by `--only` and `--skip`
+    fn name(&self) -> &'static str;
+
+    /// Collect into `collected`, with the source's warnings
+    fn collect_into(
+        &self,
+        state: &mut State,
+        window: Window,
+        collected: &mut Collected,
+    ) -> Result<()>;
+}
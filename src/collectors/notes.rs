@@ -1,58 +1,151 @@
 use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 use walkdir::WalkDir;
 
-use crate::config::Config;
+use crate::collectors::{Collected, Collector, Source, TodoCollector, Warnings, Window};
+use crate::config::{Config, ExcerptBoundary, ExcerptMode};
+use crate::display::log;
 use crate::error::{ChronicleError, Result};
-use crate::models::{ChangeKind, Note};
-use crate::state::{self, SourceState, State};
+use crate::models::{ChangeKind, CollectionWarning, Note, Todo};
+use crate::state::{self, NoteFileState, SourceState, State};
 
 /// Notes collector for scanning note directories
 pub struct NotesCollector<'a> {
     config: &'a Config,
+    warnings: Warnings,
+    omitted: Cell<usize>,
 }
 
 impl<'a> NotesCollector<'a> {
     pub fn new(config: &'a Config) -> Self {
-        Self { config }
+        Self {
+            config,
+            warnings: Warnings::new("notes"),
+            omitted: Cell::new(0),
+        }
+    }
+
+    /// Notes the last `collect_between` left out over `max_notes_total_chars`
+    pub fn omitted(&self) -> usize {
+        self.omitted.get()
     }
 
-    /// Collect notes from all configured directories
-    pub fn collect(&self, state: &mut State, since: DateTime<Utc>) -> Result<Vec<Note>> {
+    /// Collect notes from all configured directories, modified between
+    /// `since` and `until` (open-ended when `until` is None)
+    pub fn collect_between(
+        &self,
+        state: &mut State,
+        since: DateTime<Utc>,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Note>> {
         let mut all_notes = Vec::new();
+        let filter = NoteFilter::new(self.config)?;
+
+        // Wikilink targets across all note directories
+        let link_targets = self
+            .config
+            .resolve_wikilinks
+            .then(|| self.index_note_files());
 
         for notes_dir in &self.config.notes_dirs {
-            match self.collect_directory(notes_dir, state, since) {
+            match self.collect_directory(
+                notes_dir,
+                state,
+                since,
+                until,
+                &filter,
+                link_targets.as_ref(),
+            ) {
                 Ok(notes) => {
                     all_notes.extend(notes);
                 }
                 Err(e) => {
-                    eprintln!(
-                        "Warning: Skipping notes directory '{}': {}",
+                    self.warnings.skipped(format!(
+                        "Skipping notes directory '{}': {}",
                         notes_dir.display(),
                         e
-                    );
+                    ));
                 }
             }
         }
 
         // Sort by modification time (newest first)
-        all_notes.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+        all_notes.sort_by_key(|n| std::cmp::Reverse(n.modified_at));
 
-        // Apply limit
+        // Removed notes are already gone from state, so one cut by the
+        // limits would never be reported
+        let (removed, mut all_notes): (Vec<Note>, Vec<Note>) = all_notes
+            .into_iter()
+            .partition(|note| note.change == ChangeKind::Removed);
+
+        // Apply limits
         all_notes.truncate(self.config.limits.max_note_files);
+        self.omitted.set(0);
+        if let Some(max_chars) = self.config.limits.max_notes_total_chars {
+            // Newest first; a note too large for what's left is skipped so
+            // smaller ones after it can still fit
+            let mut total = 0;
+            let before = all_notes.len();
+            all_notes.retain(|note| {
+                let chars = note.excerpt.chars().count();
+                let fits = total + chars <= max_chars;
+                if fits {
+                    total += chars;
+                }
+                fits
+            });
+            self.omitted.set(before - all_notes.len());
+        }
+
+        all_notes.extend(removed);
+        all_notes.sort_by_key(|n| std::cmp::Reverse(n.modified_at));
 
         Ok(all_notes)
     }
 
+    /// TODOs from `note_todo_keywords` lines in the collected notes
+    ///
+    /// Each note is tracked like a TODO file, so only new and changed items
+    /// are returned.
+    pub fn collect_todos(&self, notes: &[Note], state: &mut State) -> Vec<Todo> {
+        let keywords = &self.config.note_todo_keywords;
+        if keywords.is_empty() {
+            return Vec::new();
+        }
+
+        let tracker = TodoCollector::new(self.config);
+        let mut todos = Vec::new();
+        for note in notes.iter().filter(|n| n.change != ChangeKind::Removed) {
+            match self.read_note(&note.path) {
+                Ok(content) => {
+                    let found = tracker.parse_keyword_todos(&content, &note.path, keywords);
+                    todos.extend(tracker.track_changes(found, &note.path, state));
+                }
+                Err(e) => self.warnings.skipped(format!(
+                    "Skipping TODOs in note '{}': {}",
+                    note.path.display(),
+                    e
+                )),
+            }
+        }
+
+        todos
+    }
+
     /// Collect notes from a single directory
     fn collect_directory(
         &self,
         dir_path: &Path,
         state: &mut State,
         since: DateTime<Utc>,
+        until: Option<DateTime<Utc>>,
+        filter: &NoteFilter,
+        link_targets: Option<&HashMap<String, PathBuf>>,
     ) -> Result<Vec<Note>> {
         if !dir_path.exists() {
             return Err(ChronicleError::Collector(format!(
@@ -69,22 +162,13 @@ impl<'a> NotesCollector<'a> {
         }
 
         let mut notes = Vec::new();
+        let mut seen_files = HashMap::new();
 
-        // Walk directory (max depth 1 - no recursion)
-        for entry in WalkDir::new(dir_path)
-            .max_depth(1)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
-
-            // Skip directories
-            if path.is_dir() {
-                continue;
-            }
+        for path in self.note_files(dir_path) {
+            let path = path.as_path();
 
-            // Check if file is a markdown file
-            if !self.is_markdown_file(path) {
+            // Apply notes_include / notes_exclude to the path within the dir
+            if !filter.matches(path.strip_prefix(dir_path).unwrap_or(path)) {
                 continue;
             }
 
@@ -101,93 +185,244 @@ impl<'a> NotesCollector<'a> {
 
             let modified_dt: DateTime<Utc> = modified.into();
 
-            // Check if modified after since time
-            if modified_dt < since {
+            // Untracked files outside the time window are still hashed: `mv`
+            // keeps the mtime, so an old note may be a renamed tracked one
+            let in_window = modified_dt >= since && until.is_none_or(|until| modified_dt <= until);
+            if !in_window && is_tracked(state, dir_path, path) {
                 continue;
             }
 
-            // Determine if note is new or modified
-            let change = self.determine_note_change(path, state, dir_path);
+            let content = match self.read_note(path) {
+                Ok(content) => content,
+                Err(_) if !in_window => continue,
+                Err(e) => return Err(e),
+            };
+            let hash = content_hash(&content);
+
+            // Determine if note is new, modified, or renamed
+            let (change, renamed_from) = self.determine_note_change(path, &hash, state, dir_path);
+
+            seen_files.insert(
+                state::path_key(path),
+                NoteFileState {
+                    modified_at: modified_dt,
+                    content_hash: Some(hash),
+                },
+            );
+
+            // Outside the window only renames are reported
+            if !in_window && renamed_from.is_none() {
+                continue;
+            }
 
-            // Extract excerpt
-            let excerpt = self.extract_excerpt(path)?;
+            // Extract excerpt and count the whole note
+            let mut excerpt = self.extract_excerpt(&content);
+            if let Some(link_targets) = link_targets {
+                excerpt = resolve_wikilinks(&excerpt, dir_path, link_targets);
+            }
 
             notes.push(Note {
                 path: path.to_path_buf(),
                 change,
+                renamed_from,
                 modified_at: modified_dt,
                 excerpt,
+                word_count: content.split_whitespace().count(),
+                char_count: content.chars().count(),
             });
         }
 
+        // Tracked notes that are gone, whenever they were deleted
+        notes.extend(self.removed_notes(state, dir_path, &notes));
+
         // Update state
-        self.update_state(state, dir_path, &notes);
+        self.update_state(state, dir_path, seen_files);
 
         Ok(notes)
     }
 
-    /// Check if a file is a markdown file
-    fn is_markdown_file(&self, path: &Path) -> bool {
+    /// Notes tracked in state for `dir_path` that no longer exist
+    ///
+    /// Files that `notes` reports as renamed are moves, not removals.
+    fn removed_notes(&self, state: &State, dir_path: &Path, notes: &[Note]) -> Vec<Note> {
+        let source_key = state::path_key(dir_path);
+        let Some(SourceState::Notes { files, .. }) = state::get_source(state, &source_key) else {
+            return Vec::new();
+        };
+
+        let renamed: HashSet<&PathBuf> = notes
+            .iter()
+            .filter_map(|note| note.renamed_from.as_ref())
+            .collect();
+
+        files
+            .iter()
+            .map(|(file_key, file)| (state::key_path(file_key), file))
+            .filter(|(path, _)| !path.exists() && !renamed.contains(path))
+            .map(|(path, file)| Note {
+                path,
+                change: ChangeKind::Removed,
+                renamed_from: None,
+                modified_at: file.modified_at,
+                excerpt: String::new(),
+                word_count: 0,
+                char_count: 0,
+            })
+            .collect()
+    }
+
+    /// Check if a file has one of the configured note extensions
+    ///
+    /// Hidden files are not notes unless `include_hidden_notes` is set.
+    fn is_note_file(&self, path: &Path) -> bool {
+        let hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        if hidden && !self.config.include_hidden_notes {
+            return false;
+        }
+
         if let Some(ext) = path.extension() {
-            let ext_str = ext.to_string_lossy().to_lowercase();
-            ext_str == "md" || ext_str == "markdown"
+            let ext_str = ext.to_string_lossy();
+            self.config.note_extensions.iter().any(|note_ext| {
+                note_ext
+                    .trim_start_matches('.')
+                    .eq_ignore_ascii_case(&ext_str)
+            })
         } else {
             false
         }
     }
 
-    /// Extract excerpt from a note file
-    fn extract_excerpt(&self, path: &Path) -> Result<String> {
-        let content = fs::read_to_string(path).map_err(|e| {
-            ChronicleError::Collector(format!("Cannot read note file '{}': {}", path.display(), e))
-        })?;
+    /// Note files in `dir`, down to `notes_max_depth`
+    ///
+    /// Hidden subdirectories (like `.obsidian`) are not entered unless
+    /// `include_hidden_notes` is set.
+    fn note_files(&self, dir: &Path) -> Vec<PathBuf> {
+        let include_hidden = self.config.include_hidden_notes;
 
-        // Take up to max_chars_per_item characters
-        let max_chars = self.config.limits.max_chars_per_item;
-        let excerpt = if content.len() <= max_chars {
-            content
-        } else {
-            // Try to find a sentence boundary
-            let truncated = &content[..max_chars];
-            if let Some(pos) = truncated.rfind('.') {
-                truncated[..=pos].to_string()
-            } else if let Some(pos) = truncated.rfind('\n') {
-                truncated[..pos].to_string()
-            } else {
-                format!("{}...", truncated)
+        WalkDir::new(dir)
+            .max_depth(self.config.notes_max_depth)
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || include_hidden
+                    || !entry.file_name().to_string_lossy().starts_with('.')
+            })
+            .filter_map(|e| e.ok())
+            .map(|entry| entry.into_path())
+            .filter(|path| path.is_file() && self.is_note_file(path))
+            .collect()
+    }
+
+    /// Map wikilink keys to note files in all configured directories
+    fn index_note_files(&self) -> HashMap<String, PathBuf> {
+        let mut targets = HashMap::new();
+
+        for notes_dir in &self.config.notes_dirs {
+            for path in self.note_files(notes_dir) {
+                if let Some(stem) = path.file_stem() {
+                    targets
+                        .entry(wikilink_key(&stem.to_string_lossy()))
+                        .or_insert_with(|| path.to_path_buf());
+                }
             }
-        };
+        }
+
+        targets
+    }
 
-        Ok(excerpt.trim().to_string())
+    /// Read the content of a note file
+    fn read_note(&self, path: &Path) -> Result<String> {
+        fs::read_to_string(path).map_err(|e| {
+            ChronicleError::Collector(format!("Cannot read note file '{}': {}", path.display(), e))
+        })
+    }
+
+    /// Extract excerpt from note content, as configured by `excerpt_mode`
+    ///
+    /// Notes without headings (or without a paragraph) fall back to the
+    /// prefix excerpt.
+    fn extract_excerpt(&self, content: &str) -> String {
+        match self.config.excerpt_mode {
+            ExcerptMode::Prefix => self.truncate_excerpt(content),
+            ExcerptMode::Outline => outline(content)
+                .map(|outline| self.truncate_excerpt(&outline))
+                .unwrap_or_else(|| self.truncate_excerpt(content)),
+            ExcerptMode::FirstParagraph => first_paragraph(content)
+                .map(|paragraph| self.truncate_excerpt(&paragraph))
+                .unwrap_or_else(|| self.truncate_excerpt(content)),
+        }
+    }
+
+    /// Cut text to `max_chars_per_item`, at the first of
+    /// `excerpt_boundaries` that fits
+    fn truncate_excerpt(&self, content: &str) -> String {
+        cut_excerpt(
+            content,
+            self.config.limits.max_chars_per_item,
+            &self.config.excerpt_boundaries,
+        )
     }
 
-    /// Determine if a note is new or modified
-    fn determine_note_change(&self, path: &Path, state: &State, dir_path: &Path) -> ChangeKind {
-        let source_key = dir_path.to_string_lossy().to_string();
+    /// Determine if a note is new or modified, and where it was renamed from
+    ///
+    /// A note at an unknown path whose content matches a tracked file that no
+    /// longer exists is the same note, renamed or moved.
+    fn determine_note_change(
+        &self,
+        path: &Path,
+        hash: &str,
+        state: &State,
+        dir_path: &Path,
+    ) -> (ChangeKind, Option<PathBuf>) {
+        let source_key = state::path_key(dir_path);
         let source_state = state::get_source(state, &source_key);
 
         match source_state {
             Some(SourceState::Notes { files, .. }) => {
-                let file_key = path.to_string_lossy().to_string();
+                let file_key = state::path_key(path);
                 if files.contains_key(&file_key) {
-                    ChangeKind::Modified
-                } else {
-                    ChangeKind::New
+                    return (ChangeKind::Modified, None);
+                }
+
+                let renamed_from = files
+                    .iter()
+                    .filter(|(_, file)| file.content_hash.as_deref() == Some(hash))
+                    .map(|(old_key, _)| state::key_path(old_key))
+                    .find(|old_path| !old_path.exists());
+
+                match renamed_from {
+                    Some(old_path) => (ChangeKind::Modified, Some(old_path)),
+                    None => (ChangeKind::New, None),
                 }
             }
-            _ => ChangeKind::New,
+            _ => (ChangeKind::New, None),
         }
     }
 
-    /// Update state with current notes
-    fn update_state(&self, state: &mut State, dir_path: &Path, notes: &[Note]) {
-        let source_key = dir_path.to_string_lossy().to_string();
+    /// Update state with the notes seen in this run
+    ///
+    /// Files tracked earlier stay in state while they exist, so their hashes
+    /// are still available to detect a later rename.
+    fn update_state(
+        &self,
+        state: &mut State,
+        dir_path: &Path,
+        seen_files: HashMap<String, NoteFileState>,
+    ) {
+        let source_key = state::path_key(dir_path);
 
-        let mut files = HashMap::new();
-        for note in notes {
-            let file_key = note.path.to_string_lossy().to_string();
-            files.insert(file_key, note.modified_at);
-        }
+        let mut files: HashMap<String, NoteFileState> = match state::get_source(state, &source_key)
+        {
+            Some(SourceState::Notes { files, .. }) => files
+                .iter()
+                .filter(|(file_key, _)| state::key_path(file_key).exists())
+                .map(|(file_key, file)| (file_key.clone(), file.clone()))
+                .collect(),
+            _ => HashMap::new(),
+        };
+        files.extend(seen_files);
 
         let source_state = SourceState::Notes {
             last_checked: Utc::now(),
@@ -198,23 +433,415 @@ impl<'a> NotesCollector<'a> {
     }
 }
 
+impl Collector for NotesCollector<'_> {
+    type Item = Note;
+
+    fn collect(&self, state: &mut State, window: Window) -> Result<Vec<Note>> {
+        self.collect_between(state, window.since, window.until)
+    }
+
+    fn take_warnings(&self) -> Vec<CollectionWarning> {
+        self.warnings.take()
+    }
+}
+
+impl Source for NotesCollector<'_> {
+    fn name(&self) -> &'static str {
+        "notes"
+    }
+
+    /// Notes, and the TODOs found in them
+    fn collect_into(
+        &self,
+        state: &mut State,
+        window: Window,
+        collected: &mut Collected,
+    ) -> Result<()> {
+        let started = Instant::now();
+        let notes = self.collect(state, window)?;
+        let note_todos = self.collect_todos(&notes, state);
+        log::detail(&format!(
+            "notes: scanned {} directories, found {} updated notes and {} changed TODOs ({:.0?})",
+            self.config.notes_dirs.len(),
+            notes.len(),
+            note_todos.len(),
+            started.elapsed()
+        ));
+
+        collected.notes.extend(notes);
+        collected.notes_omitted += self.omitted();
+        collected.todos.extend(note_todos);
+        collected.warnings.extend(self.take_warnings());
+        Ok(())
+    }
+}
+
+/// Compiled `notes_include` and `notes_exclude` globs
+struct NoteFilter {
+    /// None when every note is included
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+}
+
+impl NoteFilter {
+    fn new(config: &Config) -> Result<Self> {
+        let include = if config.notes_include.is_empty() {
+            None
+        } else {
+            Some(glob_set("notes_include", &config.notes_include)?)
+        };
+
+        Ok(Self {
+            include,
+            exclude: glob_set("notes_exclude", &config.notes_exclude)?,
+        })
+    }
+
+    /// Whether a note at `relative_path` (within its notes directory) passes
+    fn matches(&self, relative_path: &Path) -> bool {
+        self.include
+            .as_ref()
+            .is_none_or(|include| include.is_match(relative_path))
+            && !self.exclude.is_match(relative_path)
+    }
+}
+
+/// Compile the globs of config option `name`
+fn glob_set(name: &str, patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob =
+            Glob::new(pattern).map_err(|e| ChronicleError::Config(format!("{}: {}", name, e)))?;
+        builder.add(glob);
+    }
+
+    builder
+        .build()
+        .map_err(|e| ChronicleError::Config(format!("{}: {}", name, e)))
+}
+
+/// Whether state already tracks note `path` in `dir_path`
+fn is_tracked(state: &State, dir_path: &Path, path: &Path) -> bool {
+    matches!(
+        state::get_source(state, &state::path_key(dir_path)),
+        Some(SourceState::Notes { files, .. }) if files.contains_key(&state::path_key(path))
+    )
+}
+
+/// Stable hash of note content (64-bit FNV-1a, hex)
+///
+/// Stored in state, so it must not change between builds or Rust versions.
+fn content_hash(content: &str) -> String {
+    let hash = content.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    });
+
+    format!("{:016x}", hash)
+}
+
+/// Content after a leading `---` frontmatter block, if there is one
+fn strip_frontmatter(content: &str) -> &str {
+    let Some(rest) = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    else {
+        return content;
+    };
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        offset += line.len();
+        if matches!(line.trim_end(), "---" | "...") {
+            return &rest[offset..];
+        }
+    }
+
+    // Unterminated: not frontmatter after all
+    content
+}
+
+/// Level and text of an ATX heading line (`## Title`)
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let line = line.trim_start();
+    let level = line.chars().take_while(|&c| c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+
+    let text = &line[level..];
+    if !text.is_empty() && !text.starts_with([' ', '\t']) {
+        return None;
+    }
+
+    let text = text.trim().trim_end_matches('#').trim_end();
+    (!text.is_empty()).then_some((level, text))
+}
+
+/// The note's headings as a nested Markdown list, shallowest level first
+///
+/// Headings inside fenced code blocks are ignored.
+fn outline(content: &str) -> Option<String> {
+    let mut headings = Vec::new();
+    let mut in_code = false;
+
+    for line in strip_frontmatter(content).lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+        } else if !in_code {
+            headings.extend(heading(line));
+        }
+    }
+
+    let min_level = headings.iter().map(|(level, _)| *level).min()?;
+    let lines: Vec<String> = headings
+        .into_iter()
+        .map(|(level, text)| format!("{}- {}", "  ".repeat(level - min_level), text))
+        .collect();
+
+    Some(lines.join("\n"))
+}
+
+/// The first paragraph after any frontmatter, skipping paragraphs that are
+/// only headings
+fn first_paragraph(content: &str) -> Option<String> {
+    let mut paragraph: Vec<&str> = Vec::new();
+
+    for line in strip_frontmatter(content)
+        .lines()
+        .chain(std::iter::once(""))
+    {
+        if !line.trim().is_empty() {
+            paragraph.push(line);
+            continue;
+        }
+
+        if paragraph.iter().any(|line| heading(line).is_none()) {
+            return Some(paragraph.join("\n"));
+        }
+        paragraph.clear();
+    }
+
+    None
+}
+
+/// Cut `content` to at most `max_chars` characters
+///
+/// Boundaries are tried in order, taking the last match of each that fits;
+/// without one the text is cut mid-word and marked with `...`.
+fn cut_excerpt(content: &str, max_chars: usize, boundaries: &[ExcerptBoundary]) -> String {
+    // Byte index of the first character that doesn't fit
+    let Some((limit, _)) = content.char_indices().nth(max_chars) else {
+        return content.trim().to_string();
+    };
+
+    for boundary in boundaries {
+        let cut = content
+            .char_indices()
+            .map(|(index, _)| index)
+            .take_while(|&index| index <= limit)
+            .filter(|&index| is_boundary(*boundary, &content[..index], &content[index..]))
+            .last();
+        let Some(excerpt) = cut.map(|index| content[..index].trim()) else {
+            continue;
+        };
+        if excerpt.is_empty() {
+            continue;
+        }
+        return match boundary {
+            ExcerptBoundary::Word => format!("{}...", excerpt),
+            _ => excerpt.to_string(),
+        };
+    }
+
+    format!("{}...", content[..limit].trim())
+}
+
+/// Whether text can be cut between `before` and `after` at `boundary`
+fn is_boundary(boundary: ExcerptBoundary, before: &str, after: &str) -> bool {
+    match boundary {
+        ExcerptBoundary::Paragraph => after.starts_with("\n\n") || after.starts_with("\r\n\r\n"),
+        ExcerptBoundary::Sentence => {
+            before.ends_with(['.', '!', '?']) && after.starts_with(char::is_whitespace)
+        }
+        ExcerptBoundary::Line => after.starts_with('\n') || after.starts_with("\r\n"),
+        ExcerptBoundary::Word => after.starts_with(char::is_whitespace),
+    }
+}
+
+/// Lookup key for a wikilink target or note file name
+///
+/// Case, spaces, and underscores are ignored, so `[[Other Note]]` finds
+/// `other-note.md` as well as `Other Note.md`.
+fn wikilink_key(name: &str) -> String {
+    name.trim().to_lowercase().replace([' ', '_'], "-")
+}
+
+/// Replace `[[Target]]` and `[[Target|Label]]` with Markdown links to the
+/// target note, relative to `note_dir` when it lives there
+///
+/// Links whose target isn't a known note are left as written.
+fn resolve_wikilinks(text: &str, note_dir: &Path, targets: &HashMap<String, PathBuf>) -> String {
+    let mut output = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find("[[") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+
+        let Some(end) = after.find("]]").filter(|&end| !after[..end].contains('\n')) else {
+            output.push_str("[[");
+            rest = after;
+            continue;
+        };
+
+        let inner = &after[..end];
+        let (target, label) = inner.split_once('|').unwrap_or((inner, inner));
+        // `[[Note#Heading]]` links into a note; resolve the note itself
+        let note_name = target.split('#').next().unwrap_or(target);
+
+        match targets.get(&wikilink_key(note_name)) {
+            Some(path) => {
+                let link = path
+                    .strip_prefix(note_dir)
+                    .unwrap_or(path)
+                    .display()
+                    .to_string();
+
+                // Destinations with spaces or parentheses need angle brackets
+                if link.contains(|c: char| c.is_whitespace() || c == '(' || c == ')') {
+                    output.push_str(&format!("[{}](<{}>)", label.trim(), link));
+                } else {
+                    output.push_str(&format!("[{}]({})", label.trim(), link));
+                }
+            }
+            None => output.push_str(&format!("[[{}]]", inner)),
+        }
+
+        rest = &after[end + 2..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::thread;
-    use std::time::Duration;
+    use std::time::{Duration, SystemTime};
     use tempfile::TempDir;
 
     #[test]
-    fn test_is_markdown_file() {
+    fn test_is_note_file() {
         let config = Config::default();
         let collector = NotesCollector::new(&config);
 
-        assert!(collector.is_markdown_file(Path::new("test.md")));
-        assert!(collector.is_markdown_file(Path::new("test.markdown")));
-        assert!(collector.is_markdown_file(Path::new("test.MD")));
-        assert!(!collector.is_markdown_file(Path::new("test.txt")));
-        assert!(!collector.is_markdown_file(Path::new("test")));
+        assert!(collector.is_note_file(Path::new("test.md")));
+        assert!(collector.is_note_file(Path::new("test.markdown")));
+        assert!(collector.is_note_file(Path::new("test.MD")));
+        assert!(!collector.is_note_file(Path::new("test.txt")));
+        assert!(!collector.is_note_file(Path::new("test")));
+    }
+
+    #[test]
+    fn test_is_note_file_custom_extensions() {
+        let config = Config {
+            note_extensions: vec!["txt".to_string(), ".org".to_string()],
+            ..Config::default()
+        };
+        let collector = NotesCollector::new(&config);
+
+        assert!(collector.is_note_file(Path::new("test.txt")));
+        assert!(collector.is_note_file(Path::new("test.ORG")));
+        assert!(!collector.is_note_file(Path::new("test.md")));
+    }
+
+    #[test]
+    fn test_collect_plain_text_notes() {
+        let temp_dir = TempDir::new().unwrap();
+        let notes_dir = temp_dir.path().to_path_buf();
+
+        fs::write(notes_dir.join("journal.txt"), "Plain text. No markup here.").unwrap();
+        fs::write(notes_dir.join("ideas.md"), "# Ideas").unwrap();
+
+        let config = Config {
+            notes_dirs: vec![notes_dir],
+            note_extensions: vec!["txt".to_string()],
+            ..Config::default()
+        };
+        let collector = NotesCollector::new(&config);
+        let mut state = State::default();
+        let since = Utc::now() - chrono::Duration::hours(1);
+
+        let notes = collector.collect_between(&mut state, since, None).unwrap();
+
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].excerpt, "Plain text. No markup here.");
+    }
+
+    #[test]
+    fn test_resolve_wikilinks() {
+        let note_dir = Path::new("/notes");
+        let targets = HashMap::from([
+            (
+                wikilink_key("other-note"),
+                PathBuf::from("/notes/other-note.md"),
+            ),
+            (
+                wikilink_key("Meeting Log"),
+                PathBuf::from("/notes/Meeting Log.md"),
+            ),
+            (
+                wikilink_key("journal"),
+                PathBuf::from("/journal/journal.md"),
+            ),
+        ]);
+
+        assert_eq!(
+            resolve_wikilinks("See [[Other Note]].", note_dir, &targets),
+            "See [Other Note](other-note.md)."
+        );
+        assert_eq!(
+            resolve_wikilinks("[[meeting log#Actions|actions]]", note_dir, &targets),
+            "[actions](<Meeting Log.md>)"
+        );
+        assert_eq!(
+            resolve_wikilinks("[[Journal]]", note_dir, &targets),
+            "[Journal](/journal/journal.md)"
+        );
+        assert_eq!(
+            resolve_wikilinks("[[Missing]] and [[broken\n]]", note_dir, &targets),
+            "[[Missing]] and [[broken\n]]"
+        );
+    }
+
+    #[test]
+    fn test_collect_resolves_wikilinks_when_enabled() {
+        let temp_dir = TempDir::new().unwrap();
+        let notes_dir = temp_dir.path().to_path_buf();
+
+        fs::write(notes_dir.join("idea.md"), "Builds on [[Other Note]].").unwrap();
+        fs::write(notes_dir.join("other-note.md"), "Background.").unwrap();
+
+        let mut config = Config {
+            notes_dirs: vec![notes_dir],
+            ..Config::default()
+        };
+        let since = Utc::now() - chrono::Duration::hours(1);
+
+        let notes = NotesCollector::new(&config)
+            .collect_between(&mut State::default(), since, None)
+            .unwrap();
+        let idea = notes.iter().find(|n| n.path.ends_with("idea.md")).unwrap();
+        assert_eq!(idea.excerpt, "Builds on [[Other Note]].");
+
+        config.resolve_wikilinks = true;
+        let notes = NotesCollector::new(&config)
+            .collect_between(&mut State::default(), since, None)
+            .unwrap();
+        let idea = notes.iter().find(|n| n.path.ends_with("idea.md")).unwrap();
+        assert_eq!(idea.excerpt, "Builds on [Other Note](other-note.md).");
     }
 
     #[test]
@@ -228,7 +855,7 @@ mod tests {
         let config = Config::default();
         let collector = NotesCollector::new(&config);
 
-        let excerpt = collector.extract_excerpt(&note_file).unwrap();
+        let excerpt = collector.extract_excerpt(&collector.read_note(&note_file).unwrap());
         assert_eq!(excerpt, content);
     }
 
@@ -244,10 +871,57 @@ mod tests {
         let config = Config::default();
         let collector = NotesCollector::new(&config);
 
-        let excerpt = collector.extract_excerpt(&note_file).unwrap();
+        let excerpt = collector.extract_excerpt(&collector.read_note(&note_file).unwrap());
         assert!(excerpt.len() <= config.limits.max_chars_per_item + 3); // +3 for "..."
     }
 
+    #[test]
+    fn test_outline() {
+        let content = "---\ntitle: Plan\n---\n# Plan\n\nIntro.\n\n## Goals ##\n\n```\n# not a heading\n```\n\n### Details\n#hashtag\n## Risks\n";
+        assert_eq!(
+            outline(content).unwrap(),
+            "- Plan\n  - Goals\n    - Details\n  - Risks"
+        );
+
+        // Nesting starts at the shallowest heading used
+        assert_eq!(outline("## A\n### B\n").unwrap(), "- A\n  - B");
+        assert_eq!(outline("No headings here."), None);
+    }
+
+    #[test]
+    fn test_first_paragraph() {
+        let content = "---\ntags: [a]\n---\n\n# Title\n\nFirst line\nsecond line.\n\nLater.\n";
+        assert_eq!(
+            first_paragraph(content).unwrap(),
+            "First line\nsecond line."
+        );
+
+        // A lone "---" line is a thematic break, not frontmatter
+        assert_eq!(first_paragraph("---\nText").unwrap(), "---\nText");
+        assert_eq!(first_paragraph("# Only a heading\n"), None);
+    }
+
+    #[test]
+    fn test_extract_excerpt_modes() {
+        let temp_dir = TempDir::new().unwrap();
+        let note_file = temp_dir.path().join("note.md");
+        fs::write(&note_file, "# Title\n\nBody text.\n\n## Section\n").unwrap();
+
+        let mut config = Config::default();
+        let excerpt = |config: &Config| {
+            let collector = NotesCollector::new(config);
+            collector.extract_excerpt(&collector.read_note(&note_file).unwrap())
+        };
+
+        assert_eq!(excerpt(&config), "# Title\n\nBody text.\n\n## Section");
+
+        config.excerpt_mode = ExcerptMode::Outline;
+        assert_eq!(excerpt(&config), "- Title\n  - Section");
+
+        config.excerpt_mode = ExcerptMode::FirstParagraph;
+        assert_eq!(excerpt(&config), "Body text.");
+    }
+
     #[test]
     fn test_extract_excerpt_with_sentence() {
         let temp_dir = TempDir::new().unwrap();
@@ -260,10 +934,43 @@ mod tests {
         let config = Config::default();
         let collector = NotesCollector::new(&config);
 
-        let excerpt = collector.extract_excerpt(&note_file).unwrap();
+        let excerpt = collector.extract_excerpt(&collector.read_note(&note_file).unwrap());
         assert!(excerpt.ends_with('.'));
     }
 
+    #[test]
+    fn test_cut_excerpt_boundaries() {
+        use ExcerptBoundary::*;
+
+        let content = "One. Two.\n\nThree four five";
+        assert_eq!(cut_excerpt(content, 100, &[Paragraph]), content);
+        assert_eq!(cut_excerpt(content, 20, &[Paragraph, Word]), "One. Two.");
+        assert_eq!(cut_excerpt(content, 8, &[Paragraph, Sentence]), "One.");
+        assert_eq!(cut_excerpt(content, 20, &[Word]), "One. Two.\n\nThree...");
+        assert_eq!(cut_excerpt(content, 20, &[Sentence, Word]), "One. Two.");
+
+        // A boundary exactly at the limit counts
+        assert_eq!(
+            cut_excerpt("Short one. Rest", 10, &[Sentence]),
+            "Short one."
+        );
+        // Decimal points aren't sentence ends
+        assert_eq!(
+            cut_excerpt("Version 1.2 works", 12, &[Sentence, Word]),
+            "Version 1.2..."
+        );
+    }
+
+    #[test]
+    fn test_cut_excerpt_falls_back_to_char_cut() {
+        use ExcerptBoundary::*;
+
+        assert_eq!(cut_excerpt("abcdefgh", 4, &[Paragraph, Word]), "abcd...");
+        assert_eq!(cut_excerpt("abcdefgh", 4, &[]), "abcd...");
+        // Counts characters, never splitting a multi-byte one
+        assert_eq!(cut_excerpt("äöüäöü", 3, &[Word]), "äöü...");
+    }
+
     #[test]
     fn test_collect_from_empty_config() {
         let config = Config::default();
@@ -271,7 +978,7 @@ mod tests {
         let mut state = State::default();
         let since = Utc::now() - chrono::Duration::hours(24);
 
-        let result = collector.collect(&mut state, since);
+        let result = collector.collect_between(&mut state, since, None);
         assert!(result.is_ok());
         assert_eq!(result.unwrap().len(), 0);
     }
@@ -294,7 +1001,7 @@ mod tests {
         let mut state = State::default();
         let since = Utc::now() - chrono::Duration::hours(1);
 
-        let notes = collector.collect(&mut state, since).unwrap();
+        let notes = collector.collect_between(&mut state, since, None).unwrap();
 
         // Should have 2 markdown files
         assert_eq!(notes.len(), 2);
@@ -302,6 +1009,159 @@ mod tests {
         assert!(notes[0].modified_at >= notes[1].modified_at);
         // All should be marked as new on first run
         assert!(notes.iter().all(|n| n.change == ChangeKind::New));
+        // Counts cover the whole note
+        assert!(notes.iter().all(|n| n.word_count == 3));
+        assert_eq!(notes[0].char_count, "Second note content.".len());
+    }
+
+    #[test]
+    fn test_collect_todos_from_keyword_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let note = temp_dir.path().join("meeting.md");
+        fs::write(&note, "# Sync\nTODO: Send notes\ntask: Book room\n").unwrap();
+
+        let mut config = Config::default();
+        config.notes_dirs.push(temp_dir.path().to_path_buf());
+        config.note_todo_keywords = vec!["TODO".to_string(), "TASK".to_string()];
+
+        let collector = NotesCollector::new(&config);
+        let mut state = State::default();
+        let since = Utc::now() - chrono::Duration::hours(1);
+
+        let notes = collector.collect_between(&mut state, since, None).unwrap();
+        let todos = collector.collect_todos(&notes, &mut state);
+        let found: Vec<(&str, usize)> =
+            todos.iter().map(|t| (t.content.as_str(), t.line)).collect();
+        assert_eq!(found, vec![("Send notes", 2), ("Book room", 3)]);
+        assert!(todos.iter().all(|t| t.file == note));
+
+        // Editing the note reports only the new TODO
+        fs::write(
+            &note,
+            "# Sync\nTODO: Send notes\ntask: Book room\nTODO: Follow up\n",
+        )
+        .unwrap();
+        let notes = collector.collect_between(&mut state, since, None).unwrap();
+        let todos = collector.collect_todos(&notes, &mut state);
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].content, "Follow up");
+        assert_eq!(todos[0].change, ChangeKind::New);
+    }
+
+    #[test]
+    fn test_collect_skips_notes_modified_after_until() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("note.md"), "Written today.").unwrap();
+
+        let mut config = Config::default();
+        config.notes_dirs.push(temp_dir.path().to_path_buf());
+
+        let collector = NotesCollector::new(&config);
+        let since = Utc::now() - chrono::Duration::days(2);
+
+        // Backfilling yesterday leaves out today's edit
+        let until = Utc::now() - chrono::Duration::days(1);
+        let notes = collector
+            .collect_between(&mut State::default(), since, Some(until))
+            .unwrap();
+        assert!(notes.is_empty());
+
+        let notes = collector
+            .collect_between(&mut State::default(), since, None)
+            .unwrap();
+        assert_eq!(notes.len(), 1);
+    }
+
+    #[test]
+    fn test_collect_skips_hidden_notes() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("note.md"), "A real note.").unwrap();
+        fs::write(temp_dir.path().join(".template.md"), "{{title}}").unwrap();
+
+        let mut config = Config::default();
+        config.notes_dirs.push(temp_dir.path().to_path_buf());
+        let since = Utc::now() - chrono::Duration::hours(1);
+
+        let notes = NotesCollector::new(&config)
+            .collect_between(&mut State::default(), since, None)
+            .unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].path, temp_dir.path().join("note.md"));
+
+        config.include_hidden_notes = true;
+        let notes = NotesCollector::new(&config)
+            .collect_between(&mut State::default(), since, None)
+            .unwrap();
+        assert_eq!(notes.len(), 2);
+    }
+
+    #[test]
+    fn test_collect_applies_depth_and_globs() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        for dir in ["journal", "drafts", ".obsidian"] {
+            fs::create_dir(vault.join(dir)).unwrap();
+        }
+        fs::write(vault.join("top.md"), "Top.").unwrap();
+        fs::write(vault.join("journal/today.md"), "Journal.").unwrap();
+        fs::write(vault.join("drafts/idea.md"), "Draft.").unwrap();
+        fs::write(vault.join(".obsidian/workspace.md"), "Metadata.").unwrap();
+
+        let mut config = Config::default();
+        config.notes_dirs.push(vault.to_path_buf());
+        let since = Utc::now() - chrono::Duration::hours(1);
+
+        let collect = |config: &Config| {
+            let mut paths: Vec<PathBuf> = NotesCollector::new(config)
+                .collect_between(&mut State::default(), since, None)
+                .unwrap()
+                .into_iter()
+                .map(|note| note.path.strip_prefix(vault).unwrap().to_path_buf())
+                .collect();
+            paths.sort();
+            paths
+        };
+
+        // Only the top level by default
+        assert_eq!(collect(&config), vec![PathBuf::from("top.md")]);
+
+        config.notes_max_depth = 3;
+        config.notes_exclude = vec!["drafts/**".to_string()];
+        assert_eq!(
+            collect(&config),
+            vec![PathBuf::from("journal/today.md"), PathBuf::from("top.md")]
+        );
+
+        config.notes_include = vec!["journal/**".to_string()];
+        assert_eq!(collect(&config), vec![PathBuf::from("journal/today.md")]);
+
+        config.notes_include = vec!["[".to_string()];
+        assert!(NotesCollector::new(&config)
+            .collect_between(&mut State::default(), since, None)
+            .is_err());
+    }
+
+    #[test]
+    fn test_note_counts_use_full_content() {
+        let temp_dir = TempDir::new().unwrap();
+        let notes_dir = temp_dir.path().to_path_buf();
+
+        // Much longer than the excerpt limit
+        fs::write(notes_dir.join("long.md"), "word ".repeat(1000)).unwrap();
+
+        let mut config = Config::default();
+        config.limits.max_chars_per_item = 50;
+        config.notes_dirs.push(notes_dir);
+
+        let collector = NotesCollector::new(&config);
+        let mut state = State::default();
+        let since = Utc::now() - chrono::Duration::hours(1);
+
+        let notes = collector.collect_between(&mut state, since, None).unwrap();
+
+        assert!(notes[0].excerpt.len() <= 53);
+        assert_eq!(notes[0].word_count, 1000);
+        assert_eq!(notes[0].char_count, 5000);
     }
 
     #[test]
@@ -319,7 +1179,7 @@ mod tests {
         let since = Utc::now() - chrono::Duration::hours(1);
 
         // First collection
-        let notes = collector.collect(&mut state, since).unwrap();
+        let notes = collector.collect_between(&mut state, since, None).unwrap();
         assert_eq!(notes.len(), 1);
         assert_eq!(notes[0].change, ChangeKind::New);
 
@@ -328,11 +1188,128 @@ mod tests {
         fs::write(notes_dir.join("note.md"), "Modified content.").unwrap();
 
         // Second collection
-        let notes2 = collector.collect(&mut state, since).unwrap();
+        let notes2 = collector.collect_between(&mut state, since, None).unwrap();
         assert_eq!(notes2.len(), 1);
         assert_eq!(notes2[0].change, ChangeKind::Modified);
     }
 
+    #[test]
+    fn test_detect_renamed_note() {
+        let temp_dir = TempDir::new().unwrap();
+        let notes_dir = temp_dir.path().to_path_buf();
+
+        fs::write(notes_dir.join("idea.md"), "A note worth keeping.").unwrap();
+        fs::write(notes_dir.join("other.md"), "Something else.").unwrap();
+
+        let mut config = Config::default();
+        config.notes_dirs.push(notes_dir.clone());
+
+        let collector = NotesCollector::new(&config);
+        let mut state = State::default();
+        let since = Utc::now() - chrono::Duration::hours(1);
+
+        collector.collect_between(&mut state, since, None).unwrap();
+
+        fs::rename(notes_dir.join("idea.md"), notes_dir.join("big-idea.md")).unwrap();
+        // A copy keeps its original, so it is a new note
+        fs::copy(notes_dir.join("other.md"), notes_dir.join("other-copy.md")).unwrap();
+
+        let notes = collector.collect_between(&mut state, since, None).unwrap();
+
+        let renamed = notes
+            .iter()
+            .find(|n| n.path.ends_with("big-idea.md"))
+            .unwrap();
+        assert_eq!(renamed.change, ChangeKind::Modified);
+        assert_eq!(renamed.renamed_from, Some(notes_dir.join("idea.md")));
+
+        let copy = notes
+            .iter()
+            .find(|n| n.path.ends_with("other-copy.md"))
+            .unwrap();
+        assert_eq!(copy.change, ChangeKind::New);
+        assert_eq!(copy.renamed_from, None);
+
+        // The old path is dropped from state
+        match state::get_source(&state, &notes_dir.to_string_lossy()) {
+            Some(SourceState::Notes { files, .. }) => {
+                assert!(
+                    !files.contains_key(&notes_dir.join("idea.md").to_string_lossy().to_string())
+                );
+                assert!(files
+                    .contains_key(&notes_dir.join("big-idea.md").to_string_lossy().to_string()));
+            }
+            _ => panic!("Expected Notes state"),
+        }
+    }
+
+    #[test]
+    fn test_detect_renamed_note_outside_window() {
+        let temp_dir = TempDir::new().unwrap();
+        let notes_dir = temp_dir.path().to_path_buf();
+        fs::write(notes_dir.join("idea.md"), "A note worth keeping.").unwrap();
+
+        let mut config = Config::default();
+        config.notes_dirs.push(notes_dir.clone());
+
+        let collector = NotesCollector::new(&config);
+        let mut state = State::default();
+        let since = Utc::now() - chrono::Duration::hours(1);
+        collector.collect_between(&mut state, since, None).unwrap();
+
+        // `mv` keeps the old mtime, from before the next window
+        fs::rename(notes_dir.join("idea.md"), notes_dir.join("big-idea.md")).unwrap();
+        fs::File::options()
+            .write(true)
+            .open(notes_dir.join("big-idea.md"))
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(3 * 3600))
+            .unwrap();
+
+        let notes = collector.collect_between(&mut state, since, None).unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].path, notes_dir.join("big-idea.md"));
+        assert_eq!(notes[0].renamed_from, Some(notes_dir.join("idea.md")));
+
+        // Not reported again, and never as removed
+        let notes = collector.collect_between(&mut state, since, None).unwrap();
+        assert!(notes.is_empty());
+    }
+
+    #[test]
+    fn test_detect_removed_note() {
+        let temp_dir = TempDir::new().unwrap();
+        let notes_dir = temp_dir.path().to_path_buf();
+        fs::write(notes_dir.join("keep.md"), "Staying.").unwrap();
+        fs::write(notes_dir.join("gone.md"), "Leaving.").unwrap();
+
+        let mut config = Config::default();
+        config.notes_dirs.push(notes_dir.clone());
+
+        let collector = NotesCollector::new(&config);
+        let mut state = State::default();
+        let since = Utc::now() - chrono::Duration::hours(1);
+        collector.collect_between(&mut state, since, None).unwrap();
+
+        // Reported even though the window has moved past its last change
+        fs::remove_file(notes_dir.join("gone.md")).unwrap();
+        let later = Utc::now() + chrono::Duration::hours(1);
+        let notes = collector.collect_between(&mut state, later, None).unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].path, notes_dir.join("gone.md"));
+        assert_eq!(notes[0].change, ChangeKind::Removed);
+
+        // Only once
+        let notes = collector.collect_between(&mut state, since, None).unwrap();
+        assert!(notes.iter().all(|note| note.change != ChangeKind::Removed));
+    }
+
+    #[test]
+    fn test_content_hash_is_stable() {
+        assert_eq!(content_hash(""), "cbf29ce484222325");
+        assert_eq!(content_hash("a"), "af63dc4c8601ec8c");
+    }
+
     #[test]
     fn test_respects_max_note_files_limit() {
         let temp_dir = TempDir::new().unwrap();
@@ -345,15 +1322,71 @@ mod tests {
         }
 
         let mut config = Config::default();
-        config.notes_dirs.push(notes_dir);
+        config.notes_dirs.push(notes_dir.clone());
         config.limits.max_note_files = 30;
 
         let collector = NotesCollector::new(&config);
         let mut state = State::default();
         let since = Utc::now() - chrono::Duration::hours(1);
 
-        let notes = collector.collect(&mut state, since).unwrap();
+        let notes = collector.collect_between(&mut state, since, None).unwrap();
 
         assert_eq!(notes.len(), 30);
+
+        // Removals are reported on top of the limit
+        for i in 0..5 {
+            fs::remove_file(notes_dir.join(format!("note{}.md", i))).unwrap();
+        }
+        let notes = collector.collect_between(&mut state, since, None).unwrap();
+        let removed = notes
+            .iter()
+            .filter(|note| note.change == ChangeKind::Removed)
+            .count();
+        assert_eq!(removed, 5);
+        assert_eq!(notes.len(), 35);
+    }
+
+    #[test]
+    fn test_respects_max_notes_total_chars() {
+        let temp_dir = TempDir::new().unwrap();
+        let notes_dir = temp_dir.path().to_path_buf();
+
+        // 10 characters each, oldest first
+        for i in 0..5 {
+            fs::write(notes_dir.join(format!("note{}.md", i)), "Ten chars.").unwrap();
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        let mut config = Config::default();
+        config.notes_dirs.push(notes_dir.clone());
+        config.limits.max_notes_total_chars = Some(25);
+
+        let collector = NotesCollector::new(&config);
+        let mut state = State::default();
+        let since = Utc::now() - chrono::Duration::hours(1);
+
+        let notes = collector.collect_between(&mut state, since, None).unwrap();
+
+        // The newest notes fit; the rest are counted
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[0].path, notes_dir.join("note4.md"));
+        assert_eq!(collector.omitted(), 3);
+
+        // A newest note over the budget doesn't crowd out the others, and
+        // removals don't count against it
+        fs::remove_file(notes_dir.join("note0.md")).unwrap();
+        fs::write(notes_dir.join("huge.md"), "x".repeat(100)).unwrap();
+        let notes = collector.collect_between(&mut state, since, None).unwrap();
+        let paths: Vec<_> = notes.iter().map(|note| note.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                notes_dir.join("note4.md"),
+                notes_dir.join("note3.md"),
+                notes_dir.join("note0.md")
+            ]
+        );
+        assert_eq!(notes[2].change, ChangeKind::Removed);
+        assert_eq!(collector.omitted(), 3);
     }
 }
@@ -1,26 +1,43 @@
-use chrono::Utc;
+use chrono::{NaiveDate, Utc};
+use std::cell::Cell;
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
+use std::time::Instant;
 
-use crate::config::Config;
+use crate::collectors::{Collected, Collector, Source, Warnings, Window};
+use crate::config::{Config, TodoFile, TodoFormat};
+use crate::display::log;
 use crate::error::{ChronicleError, Result};
-use crate::models::{ChangeKind, Todo, TodoStatus};
-use crate::state::{self, SourceState, State};
+use crate::models::{ChangeKind, CollectionWarning, Todo, TodoLocation, TodoStatus, TodoTotals};
+use crate::state::{self, SourceState, State, TodoItemState};
 
 /// TODO collector for parsing TODO/Inbox markdown files
 pub struct TodoCollector<'a> {
     config: &'a Config,
+    warnings: Warnings,
+    totals: Cell<TodoTotals>,
 }
 
 impl<'a> TodoCollector<'a> {
     pub fn new(config: &'a Config) -> Self {
-        Self { config }
+        Self {
+            config,
+            warnings: Warnings::new("todos"),
+            totals: Cell::new(TodoTotals::default()),
+        }
+    }
+
+    /// Open and done TODOs in the files read by the last `collect_files`,
+    /// including unchanged ones
+    pub fn totals(&self) -> TodoTotals {
+        self.totals.get()
     }
 
     /// Collect TODOs from all configured files
-    pub fn collect(&self, state: &mut State) -> Result<Vec<Todo>> {
+    pub fn collect_files(&self, state: &mut State) -> Result<Vec<Todo>> {
         let mut all_todos = Vec::new();
+        self.totals.set(TodoTotals::default());
 
         for todo_file in &self.config.todo_files {
             match self.collect_file(todo_file, state) {
@@ -28,20 +45,53 @@ impl<'a> TodoCollector<'a> {
                     all_todos.extend(todos);
                 }
                 Err(e) => {
-                    eprintln!(
-                        "Warning: Skipping TODO file '{}': {}",
-                        todo_file.display(),
+                    self.warnings.skipped(format!(
+                        "Skipping TODO file '{}': {}",
+                        todo_file.path.display(),
                         e
-                    );
+                    ));
                 }
             }
         }
 
+        if self.config.dedupe_todos {
+            all_todos = self.dedupe(all_todos);
+        }
+
         Ok(all_todos)
     }
 
+    /// Collapse TODOs with the same normalized content and status
+    ///
+    /// The first occurrence (in `todo_files` order) is kept, with the others
+    /// recorded in `also_in`. Duplicates within one file are kept separate.
+    fn dedupe(&self, todos: Vec<Todo>) -> Vec<Todo> {
+        let mut kept: Vec<Todo> = Vec::with_capacity(todos.len());
+        let mut index: HashMap<(String, TodoStatus), usize> = HashMap::new();
+
+        for todo in todos {
+            let key = (self.normalize_content(&todo.content), todo.status);
+            match index.get(&key).map(|&i| &mut kept[i]) {
+                Some(first) if first.file != todo.file => {
+                    first.also_in.push(TodoLocation {
+                        file: todo.file,
+                        line: todo.line,
+                    });
+                }
+                _ => {
+                    index.entry(key).or_insert(kept.len());
+                    kept.push(todo);
+                }
+            }
+        }
+
+        kept
+    }
+
     /// Collect TODOs from a single file
-    fn collect_file(&self, file_path: &Path, state: &mut State) -> Result<Vec<Todo>> {
+    fn collect_file(&self, todo_file: &TodoFile, state: &mut State) -> Result<Vec<Todo>> {
+        let file_path = todo_file.path.as_path();
+
         // Read file content
         let content = fs::read_to_string(file_path).map_err(|e| {
             ChronicleError::Collector(format!(
@@ -51,36 +101,94 @@ impl<'a> TodoCollector<'a> {
             ))
         })?;
 
-        // Get file metadata for last modified time
-        let _metadata = fs::metadata(file_path)?;
-
         // Parse todos from content
-        let mut todos = self.parse_todos(&content, file_path)?;
+        let todos = match todo_file.format {
+            TodoFormat::Markdown => self.parse_todos(&content, file_path)?,
+            TodoFormat::TodoTxt => self.parse_todo_txt(&content, file_path),
+            TodoFormat::Org => self.parse_org(&content, file_path),
+        };
+
+        let mut totals = self.totals.get();
+        totals.add(&todos, &self.config.completed_statuses);
+        self.totals.set(totals);
 
+        Ok(self.track_changes(todos, file_path, state))
+    }
+
+    /// Compare the TODOs found in `file_path` with the last run, record them
+    /// in state, and return the changed ones
+    pub fn track_changes(
+        &self,
+        mut todos: Vec<Todo>,
+        file_path: &Path,
+        state: &mut State,
+    ) -> Vec<Todo> {
         // Detect changes using state
         self.detect_changes(&mut todos, state, file_path);
 
         // Update state with all TODOs (before filtering)
         self.update_state_for_file(state, file_path, &todos);
 
-        // Filter out unchanged todos
-        let changed_todos: Vec<Todo> = todos
+        // Filter out unchanged todos, unless the full list is wanted
+        todos
             .into_iter()
-            .filter(|t| t.change != ChangeKind::Unchanged)
-            .collect();
+            .filter(|t| self.config.show_unchanged_todos || t.change != ChangeKind::Unchanged)
+            .collect()
+    }
 
-        Ok(changed_todos)
+    /// Parse `KEYWORD: task` lines (e.g. `TODO: Call Sam`) as pending TODOs
+    ///
+    /// Keywords match case-insensitively at the start of a line, optionally
+    /// after a list bullet.
+    pub fn parse_keyword_todos(
+        &self,
+        content: &str,
+        file_path: &Path,
+        keywords: &[String],
+    ) -> Vec<Todo> {
+        content
+            .lines()
+            .enumerate()
+            .filter_map(|(line_num, line)| {
+                let task = keyword_task(line, keywords)?;
+                let (content, tags) = extract_tags(task);
+                Some(Todo {
+                    content,
+                    status: TodoStatus::Pending,
+                    change: ChangeKind::New,
+                    previous_status: None,
+                    previous_content: None,
+                    file: file_path.to_path_buf(),
+                    line: line_num + 1,
+                    depth: 0,
+                    tags,
+                    priority: None,
+                    deadline: None,
+                    also_in: Vec::new(),
+                })
+            })
+            .collect()
     }
 
     /// Parse TODO items from file content
     fn parse_todos(&self, content: &str, file_path: &Path) -> Result<Vec<Todo>> {
         let mut todos = Vec::new();
 
+        // Indentation widths of the enclosing TODOs, outermost first
+        let mut parents: Vec<usize> = Vec::new();
+
         for (line_num, line) in content.lines().enumerate() {
             let trimmed = line.trim();
 
             // Check for TODO patterns
-            if let Some(todo) = self.parse_todo_line(trimmed, file_path, line_num + 1)? {
+            if let Some(mut todo) = self.parse_todo_line(trimmed, file_path, line_num + 1)? {
+                let indent = indent_width(line);
+                while parents.last().is_some_and(|&parent| parent >= indent) {
+                    parents.pop();
+                }
+
+                todo.depth = parents.len();
+                parents.push(indent);
                 todos.push(todo);
             }
         }
@@ -88,6 +196,74 @@ impl<'a> TodoCollector<'a> {
         Ok(todos)
     }
 
+    /// Parse todo.txt content: one task per line, `x ` marking done ones
+    ///
+    /// The priority, `+project`s and `@context`s (as tags), and dates are
+    /// taken out of the content.
+    fn parse_todo_txt(&self, content: &str, file_path: &Path) -> Vec<Todo> {
+        content
+            .lines()
+            .enumerate()
+            .filter_map(|(line_num, line)| {
+                let (status, priority, content, tags) = todo_txt_task(line)?;
+                Some(Todo {
+                    content,
+                    status,
+                    change: ChangeKind::New, // Will be updated by detect_changes
+                    previous_status: None,
+                    previous_content: None,
+                    file: file_path.to_path_buf(),
+                    line: line_num + 1,
+                    depth: 0,
+                    tags,
+                    priority,
+                    deadline: None,
+                    also_in: Vec::new(),
+                })
+            })
+            .collect()
+    }
+
+    /// Parse org-mode content: headings with a TODO keyword, nested by
+    /// heading level
+    ///
+    /// A `DEADLINE:` on the lines under a heading sets its deadline.
+    fn parse_org(&self, content: &str, file_path: &Path) -> Vec<Todo> {
+        let mut todos: Vec<Todo> = Vec::new();
+        // Whether the last heading seen is the last TODO
+        let mut under_todo = false;
+
+        for (line_num, line) in content.lines().enumerate() {
+            if let Some(level) = org_heading_level(line) {
+                under_todo = false;
+                let Some((status, priority, content, tags)) = org_task(&line[level..]) else {
+                    continue;
+                };
+                todos.push(Todo {
+                    content,
+                    status,
+                    change: ChangeKind::New, // Will be updated by detect_changes
+                    previous_status: None,
+                    previous_content: None,
+                    file: file_path.to_path_buf(),
+                    line: line_num + 1,
+                    depth: level - 1,
+                    tags,
+                    priority,
+                    deadline: None,
+                    also_in: Vec::new(),
+                });
+                under_todo = true;
+            } else if under_todo {
+                if let (Some(todo), Some(deadline)) = (todos.last_mut(), org_deadline(line)) {
+                    todo.deadline = Some(deadline);
+                }
+            }
+        }
+
+        todos
+    }
+
     /// Parse a single TODO line
     fn parse_todo_line(
         &self,
@@ -95,128 +271,382 @@ impl<'a> TodoCollector<'a> {
         file_path: &Path,
         line_num: usize,
     ) -> Result<Option<Todo>> {
-        let (status, content) = if let Some(content) = line.strip_prefix("- [ ] ") {
+        // CommonMark allows `-`, `*` and `+` as bullet markers
+        let Some(checkbox) = line.trim_start().strip_prefix(['-', '*', '+']) else {
+            return Ok(None);
+        };
+
+        let (status, content) = if let Some(content) = checkbox.strip_prefix(" [ ] ") {
             (TodoStatus::Pending, content)
-        } else if let Some(content) = line.strip_prefix("- [x] ") {
+        } else if let Some(content) = checkbox.strip_prefix(" [x] ") {
             (TodoStatus::Done, content)
-        } else if let Some(content) = line.strip_prefix("- [~] ") {
+        } else if let Some(content) = checkbox.strip_prefix(" [~] ") {
             (TodoStatus::InProgress, content)
         } else {
             return Ok(None);
         };
 
-        let content = content.to_string();
+        let (content, tags) = extract_tags(content);
 
         Ok(Some(Todo {
             content,
             status,
             change: ChangeKind::New, // Will be updated by detect_changes
             previous_status: None,
+            previous_content: None,
             file: file_path.to_path_buf(),
             line: line_num,
+            depth: 0, // Set by parse_todos from indentation
+            tags,
+            priority: None,
+            deadline: None,
+            also_in: Vec::new(),
         }))
     }
 
     /// Detect changes in TODOs compared to state
     fn detect_changes(&self, todos: &mut [Todo], state: &State, file_path: &Path) {
-        let source_key = file_path.to_string_lossy().to_string();
+        let source_key = state::path_key(file_path);
         let source_state = state::get_source(state, &source_key);
 
-        // Get previous TODO hashes if available
-        let previous_hashes = match source_state {
-            Some(SourceState::Todo { item_hashes, .. }) => Some(item_hashes),
-            _ => None,
+        // Get previous TODO items if available
+        let previous_items = match source_state {
+            Some(SourceState::Todo { items, .. }) => items,
+            _ => {
+                // No previous state, all TODOs are new
+                for todo in todos.iter_mut() {
+                    todo.change = ChangeKind::New;
+                }
+                return;
+            }
         };
 
-        if let Some(prev_hashes) = previous_hashes {
-            // Build map of previous todos by hash
-            let prev_map: HashMap<String, ()> =
-                prev_hashes.iter().map(|h| (h.clone(), ())).collect();
+        // A TODO is identified by its normalized content within the file, so
+        // reordering the list doesn't read as removed-and-new. The line number
+        // only disambiguates duplicate content: exact line matches are paired
+        // first, then remaining duplicates by closest line.
+        let keys: Vec<String> = todos
+            .iter()
+            .map(|todo| self.normalize_content(&todo.content))
+            .collect();
+        let mut previous_by_key: HashMap<String, Vec<usize>> = HashMap::new();
+        for (j, item) in previous_items.iter().enumerate() {
+            previous_by_key
+                .entry(self.normalize_content(&item.content))
+                .or_default()
+                .push(j);
+        }
 
-            for todo in todos.iter_mut() {
-                let hash = self.hash_todo(todo);
+        let mut matches: Vec<Option<usize>> = vec![None; todos.len()];
+        let mut used = vec![false; previous_items.len()];
 
-                if prev_map.contains_key(&hash) {
-                    // TODO exists unchanged
-                    todo.change = ChangeKind::Unchanged;
-                } else {
-                    // Check if content exists but status changed
-                    let content_hash = self.hash_todo_content(&todo.content, &todo.file, todo.line);
-                    let mut found_previous_status = false;
-
-                    for prev_hash in prev_hashes {
-                        // Check if this hash contains the same content (after the status prefix)
-                        // Hash format is "Status:file:line:content"
-                        if prev_hash.contains(&content_hash) {
-                            // This is the same TODO but with different status
-                            todo.change = ChangeKind::Modified;
-                            // Try to extract previous status
-                            if let Some(status) = self.extract_status_from_hash(prev_hash) {
-                                todo.previous_status = Some(status);
-                            }
-                            found_previous_status = true;
-                            break;
-                        }
-                    }
-
-                    if !found_previous_status {
-                        todo.change = ChangeKind::New;
-                    }
+        for same_line_only in [true, false] {
+            for (i, todo) in todos.iter().enumerate() {
+                if matches[i].is_some() {
+                    continue;
+                }
+
+                let Some(candidates) = previous_by_key.get(&keys[i]) else {
+                    continue;
+                };
+                let best = candidates
+                    .iter()
+                    .copied()
+                    .filter(|&j| {
+                        !used[j] && (!same_line_only || previous_items[j].line == todo.line)
+                    })
+                    .min_by_key(|&j| previous_items[j].line.abs_diff(todo.line));
+
+                if let Some(j) = best {
+                    used[j] = true;
+                    matches[i] = Some(j);
                 }
-            }
-        } else {
-            // No previous state, all TODOs are new
-            for todo in todos.iter_mut() {
-                todo.change = ChangeKind::New;
             }
         }
-    }
 
-    /// Generate hash for a TODO item (content + status + location)
-    fn hash_todo(&self, todo: &Todo) -> String {
-        format!(
-            "{:?}:{}:{}:{}",
-            todo.status,
-            todo.file.display(),
-            todo.line,
-            todo.content
-        )
-    }
+        // A leftover TODO on the line of a leftover previous one with the
+        // same status had its text edited
+        let mut edited = vec![false; todos.len()];
+        for (i, todo) in todos.iter().enumerate() {
+            if matches[i].is_some() {
+                continue;
+            }
 
-    /// Generate hash for TODO content only (for detecting status changes)
-    fn hash_todo_content(&self, content: &str, file: &Path, line: usize) -> String {
-        format!("{}:{}:{}", file.display(), line, content)
-    }
+            let previous = previous_items.iter().enumerate().find(|(j, item)| {
+                !used[*j] && item.line == todo.line && item.status == todo.status
+            });
+            if let Some((j, _)) = previous {
+                used[j] = true;
+                matches[i] = Some(j);
+                edited[i] = true;
+            }
+        }
 
-    /// Extract status from hash string
-    fn extract_status_from_hash(&self, hash: &str) -> Option<TodoStatus> {
-        if hash.starts_with("Pending:") {
-            Some(TodoStatus::Pending)
-        } else if hash.starts_with("Done:") {
-            Some(TodoStatus::Done)
-        } else if hash.starts_with("InProgress:") {
-            Some(TodoStatus::InProgress)
-        } else {
-            None
+        for ((todo, matched), edited) in todos.iter_mut().zip(matches).zip(edited) {
+            match matched.map(|j| &previous_items[j]) {
+                Some(item) if edited => {
+                    todo.change = ChangeKind::Modified;
+                    todo.previous_status = Some(item.status);
+                    todo.previous_content = Some(item.content.clone());
+                }
+                Some(item) if item.status == todo.status => {
+                    todo.change = ChangeKind::Unchanged;
+                    todo.previous_status = Some(item.status);
+                }
+                Some(item) => {
+                    todo.change = ChangeKind::Modified;
+                    todo.previous_status = Some(item.status);
+                }
+                None => {
+                    todo.change = ChangeKind::New;
+                }
+            }
         }
     }
 
+    /// Normalize TODO content for identity matching (collapse whitespace)
+    ///
+    /// Tags are dropped too, so state written before tags were stripped from
+    /// `content` still matches.
+    fn normalize_content(&self, content: &str) -> String {
+        content
+            .split_whitespace()
+            .filter(|word| tag_name(word).is_none())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
     /// Update state for a single file with its TODOs
     fn update_state_for_file(&self, state: &mut State, file_path: &Path, todos: &[Todo]) {
-        let source_key = file_path.to_string_lossy().to_string();
-
-        let item_hashes: Vec<String> = todos.iter().map(|t| self.hash_todo(t)).collect();
+        let source_key = state::path_key(file_path);
+
+        let items: Vec<TodoItemState> = todos
+            .iter()
+            .map(|t| TodoItemState {
+                content: t.content.clone(),
+                status: t.status,
+                line: t.line,
+            })
+            .collect();
 
         let source_state = SourceState::Todo {
             last_checked: Utc::now(),
             last_modified: Utc::now(),
-            item_hashes,
+            items,
         };
 
         state::update_source(state, source_key, source_state);
     }
 }
 
+impl Collector for TodoCollector<'_> {
+    type Item = Todo;
+
+    fn collect(&self, state: &mut State, _window: Window) -> Result<Vec<Todo>> {
+        // Changes in these files are found against state, not by time
+        self.collect_files(state)
+    }
+
+    fn take_warnings(&self) -> Vec<CollectionWarning> {
+        self.warnings.take()
+    }
+}
+
+impl Source for TodoCollector<'_> {
+    fn name(&self) -> &'static str {
+        "todos"
+    }
+
+    fn collect_into(
+        &self,
+        state: &mut State,
+        window: Window,
+        collected: &mut Collected,
+    ) -> Result<()> {
+        let started = Instant::now();
+        let todos = self.collect(state, window)?;
+        log::detail(&format!(
+            "todos: parsed {} files, found {} changed items ({:.0?})",
+            self.config.todo_files.len(),
+            todos.len(),
+            started.elapsed()
+        ));
+
+        collected.todos.extend(todos);
+        collected.todo_totals = self.totals();
+        collected.warnings.extend(self.take_warnings());
+        Ok(())
+    }
+}
+
+/// The task after a leading `KEYWORD:` in `line`, if any keyword matches
+fn keyword_task<'a>(line: &'a str, keywords: &[String]) -> Option<&'a str> {
+    let line = line.trim_start();
+    let line = line
+        .strip_prefix(['-', '*', '+'])
+        .map_or(line, str::trim_start);
+
+    keywords.iter().find_map(|keyword| {
+        let keyword = keyword.trim_end_matches(':');
+        let head = line.get(..keyword.len())?;
+        let task = line[keyword.len()..].strip_prefix(':')?.trim();
+        (!keyword.is_empty() && head.eq_ignore_ascii_case(keyword) && !task.is_empty())
+            .then_some(task)
+    })
+}
+
+/// Split `#tag` tokens out of TODO content
+///
+/// Returns the content without tags (whitespace collapsed only if a tag was
+/// removed) and the tag names in order of appearance, without duplicates.
+fn extract_tags(content: &str) -> (String, Vec<String>) {
+    let mut tags: Vec<String> = Vec::new();
+    let mut words = Vec::new();
+
+    for word in content.split_whitespace() {
+        match tag_name(word) {
+            Some(tag) => {
+                if !tags.iter().any(|t| t == tag) {
+                    tags.push(tag.to_string());
+                }
+            }
+            None => words.push(word),
+        }
+    }
+
+    if tags.is_empty() {
+        (content.to_string(), tags)
+    } else {
+        (words.join(" "), tags)
+    }
+}
+
+/// Tag name if the word is a `#tag` (starting with a letter, so `#123` issue
+/// references are left alone)
+fn tag_name(word: &str) -> Option<&str> {
+    let tag = word.strip_prefix('#')?;
+    let starts_with_letter = tag.chars().next().is_some_and(char::is_alphabetic);
+    let valid = tag
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '/'));
+
+    (starts_with_letter && valid).then_some(tag)
+}
+
+/// Status, priority, content, and tags of a todo.txt line, if it has a task
+fn todo_txt_task(line: &str) -> Option<(TodoStatus, Option<char>, String, Vec<String>)> {
+    let mut rest = line.trim();
+    if rest.is_empty() {
+        return None;
+    }
+
+    let status = match rest.strip_prefix("x ") {
+        Some(done) => {
+            rest = done;
+            TodoStatus::Done
+        }
+        None => TodoStatus::Pending,
+    };
+
+    let mut priority = None;
+    if let [b'(', letter @ b'A'..=b'Z', b')', b' ', ..] = rest.as_bytes() {
+        priority = Some(*letter as char);
+        rest = &rest[4..];
+    }
+
+    // Completion and creation dates
+    for _ in 0..2 {
+        match rest.split_once(' ') {
+            Some((date, task)) if NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok() => {
+                rest = task;
+            }
+            _ => break,
+        }
+    }
+
+    let mut tags: Vec<String> = Vec::new();
+    let mut words = Vec::new();
+    for word in rest.split_whitespace() {
+        let label = word
+            .strip_prefix(['+', '@'])
+            .filter(|label| !label.is_empty());
+        if let Some(label) = label {
+            if !tags.iter().any(|t| t == label) {
+                tags.push(label.to_string());
+            }
+        } else if let Some(letter) = word.strip_prefix("pri:") {
+            // Done tasks keep their priority as `pri:A`
+            priority = priority.or_else(|| letter.chars().next());
+        } else {
+            words.push(word);
+        }
+    }
+
+    let (content, hashtags) = extract_tags(&words.join(" "));
+    for tag in hashtags {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    (!content.is_empty()).then_some((status, priority, content, tags))
+}
+
+/// Number of stars opening an org heading, if `line` is one
+fn org_heading_level(line: &str) -> Option<usize> {
+    let level = line.bytes().take_while(|&b| b == b'*').count();
+    (level > 0 && line[level..].starts_with(' ')).then_some(level)
+}
+
+/// Status, priority, content, and tags of an org heading's text, if it
+/// starts with a TODO keyword
+fn org_task(heading: &str) -> Option<(TodoStatus, Option<char>, String, Vec<String>)> {
+    let heading = heading.trim();
+    let (keyword, mut rest) = heading.split_once(' ').unwrap_or((heading, ""));
+    let status = match keyword {
+        "TODO" | "NEXT" => TodoStatus::Pending,
+        "DOING" | "STARTED" => TodoStatus::InProgress,
+        "DONE" => TodoStatus::Done,
+        _ => return None,
+    };
+
+    let mut priority = None;
+    if let [b'[', b'#', letter @ b'A'..=b'Z', b']', ..] = rest.as_bytes() {
+        priority = Some(*letter as char);
+        rest = rest[4..].trim_start();
+    }
+
+    // Trailing `:tag1:tag2:`
+    let mut tags = Vec::new();
+    let (text, last) = rest.rsplit_once(' ').unwrap_or(("", rest));
+    if last.len() > 1 && last.starts_with(':') && last.ends_with(':') {
+        tags = last
+            .split(':')
+            .filter(|tag| !tag.is_empty())
+            .map(str::to_string)
+            .collect();
+        rest = text;
+    }
+
+    let content = rest.trim().to_string();
+    (!content.is_empty()).then_some((status, priority, content, tags))
+}
+
+/// Date of a `DEADLINE: <2024-01-20 Sat>` in a planning line
+fn org_deadline(line: &str) -> Option<NaiveDate> {
+    let (_, after) = line.split_once("DEADLINE:")?;
+    let date = after.trim_start().strip_prefix('<')?.get(..10)?;
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+/// Width of a line's leading whitespace, counting tabs as four columns
+fn indent_width(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -265,6 +695,48 @@ mod tests {
         assert_eq!(todo.status, TodoStatus::InProgress);
     }
 
+    #[test]
+    fn test_parse_todo_line_alternate_bullets() {
+        let config = Config::default();
+        let collector = TodoCollector::new(&config);
+
+        let cases = [
+            ("* [x] Star done", "Star done", TodoStatus::Done),
+            (
+                "+ [~] Plus in progress",
+                "Plus in progress",
+                TodoStatus::InProgress,
+            ),
+            ("* [ ] Star pending", "Star pending", TodoStatus::Pending),
+            ("    * [x] Indented star", "Indented star", TodoStatus::Done),
+            (
+                "\t+ [ ] Indented plus",
+                "Indented plus",
+                TodoStatus::Pending,
+            ),
+        ];
+
+        for (line, content, status) in cases {
+            let todo = collector
+                .parse_todo_line(line, Path::new("todo.md"), 1)
+                .unwrap()
+                .unwrap_or_else(|| panic!("not parsed: {:?}", line));
+
+            assert_eq!(todo.content, content);
+            assert_eq!(todo.status, status);
+        }
+
+        // A bullet marker must be followed by the checkbox
+        assert!(collector
+            .parse_todo_line("*[x] Missing space", Path::new("todo.md"), 1)
+            .unwrap()
+            .is_none());
+        assert!(collector
+            .parse_todo_line("# [x] Heading", Path::new("todo.md"), 1)
+            .unwrap()
+            .is_none());
+    }
+
     #[test]
     fn test_parse_todo_line_not_todo() {
         let config = Config::default();
@@ -305,17 +777,189 @@ Some other text
         assert_eq!(todos[3].content, "Task 4");
     }
 
+    #[test]
+    fn test_parse_todo_line_extracts_tags() {
+        let config = Config::default();
+        let collector = TodoCollector::new(&config);
+
+        let todo = collector
+            .parse_todo_line(
+                "- [ ] Ship release #work #urgent for #123 #work",
+                Path::new("todo.md"),
+                1,
+            )
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(todo.content, "Ship release for #123");
+        assert_eq!(todo.tags, vec!["work", "urgent"]);
+    }
+
+    #[test]
+    fn test_parse_todo_txt() {
+        let config = Config::default();
+        let collector = TodoCollector::new(&config);
+
+        let content = "(A) 2024-01-10 Call the bank +finances @phone due:2024-01-20\n\
+                       \n\
+                       x 2024-01-12 2024-01-10 Renew passport @errands pri:B\n\
+                       Plan offsite +team #q1\n";
+
+        let todos = collector.parse_todo_txt(content, Path::new("todo.txt"));
+
+        assert_eq!(todos.len(), 3);
+
+        assert_eq!(todos[0].content, "Call the bank due:2024-01-20");
+        assert_eq!(todos[0].status, TodoStatus::Pending);
+        assert_eq!(todos[0].priority, Some('A'));
+        assert_eq!(todos[0].tags, vec!["finances", "phone"]);
+        assert_eq!(todos[0].line, 1);
+
+        assert_eq!(todos[1].content, "Renew passport");
+        assert_eq!(todos[1].status, TodoStatus::Done);
+        assert_eq!(todos[1].priority, Some('B'));
+        assert_eq!(todos[1].tags, vec!["errands"]);
+        assert_eq!(todos[1].line, 3);
+
+        assert_eq!(todos[2].content, "Plan offsite");
+        assert_eq!(todos[2].priority, None);
+        assert_eq!(todos[2].tags, vec!["team", "q1"]);
+    }
+
+    #[test]
+    fn test_parse_org() {
+        let config = Config::default();
+        let collector = TodoCollector::new(&config);
+
+        let content = "#+TITLE: Tasks\n\
+                       * Projects\n\
+                       ** TODO [#A] Write report :work:q1:\n\
+                       \x20  DEADLINE: <2024-01-20 Sat> SCHEDULED: <2024-01-18 Thu>\n\
+                       \x20  Some notes\n\
+                       *** DOING Collect numbers\n\
+                       ** DONE Send invoice\n\
+                       ** Ideas\n\
+                       DEADLINE: <2024-02-01 Thu>\n\
+                       *bold* text\n";
+
+        let todos = collector.parse_org(content, Path::new("tasks.org"));
+
+        assert_eq!(todos.len(), 3);
+
+        assert_eq!(todos[0].content, "Write report");
+        assert_eq!(todos[0].status, TodoStatus::Pending);
+        assert_eq!(todos[0].priority, Some('A'));
+        assert_eq!(todos[0].tags, vec!["work", "q1"]);
+        assert_eq!(todos[0].deadline, NaiveDate::from_ymd_opt(2024, 1, 20));
+        assert_eq!(todos[0].depth, 1);
+        assert_eq!(todos[0].line, 3);
+
+        assert_eq!(todos[1].content, "Collect numbers");
+        assert_eq!(todos[1].status, TodoStatus::InProgress);
+        assert_eq!(todos[1].depth, 2);
+        assert_eq!(todos[1].deadline, None);
+
+        assert_eq!(todos[2].content, "Send invoice");
+        assert_eq!(todos[2].status, TodoStatus::Done);
+        assert_eq!(todos[2].deadline, None);
+    }
+
+    #[test]
+    fn test_collect_todo_txt_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("todo.txt");
+        fs::write(&path, "(B) Write report\nx Send invoice\n").unwrap();
+
+        let config = Config {
+            todo_files: vec![TodoFile {
+                path,
+                format: TodoFormat::TodoTxt,
+            }],
+            ..Config::default()
+        };
+        let collector = TodoCollector::new(&config);
+        let mut state = State::default();
+
+        let todos = collector.collect_files(&mut state).unwrap();
+
+        let contents: Vec<&str> = todos.iter().map(|t| t.content.as_str()).collect();
+        assert_eq!(contents, vec!["Write report", "Send invoice"]);
+        assert_eq!(todos[1].status, TodoStatus::Done);
+    }
+
+    #[test]
+    fn test_tagged_todo_matches_legacy_state() {
+        let config = Config::default();
+        let collector = TodoCollector::new(&config);
+        let file = Path::new("todo.md");
+
+        // State written before tags were stripped from the content
+        let mut state = State::default();
+        let mut legacy = collector.parse_todos("- [ ] Ship release\n", file).unwrap();
+        legacy[0].content = "Ship release #work".to_string();
+        collector.update_state_for_file(&mut state, file, &legacy);
+
+        let mut todos = collector
+            .parse_todos("- [ ] Ship release #work\n", file)
+            .unwrap();
+        collector.detect_changes(&mut todos, &state, file);
+
+        assert_eq!(todos[0].change, ChangeKind::Unchanged);
+    }
+
+    #[test]
+    fn test_parse_todos_nesting_depth() {
+        let config = Config::default();
+        let collector = TodoCollector::new(&config);
+
+        let content = "- [ ] Parent\n    - [ ] Child\n        - [x] Grandchild\n    - [ ] Sibling\n\t- [~] Tab child\n- [ ] Next\n";
+
+        let todos = collector
+            .parse_todos(content, Path::new("todo.md"))
+            .unwrap();
+
+        let depths: Vec<usize> = todos.iter().map(|t| t.depth).collect();
+        assert_eq!(depths, vec![0, 1, 2, 1, 1, 0]);
+        assert_eq!(todos[2].content, "Grandchild");
+    }
+
     #[test]
     fn test_collect_from_empty_config() {
         let config = Config::default();
         let collector = TodoCollector::new(&config);
         let mut state = State::default();
 
-        let result = collector.collect(&mut state);
+        let result = collector.collect_files(&mut state);
         assert!(result.is_ok());
         assert_eq!(result.unwrap().len(), 0);
     }
 
+    #[test]
+    fn test_collect_into_records_items_and_warnings() {
+        let temp_dir = TempDir::new().unwrap();
+        let todo_file = temp_dir.path().join("todo.md");
+        fs::write(&todo_file, "- [ ] New task\n").unwrap();
+
+        let config = Config {
+            todo_files: vec![todo_file.into(), temp_dir.path().join("missing.md").into()],
+            ..Config::default()
+        };
+        let source: Box<dyn Source> = Box::new(TodoCollector::new(&config));
+        let mut collected = Collected::default();
+        let window = Window {
+            since: Utc::now(),
+            until: None,
+        };
+        source
+            .collect_into(&mut State::default(), window, &mut collected)
+            .unwrap();
+
+        assert_eq!(source.name(), "todos");
+        assert_eq!(collected.todos.len(), 1);
+        assert_eq!(collected.warnings.len(), 1);
+        assert_eq!(collected.warnings[0].source, "todos");
+    }
+
     #[test]
     fn test_collect_with_file() {
         let temp_dir = TempDir::new().unwrap();
@@ -331,12 +975,12 @@ Some other text
         .unwrap();
 
         let mut config = Config::default();
-        config.todo_files.push(todo_file);
+        config.todo_files.push(todo_file.into());
 
         let collector = TodoCollector::new(&config);
         let mut state = State::default();
 
-        let todos = collector.collect(&mut state).unwrap();
+        let todos = collector.collect_files(&mut state).unwrap();
 
         // All should be marked as new on first run
         assert_eq!(todos.len(), 2);
@@ -351,13 +995,13 @@ Some other text
         fs::write(&todo_file, "- [ ] Task\n").unwrap();
 
         let mut config = Config::default();
-        config.todo_files.push(todo_file.clone());
+        config.todo_files.push(todo_file.clone().into());
 
         let collector = TodoCollector::new(&config);
         let mut state = State::default();
 
         // First collection (state is updated automatically)
-        let todos = collector.collect(&mut state).unwrap();
+        let todos = collector.collect_files(&mut state).unwrap();
         assert_eq!(todos.len(), 1);
         assert_eq!(todos[0].change, ChangeKind::New);
 
@@ -365,10 +1009,272 @@ Some other text
         fs::write(&todo_file, "- [x] Task\n").unwrap();
 
         // Second collection
-        let todos2 = collector.collect(&mut state).unwrap();
+        let todos2 = collector.collect_files(&mut state).unwrap();
         assert_eq!(todos2.len(), 1);
         assert_eq!(todos2[0].change, ChangeKind::Modified);
         assert_eq!(todos2[0].status, TodoStatus::Done);
         assert_eq!(todos2[0].previous_status, Some(TodoStatus::Pending));
     }
+
+    #[test]
+    fn test_prefix_content_not_detected_as_modified() {
+        let temp_dir = TempDir::new().unwrap();
+        let todo_file = temp_dir.path().join("todo.md");
+
+        fs::write(&todo_file, "- [ ] Task 1\n").unwrap();
+
+        let mut config = Config::default();
+        config.todo_files.push(todo_file.clone().into());
+
+        let collector = TodoCollector::new(&config);
+        let mut state = State::default();
+        collector.collect_files(&mut state).unwrap();
+
+        // "Task 1" is a prefix of "Task 10"; the old substring matching
+        // reported this as a status change of the existing item
+        fs::write(&todo_file, "- [x] Task 10\n").unwrap();
+
+        let todos = collector.collect_files(&mut state).unwrap();
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].change, ChangeKind::New);
+        assert_eq!(todos[0].previous_status, None);
+    }
+
+    #[test]
+    fn test_edited_todo_detected() {
+        let temp_dir = TempDir::new().unwrap();
+        let todo_file = temp_dir.path().join("todo.md");
+
+        fs::write(&todo_file, "- [x] Shipp release\n- [ ] Review PR\n").unwrap();
+
+        let mut config = Config::default();
+        config.todo_files.push(todo_file.clone().into());
+
+        let collector = TodoCollector::new(&config);
+        let mut state = State::default();
+        collector.collect_files(&mut state).unwrap();
+
+        // Typo fixed in a done item; the other line gets a new task
+        fs::write(&todo_file, "- [x] Ship release\n- [x] Deploy\n").unwrap();
+
+        let todos = collector.collect_files(&mut state).unwrap();
+        assert_eq!(todos.len(), 2);
+
+        assert_eq!(todos[0].content, "Ship release");
+        assert_eq!(todos[0].change, ChangeKind::Modified);
+        assert_eq!(todos[0].previous_content.as_deref(), Some("Shipp release"));
+        assert!(todos[0].was_edited());
+
+        assert_eq!(todos[1].content, "Deploy");
+        assert_eq!(todos[1].change, ChangeKind::New);
+    }
+
+    #[test]
+    fn test_reordered_todos_are_unchanged() {
+        let temp_dir = TempDir::new().unwrap();
+        let todo_file = temp_dir.path().join("todo.md");
+
+        fs::write(&todo_file, "- [ ] First\n- [ ] Second\n- [x] Third\n").unwrap();
+
+        let mut config = Config::default();
+        config.todo_files.push(todo_file.clone().into());
+
+        let collector = TodoCollector::new(&config);
+        let mut state = State::default();
+        collector.collect_files(&mut state).unwrap();
+
+        fs::write(
+            &todo_file,
+            "# Reordered\n- [x] Third\n- [ ]  First \n- [ ] Second\n",
+        )
+        .unwrap();
+
+        let todos = collector.collect_files(&mut state).unwrap();
+        assert!(todos.is_empty());
+    }
+
+    #[test]
+    fn test_moved_todo_status_change_detected() {
+        let temp_dir = TempDir::new().unwrap();
+        let todo_file = temp_dir.path().join("todo.md");
+
+        fs::write(&todo_file, "- [ ] First\n- [ ] Second\n").unwrap();
+
+        let mut config = Config::default();
+        config.todo_files.push(todo_file.clone().into());
+
+        let collector = TodoCollector::new(&config);
+        let mut state = State::default();
+        collector.collect_files(&mut state).unwrap();
+
+        fs::write(&todo_file, "- [x] Second\n- [ ] First\n").unwrap();
+
+        let todos = collector.collect_files(&mut state).unwrap();
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].content, "Second");
+        assert_eq!(todos[0].change, ChangeKind::Modified);
+        assert_eq!(todos[0].previous_status, Some(TodoStatus::Pending));
+    }
+
+    #[test]
+    fn test_duplicate_todos_disambiguated_by_line() {
+        let temp_dir = TempDir::new().unwrap();
+        let todo_file = temp_dir.path().join("todo.md");
+
+        fs::write(&todo_file, "- [ ] Call\n- [ ] Other\n- [ ] Call\n").unwrap();
+
+        let mut config = Config::default();
+        config.todo_files.push(todo_file.clone().into());
+
+        let collector = TodoCollector::new(&config);
+        let mut state = State::default();
+        collector.collect_files(&mut state).unwrap();
+
+        fs::write(&todo_file, "- [ ] Call\n- [ ] Other\n- [x] Call\n").unwrap();
+
+        let todos = collector.collect_files(&mut state).unwrap();
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].line, 3);
+        assert_eq!(todos[0].change, ChangeKind::Modified);
+        assert_eq!(todos[0].previous_status, Some(TodoStatus::Pending));
+    }
+
+    #[test]
+    fn test_unchanged_todos_filtered() {
+        let temp_dir = TempDir::new().unwrap();
+        let todo_file = temp_dir.path().join("todo.md");
+
+        fs::write(&todo_file, "- [ ] Task\n- [x] Other\n").unwrap();
+
+        let mut config = Config::default();
+        config.todo_files.push(todo_file.clone().into());
+
+        let collector = TodoCollector::new(&config);
+        let mut state = State::default();
+        collector.collect_files(&mut state).unwrap();
+
+        let todos = collector.collect_files(&mut state).unwrap();
+        assert!(todos.is_empty());
+
+        // Totals still count the unchanged items
+        assert_eq!(collector.totals(), TodoTotals { open: 1, done: 1 });
+    }
+
+    #[test]
+    fn test_totals_use_completed_statuses() {
+        let temp_dir = TempDir::new().unwrap();
+        let todo_file = temp_dir.path().join("todo.md");
+
+        fs::write(&todo_file, "- [ ] Task\n- [~] Started\n- [x] Other\n").unwrap();
+
+        let config = Config {
+            todo_files: vec![todo_file.into()],
+            completed_statuses: vec![TodoStatus::InProgress, TodoStatus::Done],
+            ..Config::default()
+        };
+
+        let collector = TodoCollector::new(&config);
+        collector.collect_files(&mut State::default()).unwrap();
+        assert_eq!(collector.totals(), TodoTotals { open: 1, done: 2 });
+    }
+
+    #[test]
+    fn test_show_unchanged_todos() {
+        let temp_dir = TempDir::new().unwrap();
+        let todo_file = temp_dir.path().join("todo.md");
+
+        fs::write(&todo_file, "- [ ] Task\n- [x] Other\n").unwrap();
+
+        let config = Config {
+            todo_files: vec![todo_file.clone().into()],
+            show_unchanged_todos: true,
+            ..Config::default()
+        };
+
+        let collector = TodoCollector::new(&config);
+        let mut state = State::default();
+        collector.collect_files(&mut state).unwrap();
+
+        fs::write(&todo_file, "- [x] Task\n- [x] Other\n").unwrap();
+
+        let todos = collector.collect_files(&mut state).unwrap();
+        let changes: Vec<ChangeKind> = todos.iter().map(|t| t.change).collect();
+        assert_eq!(changes, vec![ChangeKind::Modified, ChangeKind::Unchanged]);
+    }
+
+    #[test]
+    fn test_dedupe_todos_across_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let daily = temp_dir.path().join("daily.md");
+        let project = temp_dir.path().join("project.md");
+
+        fs::write(&daily, "- [ ] Ship  release #work\n- [x] Other\n").unwrap();
+        fs::write(&project, "- [ ] Intro\n- [ ] Ship release\n- [ ] Other\n").unwrap();
+
+        let config = Config {
+            todo_files: vec![daily.clone().into(), project.clone().into()],
+            dedupe_todos: true,
+            ..Config::default()
+        };
+
+        let collector = TodoCollector::new(&config);
+        let mut state = State::default();
+        let todos = collector.collect_files(&mut state).unwrap();
+
+        let contents: Vec<&str> = todos.iter().map(|t| t.content.as_str()).collect();
+        assert_eq!(contents, vec!["Ship release", "Other", "Intro", "Other"]);
+        assert_eq!(todos[0].file, daily);
+        assert_eq!(
+            todos[0].also_in,
+            vec![TodoLocation {
+                file: project.clone(),
+                line: 2,
+            }]
+        );
+        // Different status: not the same item
+        assert!(todos[1].also_in.is_empty());
+    }
+
+    #[test]
+    fn test_dedupe_todos_off_by_default() {
+        let temp_dir = TempDir::new().unwrap();
+        let a = temp_dir.path().join("a.md");
+        let b = temp_dir.path().join("b.md");
+        fs::write(&a, "- [ ] Same\n").unwrap();
+        fs::write(&b, "- [ ] Same\n").unwrap();
+
+        let config = Config {
+            todo_files: vec![a.into(), b.into()],
+            ..Config::default()
+        };
+
+        let collector = TodoCollector::new(&config);
+        let todos = collector.collect_files(&mut State::default()).unwrap();
+        assert_eq!(todos.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_keyword_todos() {
+        let config = Config::default();
+        let collector = TodoCollector::new(&config);
+        let keywords = vec!["TODO".to_string(), "task:".to_string()];
+
+        let content = "# Meeting\n\
+            TODO: Send notes #work\n\
+            - todo: Book room\n\
+            Task: Review draft\n\
+            TODOS: not a keyword\n\
+            Mention of TODO: mid-line\n\
+            TODO:\n";
+        let todos = collector.parse_keyword_todos(content, Path::new("meeting.md"), &keywords);
+
+        let found: Vec<(&str, usize)> =
+            todos.iter().map(|t| (t.content.as_str(), t.line)).collect();
+        assert_eq!(
+            found,
+            vec![("Send notes", 2), ("Book room", 3), ("Review draft", 4)]
+        );
+        assert_eq!(todos[0].tags, vec!["work"]);
+        assert!(todos.iter().all(|t| t.status == TodoStatus::Pending));
+    }
 }
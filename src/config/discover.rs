@@ -0,0 +1,159 @@
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// How many directory levels below the scan root are searched
+const SCAN_DEPTH: usize = 3;
+
+/// Directory names that usually hold notes
+const NOTES_DIR_NAMES: &[&str] = &["notes", "Notes", "journal", "Journal"];
+
+/// File names that usually hold TODOs
+const TODO_FILE_NAMES: &[&str] = &[
+    "TODO.md", "todo.md", "TODO.txt", "todo.txt", "Inbox.md", "inbox.md",
+];
+
+/// Directories never worth descending into
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "vendor"];
+
+/// Sources found by `discover`, for `config init --interactive` to offer
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Discovered {
+    /// Git repositories (working tree roots)
+    pub repos: Vec<PathBuf>,
+    /// TODO/Inbox files
+    pub todo_files: Vec<PathBuf>,
+    /// Notes directories, including Obsidian vaults
+    pub notes_dirs: Vec<PathBuf>,
+}
+
+/// Look for Git repositories, TODO files, and notes directories
+///
+/// Searches `root` a few levels deep (skipping hidden and build directories,
+/// and not descending into repositories), then the common locations in
+/// `home` (`~/notes`, `~/Documents/notes`, `~/TODO.md`, ...).
+pub fn discover(root: &Path, home: Option<&Path>) -> Discovered {
+    let mut found = Discovered::default();
+
+    let walker = WalkDir::new(root)
+        .max_depth(SCAN_DEPTH)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| entry.depth() == 0 || !is_skipped(entry.path(), entry.depth()));
+    for entry in walker.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        if entry.file_type().is_dir() {
+            if path.join(".git").exists() {
+                found.repos.push(path.to_path_buf());
+            }
+            if path.join(".obsidian").is_dir() || has_name(path, NOTES_DIR_NAMES) {
+                found.notes_dirs.push(path.to_path_buf());
+            }
+        } else if entry.file_type().is_file() && has_name(path, TODO_FILE_NAMES) {
+            found.todo_files.push(path.to_path_buf());
+        }
+    }
+
+    if let Some(home) = home {
+        for base in [home.to_path_buf(), home.join("Documents")] {
+            for name in NOTES_DIR_NAMES {
+                push_new(&mut found.notes_dirs, base.join(name), Path::is_dir);
+            }
+            for name in TODO_FILE_NAMES {
+                push_new(&mut found.todo_files, base.join(name), Path::is_file);
+            }
+        }
+    }
+
+    found
+}
+
+/// Whether the walk should not descend into `path`
+///
+/// Hidden and build directories are skipped, and so are subdirectories of
+/// repositories below the root (files at their top level, like `TODO.md`,
+/// are still seen).
+fn is_skipped(path: &Path, depth: usize) -> bool {
+    let hidden = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('.'));
+    let in_repo = depth > 1
+        && path.is_dir()
+        && path
+            .parent()
+            .is_some_and(|parent| parent.join(".git").exists());
+    hidden || in_repo || has_name(path, SKIPPED_DIRS)
+}
+
+fn has_name(path: &Path, names: &[&str]) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| names.contains(&name))
+}
+
+/// Add `path` if it exists as the expected kind and isn't listed yet
+fn push_new(paths: &mut Vec<PathBuf>, path: PathBuf, exists: fn(&Path) -> bool) {
+    if exists(&path) && !paths.contains(&path) {
+        paths.push(path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_discover_finds_repos_todos_and_notes() {
+        let root = TempDir::new().unwrap();
+        let path = root.path();
+        fs::create_dir_all(path.join("code/app/.git")).unwrap();
+        fs::create_dir_all(path.join("code/lib/.git")).unwrap();
+        fs::create_dir_all(path.join("vault/.obsidian")).unwrap();
+        fs::create_dir_all(path.join("notes")).unwrap();
+        fs::write(path.join("TODO.md"), "- [ ] Task\n").unwrap();
+
+        let found = discover(path, None);
+
+        assert_eq!(
+            found.repos,
+            vec![path.join("code/app"), path.join("code/lib")]
+        );
+        assert_eq!(found.todo_files, vec![path.join("TODO.md")]);
+        assert_eq!(
+            found.notes_dirs,
+            vec![path.join("notes"), path.join("vault")]
+        );
+    }
+
+    #[test]
+    fn test_discover_skips_hidden_build_and_repo_contents() {
+        let root = TempDir::new().unwrap();
+        let path = root.path();
+        fs::create_dir_all(path.join(".cache/old/.git")).unwrap();
+        fs::create_dir_all(path.join("node_modules/pkg/.git")).unwrap();
+        fs::create_dir_all(path.join("app/.git")).unwrap();
+        fs::write(path.join("app/.git/TODO.md"), "").unwrap();
+        fs::create_dir_all(path.join("app/vendored/.git")).unwrap();
+        fs::write(path.join("app/TODO.md"), "").unwrap();
+
+        let found = discover(path, None);
+
+        assert_eq!(found.repos, vec![path.join("app")]);
+        assert_eq!(found.todo_files, vec![path.join("app/TODO.md")]);
+    }
+
+    #[test]
+    fn test_discover_checks_home_locations() {
+        let root = TempDir::new().unwrap();
+        let home = TempDir::new().unwrap();
+        fs::create_dir_all(home.path().join("Documents/notes")).unwrap();
+        fs::write(home.path().join("todo.txt"), "").unwrap();
+
+        let found = discover(root.path(), Some(home.path()));
+
+        assert_eq!(found.notes_dirs, vec![home.path().join("Documents/notes")]);
+        assert_eq!(found.todo_files, vec![home.path().join("todo.txt")]);
+    }
+}
@@ -1,19 +1,93 @@
 //! Configuration module
 //!
-//! Handles loading and saving of chronicle.toml configuration files.
-//! Defines Config, Limits, and Display types.
+//! Handles loading and saving of chronicle.toml configuration files,
+//! including merging named profiles over the base configuration.
+//! Defines Config, RepoConfig, TodoFile, Limits, and Display types, and
+//! validates them with `Config::validate`. `discover` finds candidate
+//! sources for `config init --interactive`.
 
+mod discover;
 mod types;
+mod validate;
 
+pub use discover::{discover, Discovered};
 #[allow(unused_imports)]
-pub use types::{Config, Display, Limits};
+pub use types::{
+    Config, Display, ExcerptBoundary, ExcerptMode, Limits, RepoConfig, TodoFile, TodoFormat,
+    TodoGrouping,
+};
+pub use validate::{Problem, Severity};
 
+use crate::display::log;
 use crate::error::{ChronicleError, Result};
+use chrono::{DateTime, Duration, Utc};
+use directories::ProjectDirs;
+use serde::Deserialize;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// Config file name, in the current directory or a user config directory
+const CONFIG_FILE_NAME: &str = "chronicle.toml";
+
+/// Resolve which config file to use
+///
+/// An explicit `--config` path always wins. Otherwise `./chronicle.toml` is
+/// used if it exists, then the nearest `chronicle.toml` in a parent
+/// directory (like Git and Cargo), then
+/// `$XDG_CONFIG_HOME/chronicle/chronicle.toml`, then the platform config
+/// directory (e.g. `~/.config/chronicle/` on Linux). When none exist,
+/// `./chronicle.toml` is returned so errors point there.
+pub fn resolve_path(explicit: Option<PathBuf>) -> PathBuf {
+    let path = explicit.unwrap_or_else(|| {
+        let local = PathBuf::from(CONFIG_FILE_NAME);
+        if local.exists() {
+            return local;
+        }
+
+        ancestor_config_path()
+            .or_else(|| user_config_paths().into_iter().find(|path| path.exists()))
+            .unwrap_or(local)
+    });
+
+    log::detail(&format!("Using config: {}", path.display()));
+    path
+}
+
+/// The nearest `chronicle.toml` above the current directory, up to the
+/// filesystem root
+fn ancestor_config_path() -> Option<PathBuf> {
+    let cwd = std::env::current_dir().ok()?;
+    cwd.ancestors()
+        .skip(1)
+        .map(|dir| dir.join(CONFIG_FILE_NAME))
+        .find(|path| path.is_file())
+}
+
+/// Candidate config files in the user's config directories, in lookup order
+fn user_config_paths() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+
+    if let Some(dir) = std::env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+        paths.push(PathBuf::from(dir).join("chronicle").join(CONFIG_FILE_NAME));
+    }
+
+    if let Some(dirs) = ProjectDirs::from("", "", "chronicle") {
+        paths.push(dirs.config_dir().join(CONFIG_FILE_NAME));
+    }
+
+    paths
+}
 
 /// Load configuration from a TOML file
 pub fn load(path: &Path) -> Result<Config> {
+    load_profile(path, None)
+}
+
+/// Load configuration from a TOML file with an optional profile applied
+///
+/// The profile's values are merged over the top-level fields; anything the
+/// profile doesn't specify is inherited from the base configuration.
+pub fn load_profile(path: &Path, profile: Option<&str>) -> Result<Config> {
     let content = fs::read_to_string(path).map_err(|e| {
         ChronicleError::Config(format!(
             "Cannot read config from '{}': {}. Run 'chronicle config init' to create one.",
@@ -22,10 +96,105 @@ pub fn load(path: &Path) -> Result<Config> {
         ))
     })?;
 
-    let config: Config = toml::from_str(&content)?;
+    let mut table: toml::Table = toml::from_str(&content)?;
+
+    if let Some(name) = profile {
+        let overrides = table
+            .get("profiles")
+            .and_then(|profiles| profiles.get(name))
+            .and_then(|profile| profile.as_table())
+            .cloned()
+            .ok_or_else(|| {
+                let available: Vec<&str> = table
+                    .get("profiles")
+                    .and_then(|profiles| profiles.as_table())
+                    .map(|profiles| profiles.keys().map(String::as_str).collect())
+                    .unwrap_or_default();
+                ChronicleError::Config(format!(
+                    "Unknown profile '{}' (available: {})",
+                    name,
+                    if available.is_empty() {
+                        "none".to_string()
+                    } else {
+                        available.join(", ")
+                    }
+                ))
+            })?;
+
+        merge_tables(&mut table, overrides);
+    }
+
+    let mut config = Config::deserialize(toml::Value::Table(table))?;
+
+    // Relative paths in a config found in a parent directory are relative
+    // to that directory, not to wherever chronicle runs
+    if let Some(dir) = ancestor_dir(path) {
+        config.rebase_paths(&dir);
+    }
+
     Ok(config)
 }
 
+/// The directory of config file `path` if it is above the current directory
+fn ancestor_dir(path: &Path) -> Option<PathBuf> {
+    let dir = path.parent()?.canonicalize().ok()?;
+    let cwd = std::env::current_dir().ok()?.canonicalize().ok()?;
+    (cwd != dir && cwd.starts_with(&dir)).then_some(dir)
+}
+
+/// Parse a `--since`, `git_since`, or `notes_since` value: a duration back
+/// from `now` (`90m`, `48h`, `3d`, `1w`) or an RFC 3339 timestamp
+pub fn parse_since(value: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let value = value.trim();
+
+    let relative = value.char_indices().last().and_then(|(index, unit)| {
+        let minutes_per_unit = match unit {
+            'm' => 1,
+            'h' => 60,
+            'd' => 60 * 24,
+            'w' => 60 * 24 * 7,
+            _ => return None,
+        };
+        let count: u32 = value[..index].parse().ok()?;
+        Some(Duration::try_minutes(i64::from(count) * minutes_per_unit))
+    });
+    if let Some(duration) = relative {
+        return duration
+            .and_then(|duration| now.checked_sub_signed(duration))
+            .ok_or_else(|| {
+                ChronicleError::Config(format!("Invalid since '{}': too far back", value))
+            });
+    }
+
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| {
+            ChronicleError::Config(format!(
+                "Invalid since '{}' (expected a duration like 48h, 3d, or 1w, or an RFC 3339 timestamp): {}",
+                value, e
+            ))
+        })
+}
+
+/// Recursively merge `overrides` into `base` (nested tables merge, other
+/// values replace). Profiles can't define further profiles.
+fn merge_tables(base: &mut toml::Table, overrides: toml::Table) {
+    for (key, value) in overrides {
+        if key == "profiles" {
+            continue;
+        }
+
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(override_table)) => {
+                merge_tables(base_table, override_table);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
 /// Save configuration to a TOML file
 pub fn save(config: &Config, path: &Path) -> Result<()> {
     let toml = toml::to_string_pretty(config)
@@ -43,6 +212,7 @@ pub fn save(config: &Config, path: &Path) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
     use tempfile::TempDir;
 
     #[test]
@@ -55,7 +225,7 @@ mod tests {
 
         let loaded = load(&config_path).unwrap();
         assert_eq!(loaded.limits.max_commits, 50);
-        assert_eq!(loaded.display.show_authors, true);
+        assert!(loaded.display.show_authors);
     }
 
     #[test]
@@ -94,4 +264,75 @@ mod tests {
         assert_eq!(loaded.repos.len(), 2); // "." + "/test/repo"
         assert_eq!(loaded.todo_files.len(), 1);
     }
+
+    #[test]
+    fn test_load_profile_overrides_base() {
+        let temp = TempDir::new().unwrap();
+        let config_path = temp.path().join("chronicle.toml");
+
+        fs::write(
+            &config_path,
+            r#"
+output_dir = "base-out"
+todo_files = ["base.md"]
+
+[limits]
+max_commits = 10
+max_note_files = 5
+
+[profiles.work]
+output_dir = "work-out"
+
+[profiles.work.limits]
+max_commits = 200
+"#,
+        )
+        .unwrap();
+
+        let base = load(&config_path).unwrap();
+        assert_eq!(base.output_dir, Path::new("base-out"));
+        assert_eq!(base.limits.max_commits, 10);
+
+        let work = load_profile(&config_path, Some("work")).unwrap();
+        assert_eq!(work.output_dir, Path::new("work-out"));
+        assert_eq!(work.limits.max_commits, 200);
+        // Unspecified fields inherit the base
+        assert_eq!(work.limits.max_note_files, 5);
+        assert_eq!(work.todo_files.len(), 1);
+    }
+
+    #[test]
+    fn test_load_unknown_profile() {
+        let temp = TempDir::new().unwrap();
+        let config_path = temp.path().join("chronicle.toml");
+
+        fs::write(&config_path, "[profiles.work]\noutput_dir = \"w\"\n").unwrap();
+
+        let result = load_profile(&config_path, Some("personal"));
+        let message = result.unwrap_err().to_string();
+        assert!(message.contains("Unknown profile 'personal'"));
+        assert!(message.contains("available: work"));
+    }
+
+    #[test]
+    fn test_parse_since() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+
+        assert_eq!(parse_since("48h", now).unwrap(), now - Duration::hours(48));
+        assert_eq!(parse_since("1w", now).unwrap(), now - Duration::weeks(1));
+        assert_eq!(
+            parse_since("2024-01-14T09:00:00Z", now).unwrap(),
+            Utc.with_ymd_and_hms(2024, 1, 14, 9, 0, 0).unwrap()
+        );
+        assert!(parse_since("3 days", now).is_err());
+    }
+
+    #[test]
+    fn test_parse_since_out_of_range() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+
+        let error = parse_since("99999999d", now).unwrap_err();
+        assert!(error.to_string().contains("too far back"));
+        assert!(parse_since("4294967295w", now).is_err());
+    }
 }
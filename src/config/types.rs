@@ -1,38 +1,243 @@
+use chrono::{Local, NaiveDate, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+use crate::models::TodoStatus;
 
 /// Chronicle configuration
+///
+/// Missing fields fall back to their defaults, so a config file only needs
+/// to list what it changes.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
     /// Directory where chronicle files are written
     pub output_dir: PathBuf,
 
-    /// Path to state file for tracking last runs
+    /// Path to state file for tracking last runs (relative paths are
+    /// resolved against the config file's directory)
     pub state_file: PathBuf,
 
+    /// Write the state file gzip-compressed (either form is read back)
+    pub compress_state: bool,
+
+    /// Write a chronicle even when nothing happened, so every day has one
+    pub write_empty: bool,
+
+    /// Body of a chronicle without activity (see `write_empty`)
+    pub empty_message: String,
+
+    /// Record the machine's hostname in the chronicle header and state,
+    /// for chronicles synced between several machines
+    pub include_host: bool,
+
     /// Git repositories to track
-    pub repos: Vec<PathBuf>,
+    pub repos: Vec<RepoConfig>,
+
+    /// Report uncommitted changes for repositories without new commits
+    pub include_wip: bool,
+
+    /// Only chronicle commits whose message matches one of these regexes;
+    /// all commits when empty
+    pub commit_include_patterns: Vec<String>,
+
+    /// Skip commits whose message matches one of these regexes (e.g.
+    /// `^chore\(deps\):` or `\[skip ci\]`)
+    pub commit_exclude_patterns: Vec<String>,
+
+    /// Only chronicle your own commits: those whose author matches the
+    /// `user.name` or `user.email` in each repository's Git config
+    pub author_me: bool,
+
+    /// How far back to look for commits (e.g. `24h`) when no window is
+    /// given on the command line; the default window when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_since: Option<String>,
 
     /// TODO/Inbox files to parse
-    pub todo_files: Vec<PathBuf>,
+    pub todo_files: Vec<TodoFile>,
+
+    /// Show a TODO found with the same content and status in several files
+    /// once, listing the other places it appears
+    pub dedupe_todos: bool,
+
+    /// List unchanged TODOs too, making the TODOs section a snapshot of
+    /// every task rather than a changelog
+    pub show_unchanged_todos: bool,
+
+    /// Statuses a TODO is completed in: moving into one of them counts
+    /// toward "Completed TODOs" and is marked DONE
+    #[serde(with = "todo_status_names")]
+    pub completed_statuses: Vec<TodoStatus>,
 
     /// Directories containing note files
     pub notes_dirs: Vec<PathBuf>,
 
+    /// ICS calendar files whose events are listed when they start in the
+    /// chronicle's window
+    pub calendar_files: Vec<PathBuf>,
+
+    /// File extensions treated as notes (case-insensitive, without the dot)
+    pub note_extensions: Vec<String>,
+
+    /// Count dotfiles (`.template.md`) as notes; tool metadata is skipped
+    /// by default
+    pub include_hidden_notes: bool,
+
+    /// How deep to look inside each notes directory (1: only files directly
+    /// in it)
+    pub notes_max_depth: usize,
+
+    /// Only notes whose path relative to their notes directory matches one
+    /// of these globs (e.g. `journal/**`); all notes when empty
+    pub notes_include: Vec<String>,
+
+    /// Skip notes whose path relative to their notes directory matches one
+    /// of these globs (e.g. `drafts/**`)
+    pub notes_exclude: Vec<String>,
+
+    /// How far back to look for updated notes (e.g. `3d`) when no window
+    /// is given on the command line; the default window when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes_since: Option<String>,
+
+    /// Keywords (e.g. `TODO`, `TASK`) whose `KEYWORD: ...` lines in updated
+    /// notes are reported as TODOs, case-insensitively; off when empty
+    pub note_todo_keywords: Vec<String>,
+
+    /// Turn `[[Note]]` wikilinks in note excerpts into Markdown links
+    pub resolve_wikilinks: bool,
+
+    /// How note excerpts are built
+    pub excerpt_mode: ExcerptMode,
+
+    /// Where a long excerpt may be cut, most preferred first; without a
+    /// matching boundary it is cut at `max_chars_per_item` characters
+    pub excerpt_boundaries: Vec<ExcerptBoundary>,
+
+    /// Link issue references (`PROJ-123`, `#456`) in commits, TODOs, and
+    /// note excerpts, with `{key}` replaced by the key or issue number
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue_url_template: Option<String>,
+
+    /// Shell command that receives the chronicle as JSON on stdin and
+    /// prints a summary paragraph, shown under the Summary (e.g. a script
+    /// calling an LLM)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary_command: Option<String>,
+
+    /// Seconds to wait for `summary_command` before giving up
+    pub summary_timeout_secs: usize,
+
+    /// IANA timezone for displayed timestamps and `--today` (e.g.
+    /// `Europe/Berlin`); timestamps show in UTC when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<Tz>,
+
     /// Collection limits
     pub limits: Limits,
 
     /// Display settings
     pub display: Display,
+
+    /// Named profiles overriding top-level fields (e.g. `[profiles.work]`)
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, toml::Table>,
+}
+
+/// A Git repository to track, with optional per-repository overrides
+///
+/// Accepts either a plain path string (`"~/code/app"`) or a table
+/// (`{ path = "~/code/app", max_commits = 200, show_authors = false }`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "RepoEntry", into = "RepoEntry")]
+pub struct RepoConfig {
+    /// Path to the repository
+    pub path: PathBuf,
+
+    /// Override for `limits.max_commits`
+    pub max_commits: Option<usize>,
+
+    /// Override for `limits.max_changed_files`
+    pub max_changed_files: Option<usize>,
+
+    /// Override for `display.show_authors`
+    pub show_authors: Option<bool>,
+
+    /// Branch to compare others against, instead of the detected default
+    pub default_branch: Option<String>,
+}
+
+/// On-disk form of a repository entry: a bare path or a table
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum RepoEntry {
+    Path(PathBuf),
+    Table {
+        path: PathBuf,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_commits: Option<usize>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_changed_files: Option<usize>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        show_authors: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        default_branch: Option<String>,
+    },
+}
+
+/// A TODO file to parse, and the format its items are written in
+///
+/// Accepts either a plain path string (`"~/TODO.md"`, Markdown checkboxes)
+/// or a table (`{ path = "~/todo.txt", format = "todo.txt" }`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "TodoFileEntry", into = "TodoFileEntry")]
+pub struct TodoFile {
+    /// Path to the file
+    pub path: PathBuf,
+
+    /// How items are written
+    pub format: TodoFormat,
+}
+
+/// How the items in a TODO file are written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TodoFormat {
+    /// `- [ ] task` checkboxes
+    #[default]
+    #[serde(rename = "markdown")]
+    Markdown,
+    /// One task per line, per the todo.txt spec:
+    /// `x (A) call bank +project @context`
+    #[serde(rename = "todo.txt")]
+    TodoTxt,
+    /// Emacs org-mode headings: `** TODO [#A] task :tag:`
+    #[serde(rename = "org")]
+    Org,
+}
+
+/// On-disk form of a TODO file entry: a bare path or a table
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum TodoFileEntry {
+    Path(PathBuf),
+    Table {
+        path: PathBuf,
+        #[serde(default)]
+        format: TodoFormat,
+    },
 }
 
 /// Limits for data collection
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Limits {
     /// Maximum commits to collect per repository
     pub max_commits: usize,
 
-    /// Maximum changed files to show
+    /// Maximum changed files listed per branch (the rest are counted)
     pub max_changed_files: usize,
 
     /// Maximum note files to include
@@ -40,13 +245,113 @@ pub struct Limits {
 
     /// Maximum characters per item (TODOs, notes)
     pub max_chars_per_item: usize,
+
+    /// Maximum characters of a rendered commit subject, cut at a word
+    /// boundary (chronicles keep the full message)
+    pub max_commit_message_chars: usize,
+
+    /// Maximum characters of note excerpts in one chronicle; later notes
+    /// are left out once it would be exceeded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_notes_total_chars: Option<usize>,
+
+    /// Maximum commits, TODOs, and notes in one chronicle, across all sources
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_total_items: Option<usize>,
 }
 
 /// Display configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Display {
     /// Show author names on commits (useful for teams, disable for solo)
     pub show_authors: bool,
+
+    /// Terminal colors by element (`headers`, `inline_code`, `code_block`,
+    /// `bullet`, `table`), e.g. `headers = "magenta"`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub theme: BTreeMap<String, String>,
+
+    /// Wrap commit lines in the generated Markdown at this many characters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_line_width: Option<usize>,
+
+    /// Group the TODOs section by source file or by `#tag`
+    pub todo_grouping: TodoGrouping,
+
+    /// Compare the Summary with the previous chronicle
+    pub show_deltas: bool,
+
+    /// List each repository's commits under author headings instead of
+    /// per branch
+    pub group_by_author: bool,
+
+    /// Show the number of consecutive days with a chronicle in the Summary
+    pub show_streak: bool,
+
+    /// Open with a few highlights: the most active repository, completed
+    /// TODOs, and the largest note update
+    pub show_highlights: bool,
+
+    /// Chart each repository's commit count as a bar under the Summary
+    pub show_repo_breakdown: bool,
+
+    /// Leave binary files out of the changed files instead of counting them
+    pub hide_binary_files: bool,
+
+    /// Show `:shortcode:` emoji (e.g. gitmoji's `:sparkles:`) in commit
+    /// messages as the emoji itself
+    pub render_gitmoji: bool,
+
+    /// Warn in the Summary about feature branches first seen at least this
+    /// many days ago
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stale_branch_days: Option<usize>,
+
+    /// End with a Warnings section listing the sources collectors skipped
+    pub show_warnings: bool,
+
+    /// Mark commits that carry a signature (GPG or SSH) with 🔏; the
+    /// signature itself is not verified
+    pub show_signatures: bool,
+}
+
+/// How a note's excerpt is built
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExcerptMode {
+    /// The start of the note, cut at one of `excerpt_boundaries`
+    #[default]
+    Prefix,
+    /// The note's headings as a nested list
+    Outline,
+    /// The first paragraph after any frontmatter
+    FirstParagraph,
+}
+
+/// A place where a long note excerpt may be cut
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExcerptBoundary {
+    /// Before a blank line
+    Paragraph,
+    /// After a `.`, `!`, or `?` followed by whitespace
+    Sentence,
+    /// Before a line break
+    Line,
+    /// Before whitespace, marked with `...`
+    Word,
+}
+
+/// How the TODOs section is grouped
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TodoGrouping {
+    /// One group per TODO file
+    #[default]
+    File,
+    /// One group per `#tag`, untagged items last
+    Tag,
 }
 
 impl Default for Config {
@@ -54,11 +359,224 @@ impl Default for Config {
         Self {
             output_dir: PathBuf::from("./chronicles"),
             state_file: PathBuf::from("./.chronicle-state.json"),
-            repos: vec![PathBuf::from(".")],
+            compress_state: false,
+            write_empty: false,
+            empty_message: "Quiet day.".to_string(),
+            include_host: false,
+            repos: vec![RepoConfig::from(".")],
+            include_wip: false,
+            commit_include_patterns: Vec::new(),
+            commit_exclude_patterns: Vec::new(),
+            author_me: false,
+            git_since: None,
             todo_files: Vec::new(),
+            calendar_files: Vec::new(),
+            dedupe_todos: false,
+            show_unchanged_todos: false,
+            completed_statuses: vec![TodoStatus::Done],
             notes_dirs: Vec::new(),
+            note_extensions: vec!["md".to_string(), "markdown".to_string()],
+            include_hidden_notes: false,
+            notes_max_depth: 1,
+            notes_include: Vec::new(),
+            notes_exclude: Vec::new(),
+            notes_since: None,
+            note_todo_keywords: Vec::new(),
+            resolve_wikilinks: false,
+            excerpt_mode: ExcerptMode::Prefix,
+            excerpt_boundaries: vec![
+                ExcerptBoundary::Paragraph,
+                ExcerptBoundary::Sentence,
+                ExcerptBoundary::Word,
+            ],
+            issue_url_template: None,
+            summary_command: None,
+            summary_timeout_secs: 60,
+            timezone: None,
             limits: Limits::default(),
             display: Display::default(),
+            profiles: BTreeMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Find the configured entry for a repository path
+    pub fn repo(&self, path: &Path) -> Option<&RepoConfig> {
+        self.repos.iter().find(|repo| repo.path == path)
+    }
+
+    /// State file location, with a relative `state_file` resolved against
+    /// the directory of the config file it was loaded from
+    pub fn state_path(&self, config_path: &Path) -> PathBuf {
+        match config_path.parent() {
+            Some(dir) if self.state_file.is_relative() => dir.join(&self.state_file),
+            _ => self.state_file.clone(),
+        }
+    }
+
+    /// Resolve relative output, state, and source paths against `dir`, for
+    /// a config file found above the current directory
+    pub fn rebase_paths(&mut self, dir: &Path) {
+        let rebase = |path: &mut PathBuf| {
+            if path.is_relative() {
+                let relative: PathBuf = path
+                    .components()
+                    .filter(|component| *component != Component::CurDir)
+                    .collect();
+                *path = dir.join(relative).components().collect();
+            }
+        };
+
+        rebase(&mut self.output_dir);
+        rebase(&mut self.state_file);
+        self.repos
+            .iter_mut()
+            .for_each(|repo| rebase(&mut repo.path));
+        self.todo_files
+            .iter_mut()
+            .for_each(|file| rebase(&mut file.path));
+        self.notes_dirs.iter_mut().for_each(rebase);
+        self.calendar_files.iter_mut().for_each(rebase);
+    }
+
+    /// Today's date in the configured timezone (local time when unset)
+    pub fn today(&self) -> NaiveDate {
+        match self.timezone {
+            Some(timezone) => Utc::now().with_timezone(&timezone).date_naive(),
+            None => Local::now().date_naive(),
+        }
+    }
+
+    /// Effective limits for a repository path
+    pub fn limits_for(&self, path: &Path) -> Limits {
+        match self.repo(path) {
+            Some(repo) => repo.limits(&self.limits),
+            None => self.limits.clone(),
+        }
+    }
+
+    /// Effective display settings for a repository path
+    pub fn display_for(&self, path: &Path) -> Display {
+        match self.repo(path) {
+            Some(repo) => repo.display(&self.display),
+            None => self.display.clone(),
+        }
+    }
+}
+
+impl RepoConfig {
+    /// Global limits with this repository's overrides applied
+    pub fn limits(&self, global: &Limits) -> Limits {
+        Limits {
+            max_commits: self.max_commits.unwrap_or(global.max_commits),
+            max_changed_files: self.max_changed_files.unwrap_or(global.max_changed_files),
+            ..global.clone()
+        }
+    }
+
+    /// Global display settings with this repository's overrides applied
+    pub fn display(&self, global: &Display) -> Display {
+        Display {
+            show_authors: self.show_authors.unwrap_or(global.show_authors),
+            ..global.clone()
+        }
+    }
+}
+
+impl From<PathBuf> for RepoConfig {
+    fn from(path: PathBuf) -> Self {
+        Self {
+            path,
+            max_commits: None,
+            max_changed_files: None,
+            show_authors: None,
+            default_branch: None,
+        }
+    }
+}
+
+impl From<&str> for RepoConfig {
+    fn from(path: &str) -> Self {
+        PathBuf::from(path).into()
+    }
+}
+
+impl From<RepoEntry> for RepoConfig {
+    fn from(entry: RepoEntry) -> Self {
+        match entry {
+            RepoEntry::Path(path) => path.into(),
+            RepoEntry::Table {
+                path,
+                max_commits,
+                max_changed_files,
+                show_authors,
+                default_branch,
+            } => Self {
+                path,
+                max_commits,
+                max_changed_files,
+                show_authors,
+                default_branch,
+            },
+        }
+    }
+}
+
+impl From<RepoConfig> for RepoEntry {
+    fn from(repo: RepoConfig) -> Self {
+        if repo.max_commits.is_none()
+            && repo.max_changed_files.is_none()
+            && repo.show_authors.is_none()
+            && repo.default_branch.is_none()
+        {
+            // Keep simple entries as plain strings
+            return RepoEntry::Path(repo.path);
+        }
+
+        RepoEntry::Table {
+            path: repo.path,
+            max_commits: repo.max_commits,
+            max_changed_files: repo.max_changed_files,
+            show_authors: repo.show_authors,
+            default_branch: repo.default_branch,
+        }
+    }
+}
+
+impl From<PathBuf> for TodoFile {
+    fn from(path: PathBuf) -> Self {
+        Self {
+            path,
+            format: TodoFormat::default(),
+        }
+    }
+}
+
+impl From<&str> for TodoFile {
+    fn from(path: &str) -> Self {
+        PathBuf::from(path).into()
+    }
+}
+
+impl From<TodoFileEntry> for TodoFile {
+    fn from(entry: TodoFileEntry) -> Self {
+        match entry {
+            TodoFileEntry::Path(path) => path.into(),
+            TodoFileEntry::Table { path, format } => Self { path, format },
+        }
+    }
+}
+
+impl From<TodoFile> for TodoFileEntry {
+    fn from(file: TodoFile) -> Self {
+        // Keep Markdown entries as plain strings
+        match file.format {
+            TodoFormat::Markdown => TodoFileEntry::Path(file.path),
+            format => TodoFileEntry::Table {
+                path: file.path,
+                format,
+            },
         }
     }
 }
@@ -70,13 +588,73 @@ impl Default for Limits {
             max_changed_files: 80,
             max_note_files: 30,
             max_chars_per_item: 2000,
+            max_commit_message_chars: 72,
+            max_notes_total_chars: None,
+            max_total_items: None,
         }
     }
 }
 
 impl Default for Display {
     fn default() -> Self {
-        Self { show_authors: true }
+        Self {
+            show_authors: true,
+            theme: BTreeMap::new(),
+            max_line_width: None,
+            todo_grouping: TodoGrouping::File,
+            show_deltas: false,
+            group_by_author: false,
+            show_streak: false,
+            show_highlights: false,
+            show_repo_breakdown: false,
+            hide_binary_files: false,
+            render_gitmoji: false,
+            stale_branch_days: None,
+            show_warnings: false,
+            show_signatures: false,
+        }
+    }
+}
+
+/// (De)serialize TODO statuses by their config names (`"pending"`,
+/// `"in_progress"`, `"done"`) rather than the variant names used in state
+mod todo_status_names {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::models::TodoStatus;
+
+    fn name(status: &TodoStatus) -> &'static str {
+        match status {
+            TodoStatus::Pending => "pending",
+            TodoStatus::InProgress => "in_progress",
+            TodoStatus::Done => "done",
+        }
+    }
+
+    pub fn serialize<S>(statuses: &[TodoStatus], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(statuses.iter().map(name))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<TodoStatus>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|status| match status.as_str() {
+                "pending" => Ok(TodoStatus::Pending),
+                "in_progress" => Ok(TodoStatus::InProgress),
+                "done" => Ok(TodoStatus::Done),
+                other => Err(D::Error::custom(format!(
+                    "unknown TODO status '{}', expected pending, in_progress or done",
+                    other
+                ))),
+            })
+            .collect()
     }
 }
 
@@ -88,12 +666,12 @@ mod tests {
     fn test_config_default() {
         let config = Config::default();
         assert_eq!(config.output_dir, PathBuf::from("./chronicles"));
-        assert_eq!(config.repos, vec![PathBuf::from(".")]);
+        assert_eq!(config.repos, vec![RepoConfig::from(".")]);
         assert_eq!(config.limits.max_commits, 50);
         assert_eq!(config.limits.max_changed_files, 80);
         assert_eq!(config.limits.max_note_files, 30);
         assert_eq!(config.limits.max_chars_per_item, 2000);
-        assert_eq!(config.display.show_authors, true);
+        assert!(config.display.show_authors);
     }
 
     #[test]
@@ -102,7 +680,126 @@ mod tests {
         let toml = toml::to_string(&config).unwrap();
         let parsed: Config = toml::from_str(&toml).unwrap();
         assert_eq!(parsed.limits.max_commits, 50);
-        assert_eq!(parsed.display.show_authors, true);
+        assert!(parsed.display.show_authors);
+    }
+
+    #[test]
+    fn test_config_partial_uses_defaults() {
+        let parsed: Config = toml::from_str(
+            r#"
+            todo_files = ["todo.md"]
+
+            [limits]
+            max_commits = 10
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(parsed.todo_files, vec![TodoFile::from("todo.md")]);
+        assert_eq!(parsed.limits.max_commits, 10);
+        assert_eq!(parsed.limits.max_changed_files, 80);
+        assert_eq!(parsed.output_dir, PathBuf::from("./chronicles"));
+        assert!(parsed.display.show_authors);
+    }
+
+    #[test]
+    fn test_config_completed_statuses() {
+        assert_eq!(Config::default().completed_statuses, vec![TodoStatus::Done]);
+
+        let parsed: Config =
+            toml::from_str(r#"completed_statuses = ["done", "in_progress"]"#).unwrap();
+        assert_eq!(
+            parsed.completed_statuses,
+            vec![TodoStatus::Done, TodoStatus::InProgress]
+        );
+        let written = toml::to_string(&parsed).unwrap();
+        assert!(written.contains(r#"completed_statuses = ["done", "in_progress"]"#));
+
+        assert!(toml::from_str::<Config>(r#"completed_statuses = ["finished"]"#).is_err());
+    }
+
+    #[test]
+    fn test_config_todo_file_formats() {
+        let parsed: Config = toml::from_str(
+            r#"
+            todo_files = ["todo.md", { path = "todo.txt", format = "todo.txt" }]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(parsed.todo_files[0].format, TodoFormat::Markdown);
+        assert_eq!(parsed.todo_files[1].path, PathBuf::from("todo.txt"));
+        assert_eq!(parsed.todo_files[1].format, TodoFormat::TodoTxt);
+
+        // Markdown files stay plain paths when written back
+        let written = toml::to_string(&parsed).unwrap();
+        assert!(written.contains(r#""todo.md""#));
+        assert!(written.contains(r#"format = "todo.txt""#));
+    }
+
+    #[test]
+    fn test_repos_accept_strings_and_tables() {
+        let parsed: Config = toml::from_str(
+            r#"
+            repos = [
+                "/plain/repo",
+                { path = "/big/repo", max_commits = 200, show_authors = false, default_branch = "develop" },
+            ]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(parsed.repos.len(), 2);
+        assert_eq!(parsed.repos[0], RepoConfig::from("/plain/repo"));
+        assert_eq!(parsed.repos[1].path, PathBuf::from("/big/repo"));
+        assert_eq!(parsed.repos[1].max_commits, Some(200));
+        assert_eq!(parsed.repos[1].show_authors, Some(false));
+        assert_eq!(parsed.repos[1].default_branch.as_deref(), Some("develop"));
+    }
+
+    #[test]
+    fn test_repos_array_of_tables() {
+        let parsed: Config = toml::from_str(
+            r#"
+            [[repos]]
+            path = "/a"
+
+            [[repos]]
+            path = "/b"
+            max_changed_files = 5
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(parsed.repos.len(), 2);
+        assert_eq!(parsed.repos[1].max_changed_files, Some(5));
+    }
+
+    #[test]
+    fn test_plain_repo_serializes_as_string() {
+        let config = Config::default();
+        let toml = toml::to_string(&config).unwrap();
+        assert!(toml.contains("repos = [\".\"]"));
+    }
+
+    #[test]
+    fn test_repo_overrides_resolve_against_globals() {
+        let mut config = Config::default();
+        config.repos.push(RepoConfig {
+            path: PathBuf::from("/big/repo"),
+            max_commits: Some(200),
+            max_changed_files: None,
+            show_authors: Some(false),
+            default_branch: None,
+        });
+
+        let repo = config.repo(Path::new("/big/repo")).unwrap();
+        let limits = repo.limits(&config.limits);
+        assert_eq!(limits.max_commits, 200);
+        assert_eq!(limits.max_changed_files, 80);
+
+        assert!(!config.display_for(Path::new("/big/repo")).show_authors);
+        assert!(config.display_for(Path::new("/other")).show_authors);
     }
 
     #[test]
@@ -114,9 +811,96 @@ mod tests {
         assert_eq!(limits.max_chars_per_item, 2000);
     }
 
+    #[test]
+    fn test_display_theme_table() {
+        let parsed: Config = toml::from_str(
+            r#"
+            [display.theme]
+            headers = "magenta"
+            inline_code = "dark_green"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(parsed.display.theme.len(), 2);
+        assert_eq!(parsed.display.theme["headers"], "magenta");
+        assert!(parsed.display.show_authors);
+    }
+
+    #[test]
+    fn test_display_todo_grouping() {
+        assert_eq!(Display::default().todo_grouping, TodoGrouping::File);
+
+        let parsed: Config = toml::from_str("[display]\ntodo_grouping = \"tag\"\n").unwrap();
+        assert_eq!(parsed.display.todo_grouping, TodoGrouping::Tag);
+
+        assert!(toml::from_str::<Config>("[display]\ntodo_grouping = \"topic\"\n").is_err());
+    }
+
+    #[test]
+    fn test_state_path_relative_to_config() {
+        let config = Config::default();
+        assert_eq!(
+            config.state_path(Path::new("/home/me/.config/chronicle/chronicle.toml")),
+            PathBuf::from("/home/me/.config/chronicle/.chronicle-state.json")
+        );
+        assert_eq!(
+            config.state_path(Path::new("chronicle.toml")),
+            PathBuf::from("./.chronicle-state.json")
+        );
+
+        let config = Config {
+            state_file: PathBuf::from("/var/lib/chronicle/state.json"),
+            ..Config::default()
+        };
+        assert_eq!(
+            config.state_path(Path::new("/home/me/chronicle.toml")),
+            PathBuf::from("/var/lib/chronicle/state.json")
+        );
+    }
+
+    #[test]
+    fn test_rebase_paths() {
+        let mut config: Config = toml::from_str(
+            "output_dir = \"./chronicles\"\nrepos = [\".\", \"/src/app\"]\n\
+             todo_files = [\"todo.md\"]\nnotes_dirs = [\"notes\"]\n",
+        )
+        .unwrap();
+        config.rebase_paths(Path::new("/home/me/project"));
+
+        assert_eq!(
+            config.output_dir,
+            PathBuf::from("/home/me/project/chronicles")
+        );
+        assert_eq!(
+            config.state_file,
+            PathBuf::from("/home/me/project/.chronicle-state.json")
+        );
+        assert_eq!(config.repos[0].path, PathBuf::from("/home/me/project"));
+        assert_eq!(config.repos[1].path, PathBuf::from("/src/app"));
+        assert_eq!(
+            config.todo_files[0].path,
+            PathBuf::from("/home/me/project/todo.md")
+        );
+        assert_eq!(
+            config.notes_dirs[0],
+            PathBuf::from("/home/me/project/notes")
+        );
+    }
+
+    #[test]
+    fn test_timezone() {
+        assert_eq!(Config::default().timezone, None);
+
+        let parsed: Config = toml::from_str("timezone = \"Europe/Berlin\"\n").unwrap();
+        assert_eq!(parsed.timezone, Some(chrono_tz::Europe::Berlin));
+
+        assert!(toml::from_str::<Config>("timezone = \"Mars/Olympus\"\n").is_err());
+    }
+
     #[test]
     fn test_display_default() {
         let display = Display::default();
-        assert_eq!(display.show_authors, true);
+        assert!(display.show_authors);
     }
 }
@@ -0,0 +1,293 @@
+use chrono::Utc;
+use globset::Glob;
+use regex::Regex;
+use std::collections::HashSet;
+use std::fmt;
+
+use super::{parse_since, Config};
+use crate::error::ChronicleError;
+
+/// How serious a configuration problem is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The configuration cannot work as written
+    Error,
+    /// Likely a mistake, but chronicle can still run
+    Warning,
+}
+
+/// A problem found by [`Config::validate`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Problem {
+    fn error(message: String) -> Self {
+        Self {
+            severity: Severity::Error,
+            message,
+        }
+    }
+
+    fn warning(message: String) -> Self {
+        Self {
+            severity: Severity::Warning,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.severity {
+            Severity::Error => write!(f, "error: {}", self.message),
+            Severity::Warning => write!(f, "warning: {}", self.message),
+        }
+    }
+}
+
+impl Config {
+    /// Check the configuration for values that cannot work (errors) and
+    /// paths that don't exist (warnings)
+    pub fn validate(&self) -> Vec<Problem> {
+        let mut problems = Vec::new();
+
+        let limits = [
+            ("limits.max_commits", Some(self.limits.max_commits)),
+            (
+                "limits.max_changed_files",
+                Some(self.limits.max_changed_files),
+            ),
+            ("limits.max_note_files", Some(self.limits.max_note_files)),
+            (
+                "limits.max_chars_per_item",
+                Some(self.limits.max_chars_per_item),
+            ),
+            (
+                "limits.max_commit_message_chars",
+                Some(self.limits.max_commit_message_chars),
+            ),
+            (
+                "limits.max_notes_total_chars",
+                self.limits.max_notes_total_chars,
+            ),
+            ("limits.max_total_items", self.limits.max_total_items),
+            ("notes_max_depth", Some(self.notes_max_depth)),
+            ("summary_timeout_secs", Some(self.summary_timeout_secs)),
+            ("display.max_line_width", self.display.max_line_width),
+            ("display.stale_branch_days", self.display.stale_branch_days),
+        ];
+        for (name, value) in limits {
+            if value == Some(0) {
+                problems.push(Problem::error(format!("{} must be at least 1", name)));
+            }
+        }
+
+        for (name, patterns) in [
+            ("notes_include", &self.notes_include),
+            ("notes_exclude", &self.notes_exclude),
+        ] {
+            for pattern in patterns {
+                if let Err(e) = Glob::new(pattern) {
+                    problems.push(Problem::error(format!("{}: {}", name, e)));
+                }
+            }
+        }
+
+        for (name, patterns) in [
+            ("commit_include_patterns", &self.commit_include_patterns),
+            ("commit_exclude_patterns", &self.commit_exclude_patterns),
+        ] {
+            for pattern in patterns {
+                if let Err(e) = Regex::new(pattern) {
+                    problems.push(Problem::error(format!("{}: {}", name, e)));
+                }
+            }
+        }
+
+        for (name, value) in [
+            ("git_since", &self.git_since),
+            ("notes_since", &self.notes_since),
+        ] {
+            if let Some(Err(e)) = value.as_deref().map(|value| parse_since(value, Utc::now())) {
+                let message = match e {
+                    ChronicleError::Config(message) => message,
+                    other => other.to_string(),
+                };
+                problems.push(Problem::error(format!("{}: {}", name, message)));
+            }
+        }
+
+        if self
+            .note_todo_keywords
+            .iter()
+            .any(|keyword| keyword.trim_end_matches(':').trim().is_empty())
+        {
+            problems.push(Problem::error(
+                "note_todo_keywords must not contain empty keywords".to_string(),
+            ));
+        }
+
+        if !self.notes_dirs.is_empty() && self.note_extensions.is_empty() {
+            problems.push(Problem::error(
+                "note_extensions is empty, so no files in notes_dirs count as notes".to_string(),
+            ));
+        }
+
+        if self.output_dir.exists() && !self.output_dir.is_dir() {
+            problems.push(Problem::error(format!(
+                "output_dir '{}' is not a directory",
+                self.output_dir.display()
+            )));
+        }
+
+        let mut seen = HashSet::new();
+        for repo in &self.repos {
+            if !seen.insert(&repo.path) {
+                problems.push(Problem::warning(format!(
+                    "Repository '{}' is listed more than once",
+                    repo.path.display()
+                )));
+            } else if !repo.path.exists() {
+                problems.push(Problem::warning(format!(
+                    "Repository '{}' does not exist",
+                    repo.path.display()
+                )));
+            }
+        }
+
+        for file in &self.todo_files {
+            if !file.path.is_file() {
+                problems.push(Problem::warning(format!(
+                    "TODO file '{}' does not exist",
+                    file.path.display()
+                )));
+            }
+        }
+
+        for file in &self.calendar_files {
+            if !file.is_file() {
+                problems.push(Problem::warning(format!(
+                    "Calendar file '{}' does not exist",
+                    file.display()
+                )));
+            }
+        }
+
+        for dir in &self.notes_dirs {
+            if !dir.is_dir() {
+                problems.push(Problem::warning(format!(
+                    "Notes directory '{}' does not exist",
+                    dir.display()
+                )));
+            }
+        }
+
+        problems
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_validate_default_config_in_repo() {
+        // The default config tracks "." which is this repository
+        assert!(Config::default().validate().is_empty());
+    }
+
+    #[test]
+    fn test_validate_reports_errors_and_warnings() {
+        let temp_dir = TempDir::new().unwrap();
+        let todo_file = temp_dir.path().join("todo.md");
+        std::fs::write(&todo_file, "- [ ] Task\n").unwrap();
+
+        let mut config = Config {
+            repos: vec![".".into(), ".".into()],
+            todo_files: vec![todo_file.into(), temp_dir.path().join("missing.md").into()],
+            notes_dirs: vec![temp_dir.path().to_path_buf()],
+            note_extensions: vec![],
+            ..Config::default()
+        };
+        config.limits.max_commits = 0;
+        config.limits.max_total_items = Some(0);
+        config.notes_exclude = vec!["drafts/[".to_string()];
+
+        let problems = config.validate();
+        let messages: Vec<String> = problems.iter().map(ToString::to_string).collect();
+
+        assert_eq!(
+            messages,
+            vec![
+                "error: limits.max_commits must be at least 1".to_string(),
+                "error: limits.max_total_items must be at least 1".to_string(),
+                "error: notes_exclude: error parsing glob 'drafts/[': unclosed character class; missing ']'".to_string(),
+                "error: note_extensions is empty, so no files in notes_dirs count as notes"
+                    .to_string(),
+                "warning: Repository '.' is listed more than once".to_string(),
+                format!(
+                    "warning: TODO file '{}' does not exist",
+                    temp_dir.path().join("missing.md").display()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_commit_patterns() {
+        let config = Config {
+            commit_exclude_patterns: vec!["^chore\\(deps\\):".to_string(), "[skip".to_string()],
+            ..Config::default()
+        };
+
+        let problems = config.validate();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0]
+            .to_string()
+            .starts_with("error: commit_exclude_patterns: regex parse error"));
+    }
+
+    #[test]
+    fn test_validate_source_since() {
+        let config = Config {
+            git_since: Some("3 days".to_string()),
+            notes_since: Some("3d".to_string()),
+            ..Config::default()
+        };
+
+        let problems = config.validate();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0]
+            .to_string()
+            .starts_with("error: git_since: Invalid since '3 days'"));
+    }
+
+    #[test]
+    fn test_validate_output_dir_must_be_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let output_file = temp_dir.path().join("chronicles");
+        std::fs::write(&output_file, "").unwrap();
+
+        let config = Config {
+            output_dir: output_file,
+            repos: vec![],
+            ..Config::default()
+        };
+
+        let problems = config.validate();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].severity, Severity::Error);
+
+        let config = Config {
+            output_dir: PathBuf::from("does-not-exist-yet"),
+            repos: vec![],
+            ..Config::default()
+        };
+        assert!(config.validate().is_empty());
+    }
+}
@@ -1,30 +1,165 @@
 //! Markdown terminal formatting using termimad
 
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use termimad::crossterm::style::Color;
 use termimad::{gray, MadSkin};
 
-use crate::display::terminal::should_use_colors;
+use crate::config::Display;
+use crate::display::log;
+use crate::display::terminal::{
+    pager_command, should_use_colors, supports_unicode, terminal_width,
+};
+use crate::error::{ChronicleError, Result};
 
 /// Print markdown to terminal with rich formatting (or plain fallback)
-pub fn print_markdown(markdown: &str) {
-    if should_use_colors() {
-        if let Err(e) = print_rich(markdown) {
-            eprintln!(
-                "Warning: Terminal rendering failed ({}), using plain output",
-                e
-            );
-            print_plain(markdown);
+pub fn print_markdown(markdown: &str, display: &Display) {
+    print!("{}", render(markdown, display));
+}
+
+/// Like `print_markdown`, but through `$PAGER` when stdout is a terminal
+pub fn page_markdown(markdown: &str, display: &Display) {
+    let text = render(markdown, display);
+
+    if let Some(pager) = pager_command() {
+        match write_to_pager(&pager, &text) {
+            Ok(()) => return,
+            Err(e) => log::detail(&format!("Pager '{}' unavailable ({})", pager.join(" "), e)),
         }
+    }
+
+    print!("{}", text);
+}
+
+/// Run the pager and feed it the rendered text on stdin
+fn write_to_pager(pager: &[String], text: &str) -> Result<()> {
+    let (program, args) = pager
+        .split_first()
+        .ok_or_else(|| ChronicleError::Config("Empty pager command".to_string()))?;
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // The user may quit the pager before reading everything
+        let _ = stdin.write_all(text.as_bytes());
+    }
+
+    child.wait()?;
+    Ok(())
+}
+
+/// Render markdown as it will appear on screen
+fn render(markdown: &str, display: &Display) -> String {
+    let markdown = &ascii_fallback(markdown);
+    if should_use_colors() {
+        render_rich(markdown, &display.theme, terminal_width())
     } else {
-        print_plain(markdown);
+        render_plain(markdown)
     }
 }
 
-/// Print with termimad styling
-fn print_rich(markdown: &str) -> Result<(), termimad::Error> {
+/// Swap the repository breakdown's block bars for `#` on terminals without
+/// Unicode; the written file always keeps the blocks
+fn ascii_fallback(markdown: &str) -> String {
+    if supports_unicode() {
+        markdown.to_string()
+    } else {
+        markdown.replace('█', "#")
+    }
+}
+
+/// Colors for the change markers the renderer emits as ` ← **LABEL**`
+const MARKER_COLORS: &[(&str, Color)] = &[
+    ("NEW", Color::Cyan),
+    ("DONE", Color::Green),
+    ("MODIFIED", Color::Yellow),
+    ("STARTED", Color::Blue),
+    ("REOPENED", Color::Magenta),
+    ("PAUSED", Color::DarkYellow),
+    ("RENAMED", Color::DarkCyan),
+];
+
+/// Render with termimad styling, wrapped at `width` columns
+///
+/// A skin styles all bold text alike, so lines carrying a change marker are
+/// rendered on their own with the bold color set to the marker's color.
+/// Fenced code blocks (` ``` ` or `~~~`) are kept whole, so a marker, `#`,
+/// or `- [ ]` inside one stays code.
+fn render_rich(markdown: &str, theme: &BTreeMap<String, String>, width: usize) -> String {
     let mut skin = MadSkin::default();
     customize_skin(&mut skin);
-    skin.print_text(markdown);
-    Ok(())
+
+    let mut themed = skin.clone();
+    match apply_theme(&mut themed, theme) {
+        Ok(()) => skin = themed,
+        Err(e) => log::warn(&format!("{}; using the default theme", e)),
+    }
+
+    let width = Some(width);
+    let mut output = String::new();
+    let mut block: Vec<Cow<str>> = Vec::new();
+    let mut fence: Option<&str> = None;
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        let marker = fence.or_else(|| {
+            ["```", "~~~"]
+                .into_iter()
+                .find(|marker| trimmed.starts_with(marker))
+        });
+        if let Some(marker) = marker {
+            let delimiter = trimmed.starts_with(marker);
+            fence = match fence {
+                Some(_) if delimiter => None,
+                _ => Some(marker),
+            };
+            // termimad only knows unindented backtick fences, and would end
+            // a tilde block at a backtick line inside it
+            block.push(if delimiter {
+                Cow::Owned(trimmed.replacen(marker, "```", 1))
+            } else if trimmed.starts_with("```") {
+                Cow::Owned(format!(" {}", line))
+            } else {
+                Cow::Borrowed(line)
+            });
+            continue;
+        }
+
+        match marker_color(line) {
+            Some(color) => {
+                if !block.is_empty() {
+                    output.push_str(&skin.text(&block.join("\n"), width).to_string());
+                    block.clear();
+                }
+
+                let mut marker_skin = skin.clone();
+                marker_skin.bold.set_fg(color);
+                output.push_str(&marker_skin.text(line, width).to_string());
+            }
+            None => block.push(Cow::Borrowed(line)),
+        }
+    }
+
+    if !block.is_empty() {
+        output.push_str(&skin.text(&block.join("\n"), width).to_string());
+    }
+
+    output
+}
+
+/// Color for the change marker on a line, if it has one
+fn marker_color(line: &str) -> Option<Color> {
+    let (_, rest) = line.rsplit_once("← **")?;
+    let (label, _) = rest.split_once("**")?;
+
+    MARKER_COLORS
+        .iter()
+        .find(|(marker, _)| marker.eq_ignore_ascii_case(label))
+        .map(|(_, color)| *color)
 }
 
 /// Customize termimad skin to match chronicle aesthetic
@@ -56,9 +191,46 @@ fn customize_skin(skin: &mut MadSkin) {
     skin.bullet.set_fg(Cyan);
 }
 
-/// Print plain markdown without formatting
-fn print_plain(markdown: &str) {
-    println!("{}", markdown);
+/// Check `[display.theme]` for unknown elements and colors
+pub fn check_theme(theme: &BTreeMap<String, String>) -> Result<()> {
+    apply_theme(&mut MadSkin::default(), theme)
+}
+
+/// Apply user colors from `[display.theme]` on top of the chronicle skin
+fn apply_theme(skin: &mut MadSkin, theme: &BTreeMap<String, String>) -> Result<()> {
+    for (element, color_name) in theme {
+        let color = Color::try_from(color_name.as_str()).map_err(|_| {
+            ChronicleError::Config(format!(
+                "Unknown color '{}' for theme element '{}'",
+                color_name, element
+            ))
+        })?;
+
+        match element.as_str() {
+            "headers" => {
+                for header in skin.headers.iter_mut() {
+                    header.set_fg(color);
+                }
+            }
+            "inline_code" => skin.inline_code.set_fg(color),
+            "code_block" => skin.code_block.set_fg(color),
+            "bullet" => skin.bullet.set_fg(color),
+            "table" => skin.table.set_fg(color),
+            _ => {
+                return Err(ChronicleError::Config(format!(
+                    "Unknown theme element '{}' (expected headers, inline_code, code_block, bullet, or table)",
+                    element
+                )))
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Plain markdown without formatting
+fn render_plain(markdown: &str) -> String {
+    format!("{}\n", markdown)
 }
 
 #[cfg(test)]
@@ -73,11 +245,25 @@ mod tests {
         std::env::set_var("NO_COLOR", "1");
 
         // Should not panic, should use plain output
-        print_markdown("# Test\n\nHello **world**");
+        print_markdown("# Test\n\nHello **world**", &Display::default());
 
         std::env::remove_var("NO_COLOR");
     }
 
+    #[test]
+    #[serial]
+    fn test_ascii_fallback() {
+        std::env::remove_var("LC_ALL");
+        std::env::remove_var("LC_CTYPE");
+
+        std::env::set_var("LANG", "C");
+        assert_eq!(ascii_fallback("- `a` ███ 3"), "- `a` ### 3");
+        std::env::set_var("LANG", "en_US.UTF-8");
+        assert_eq!(ascii_fallback("- `a` ███ 3"), "- `a` ███ 3");
+
+        std::env::remove_var("LANG");
+    }
+
     #[test]
     fn test_customize_skin_no_panic() {
         let mut skin = MadSkin::default();
@@ -85,18 +271,144 @@ mod tests {
         // Should customize without panicking
         customize_skin(&mut skin);
 
-        // Basic assertion to verify skin was modified
-        assert!(true);
+        // Verify skin was modified
+        assert_eq!(
+            skin.inline_code.object_style.foreground_color,
+            Some(termimad::crossterm::style::Color::Yellow)
+        );
     }
 
     #[test]
-    fn test_print_rich_with_valid_markdown() {
-        // Should handle valid markdown without error
-        let result = print_rich("# Header\n\n- Item 1\n- Item 2");
-        // In test environment (non-TTY), this may fail, which is expected
-        // We're testing it doesn't panic, not that it succeeds
-        match result {
-            Ok(_) | Err(_) => assert!(true),
+    fn test_render_rich_keeps_fenced_blocks_whole() {
+        let markdown = "## Notes\n\n\
+                        ```markdown\n\
+                        # Not a heading\n\
+                        - [ ] Not a todo ← **NEW**\n\
+                        ```\n\
+                        ~~~\n\
+                        ## Nor this\n\
+                        ```\n\
+                        ~~~\n\
+                        - [x] Real todo ← **DONE**";
+        let output = render_rich(markdown, &BTreeMap::new(), 100);
+
+        // Code keeps its text, without the fences
+        assert!(output.contains("# Not a heading"));
+        assert!(output.contains("- [ ] Not a todo ← **NEW**"));
+        assert!(output.contains("## Nor this"));
+        assert!(output.contains(" ```"));
+        assert!(!output.contains("~~~"));
+        assert!(!output.contains("## Notes"));
+        assert!(!output.contains("**DONE**"));
+
+        // Both kinds of block are styled as code
+        let theme = BTreeMap::from([("code_block".to_string(), "magenta".to_string())]);
+        for block in ["```\n# Code\n```", "~~~\n# Code\n~~~"] {
+            assert!(!render_rich(block, &BTreeMap::new(), 100).contains("```"));
+            assert_ne!(
+                render_rich(block, &theme, 100),
+                render_rich(block, &BTreeMap::new(), 100)
+            );
         }
     }
+
+    #[test]
+    fn test_marker_color() {
+        assert_eq!(
+            marker_color("- [x] Buy milk ← **DONE**  "),
+            Some(Color::Green)
+        );
+        assert_eq!(
+            marker_color("#### `feature` (ahead 2, behind 0) ← **NEW**"),
+            Some(Color::Cyan)
+        );
+        assert_eq!(
+            marker_color("### `notes/idea.md` ← **modified**"),
+            Some(Color::Yellow)
+        );
+        assert_eq!(
+            marker_color("### `notes/b.md` ← **renamed** from `a.md`"),
+            Some(Color::DarkCyan)
+        );
+        assert_eq!(marker_color("- `abc1234` Fix bug — *Alice*"), None);
+        assert_eq!(marker_color("**Path:** `/repo`"), None);
+    }
+
+    #[test]
+    fn test_apply_theme() {
+        let mut skin = MadSkin::default();
+        customize_skin(&mut skin);
+
+        let theme = BTreeMap::from([
+            ("headers".to_string(), "magenta".to_string()),
+            ("inline_code".to_string(), "dark_green".to_string()),
+        ]);
+        apply_theme(&mut skin, &theme).unwrap();
+
+        assert_eq!(
+            skin.headers[1].compound_style.object_style.foreground_color,
+            Some(Color::Magenta)
+        );
+        assert_eq!(
+            skin.inline_code.object_style.foreground_color,
+            Some(Color::DarkGreen)
+        );
+    }
+
+    #[test]
+    fn test_apply_theme_rejects_invalid_entries() {
+        let mut skin = MadSkin::default();
+
+        let bad_color = BTreeMap::from([("headers".to_string(), "blurple".to_string())]);
+        assert!(apply_theme(&mut skin, &bad_color)
+            .unwrap_err()
+            .to_string()
+            .contains("Unknown color 'blurple'"));
+
+        let bad_element = BTreeMap::from([("footer".to_string(), "red".to_string())]);
+        assert!(apply_theme(&mut skin, &bad_element)
+            .unwrap_err()
+            .to_string()
+            .contains("Unknown theme element 'footer'"));
+    }
+
+    #[test]
+    fn test_render_rich_with_markers() {
+        // Marker lines are rendered separately but kept in order
+        let output = render_rich(
+            "## TODOs\n\n- [ ] Task ← **NEW**  \n- [x] Done ← **DONE**  \n\nText",
+            &BTreeMap::new(),
+            100,
+        );
+
+        let task = output.find("Task").unwrap();
+        let done = output.find("Done").unwrap();
+        let text = output.find("Text").unwrap();
+        assert!(task < done && done < text);
+    }
+
+    #[test]
+    fn test_render_rich_with_valid_markdown() {
+        let output = render_rich("# Header\n\n- Item 1\n- Item 2", &BTreeMap::new(), 100);
+
+        assert!(output.contains("Header"));
+        assert!(output.contains("Item 2"));
+    }
+
+    #[test]
+    fn test_render_rich_wraps_to_width() {
+        let long_line = "word ".repeat(60);
+        let output = render_rich(&long_line, &BTreeMap::new(), 100);
+
+        assert!(output.lines().count() > 1);
+        assert!(output.lines().all(|line| line.chars().count() <= 100));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_write_to_pager() {
+        assert!(write_to_pager(&["cat".to_string()], "").is_ok());
+        assert!(write_to_pager(&["chronicle-no-such-pager".to_string()], "text").is_err());
+        assert!(write_to_pager(&[], "text").is_err());
+    }
 }
@@ -0,0 +1,87 @@
+//! Verbosity-aware status output
+//!
+//! All non-Markdown output (status lines, warnings, diagnostics) goes through
+//! these helpers so `--quiet` and `--verbose` are honored everywhere.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much status output to print
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Errors only
+    Quiet,
+    /// Status lines and warnings (default)
+    Normal,
+    /// Additionally print per-source timing and counts
+    Verbose,
+}
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+/// Set the process-wide verbosity level
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+/// Get the current verbosity level
+pub fn verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        1 => Verbosity::Normal,
+        _ => Verbosity::Verbose,
+    }
+}
+
+/// Print a status line to stdout (suppressed by `--quiet`)
+pub fn info(message: &str) {
+    if verbosity() >= Verbosity::Normal {
+        println!("{}", message);
+    }
+}
+
+/// Print a status line to stderr (suppressed by `--quiet`), for output that
+/// must not mix with Markdown on stdout
+pub fn note(message: &str) {
+    if verbosity() >= Verbosity::Normal {
+        eprintln!("{}", message);
+    }
+}
+
+/// Print a warning to stderr (suppressed by `--quiet`)
+pub fn warn(message: &str) {
+    if verbosity() >= Verbosity::Normal {
+        eprintln!("Warning: {}", message);
+    }
+}
+
+/// Print a diagnostic line to stderr (only with `--verbose`)
+pub fn detail(message: &str) {
+    if verbosity() >= Verbosity::Verbose {
+        eprintln!("{}", message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn test_verbosity_roundtrip() {
+        set_verbosity(Verbosity::Quiet);
+        assert_eq!(verbosity(), Verbosity::Quiet);
+
+        set_verbosity(Verbosity::Verbose);
+        assert_eq!(verbosity(), Verbosity::Verbose);
+
+        set_verbosity(Verbosity::Normal);
+        assert_eq!(verbosity(), Verbosity::Normal);
+    }
+
+    #[test]
+    fn test_verbosity_ordering() {
+        assert!(Verbosity::Quiet < Verbosity::Normal);
+        assert!(Verbosity::Normal < Verbosity::Verbose);
+    }
+}
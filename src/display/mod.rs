@@ -1,8 +1,12 @@
 //! Terminal display module
 //!
-//! Handles rich terminal output with automatic TTY detection.
+//! Handles rich terminal output with automatic TTY detection,
+//! verbosity-aware status messages, and opening written files.
 
 mod formatter;
+pub mod log;
+pub mod opener;
 mod terminal;
 
-pub use formatter::print_markdown;
+pub use formatter::{check_theme, page_markdown, print_markdown};
+pub use log::{set_verbosity, Verbosity};
@@ -0,0 +1,40 @@
+//! Opening files in the operating system's default application
+
+use std::path::Path;
+
+use super::log;
+
+/// Open `path` with the default application for its type, warning instead
+/// of failing when that isn't possible (e.g. over SSH)
+///
+/// The path is handed to the platform's launcher (`open`, `xdg-open`, or
+/// the Windows shell) as a single argument, never through a shell command
+/// line that could interpret characters like `&` or `%` in it.
+pub fn open(path: &Path) {
+    if !has_desktop() {
+        log::warn(&format!(
+            "No graphical session to open '{}' in",
+            path.display()
+        ));
+        return;
+    }
+
+    match open::that_detached(path) {
+        Ok(()) => log::detail(&format!("Opened: {}", path.display())),
+        Err(e) => log::warn(&format!("Cannot open '{}': {}", path.display(), e)),
+    }
+}
+
+/// Whether there is a desktop to open files on
+///
+/// Only checked on Linux and other Unix desktops, where a session over SSH
+/// or in a container has neither `DISPLAY` nor `WAYLAND_DISPLAY`.
+fn has_desktop() -> bool {
+    if cfg!(any(target_os = "macos", windows)) {
+        return true;
+    }
+
+    ["DISPLAY", "WAYLAND_DISPLAY"]
+        .iter()
+        .any(|var| std::env::var_os(var).is_some_and(|value| !value.is_empty()))
+}
@@ -28,6 +28,66 @@ pub fn should_use_colors() -> bool {
     std::io::stdout().is_terminal()
 }
 
+/// Determine if the terminal can show Unicode block characters
+///
+/// False with a locale (`LC_ALL`, `LC_CTYPE`, `LANG`) that isn't UTF-8;
+/// an unset locale counts as UTF-8.
+pub fn supports_unicode() -> bool {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .is_none_or(|locale| {
+            let locale = locale.to_ascii_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        })
+}
+
+/// Width used for rendering when stdout is not a terminal
+const DEFAULT_WIDTH: usize = 100;
+
+/// Width to wrap rendered output at
+pub fn terminal_width() -> usize {
+    let is_tty = std::io::stdout().is_terminal();
+    let size = is_tty
+        .then(termimad::crossterm::terminal::size)
+        .and_then(|size| size.ok())
+        .map(|(width, _)| width as usize);
+    width_for(is_tty, size)
+}
+
+/// Width to wrap at for a stdout that is a terminal if `is_tty`, `size`
+/// columns wide if known
+fn width_for(is_tty: bool, size: Option<usize>) -> usize {
+    match size {
+        Some(width) if is_tty && width > 0 => width,
+        _ => DEFAULT_WIDTH,
+    }
+}
+
+/// Pager command to run for long output, if stdout is a terminal
+///
+/// Uses `$PAGER` (default `less -R`); an empty `$PAGER` disables paging.
+pub fn pager_command() -> Option<Vec<String>> {
+    pager_command_for(std::io::stdout().is_terminal())
+}
+
+/// Pager command for a stdout that is a terminal if `is_tty`
+fn pager_command_for(is_tty: bool) -> Option<Vec<String>> {
+    if !is_tty {
+        return None;
+    }
+
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| "less -R".to_string());
+    let command: Vec<String> = pager.split_whitespace().map(String::from).collect();
+
+    if command.is_empty() {
+        None
+    } else {
+        Some(command)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -41,7 +101,7 @@ mod tests {
         std::env::remove_var("CLICOLOR");
 
         std::env::set_var("NO_COLOR", "1");
-        assert_eq!(should_use_colors(), false);
+        assert!(!should_use_colors());
         std::env::remove_var("NO_COLOR");
     }
 
@@ -53,7 +113,7 @@ mod tests {
         std::env::remove_var("CLICOLOR");
 
         std::env::set_var("CLICOLOR_FORCE", "1");
-        assert_eq!(should_use_colors(), true);
+        assert!(should_use_colors());
         std::env::remove_var("CLICOLOR_FORCE");
     }
 
@@ -65,11 +125,53 @@ mod tests {
 
         std::env::set_var("NO_COLOR", "1");
         std::env::set_var("CLICOLOR_FORCE", "1");
-        assert_eq!(should_use_colors(), false);
+        assert!(!should_use_colors());
         std::env::remove_var("NO_COLOR");
         std::env::remove_var("CLICOLOR_FORCE");
     }
 
+    #[test]
+    #[serial]
+    fn test_supports_unicode() {
+        std::env::remove_var("LC_ALL");
+        std::env::remove_var("LC_CTYPE");
+
+        std::env::set_var("LANG", "en_US.UTF-8");
+        assert!(supports_unicode());
+        std::env::set_var("LANG", "C");
+        assert!(!supports_unicode());
+        std::env::set_var("LC_ALL", "de_DE.utf8");
+        assert!(supports_unicode());
+
+        std::env::remove_var("LC_ALL");
+        std::env::remove_var("LANG");
+    }
+
+    #[test]
+    fn test_width_for() {
+        assert_eq!(width_for(true, Some(80)), 80);
+        assert_eq!(width_for(false, Some(80)), DEFAULT_WIDTH);
+        assert_eq!(width_for(true, Some(0)), DEFAULT_WIDTH);
+        assert_eq!(width_for(true, None), DEFAULT_WIDTH);
+    }
+
+    #[test]
+    #[serial]
+    fn test_pager_command_for() {
+        std::env::set_var("PAGER", "less");
+        assert_eq!(pager_command_for(true), Some(vec!["less".to_string()]));
+        assert_eq!(pager_command_for(false), None);
+
+        std::env::set_var("PAGER", "");
+        assert_eq!(pager_command_for(true), None);
+
+        std::env::remove_var("PAGER");
+        assert_eq!(
+            pager_command_for(true),
+            Some(vec!["less".to_string(), "-R".to_string()])
+        );
+    }
+
     #[test]
     #[serial]
     fn test_clicolor_zero_disables() {
@@ -78,7 +180,7 @@ mod tests {
         std::env::remove_var("CLICOLOR_FORCE");
 
         std::env::set_var("CLICOLOR", "0");
-        assert_eq!(should_use_colors(), false);
+        assert!(!should_use_colors());
         std::env::remove_var("CLICOLOR");
     }
 }
@@ -12,8 +12,13 @@ pub enum ChronicleError {
     #[error("Collector error: {0}")]
     Collector(String),
 
+    #[error("Watch error: {0}")]
+    Watch(String),
+
+    #[error("Summary command error: {0}")]
+    SummaryCommand(String),
+
     #[error("Renderer error: {0}")]
-    #[allow(dead_code)]
     Renderer(String),
 
     #[error("IO error: {0}")]
@@ -27,6 +32,22 @@ pub enum ChronicleError {
 
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
+
+    #[error("No activity to report")]
+    NoActivity,
+}
+
+/// Exit code for `gen --fail-on-empty` when nothing was collected
+pub const EXIT_NO_ACTIVITY: i32 = 2;
+
+impl ChronicleError {
+    /// Process exit code for this error
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ChronicleError::NoActivity => EXIT_NO_ACTIVITY,
+            _ => 1,
+        }
+    }
 }
 
 /// Result type for Chronicle operations
@@ -54,6 +75,24 @@ mod tests {
         assert_eq!(err.to_string(), "Collector error: test collector error");
     }
 
+    #[test]
+    fn test_error_display_watch() {
+        let err = ChronicleError::Watch("test watch error".to_string());
+        assert_eq!(err.to_string(), "Watch error: test watch error");
+    }
+
+    #[test]
+    fn test_error_display_summary_command() {
+        let err = ChronicleError::SummaryCommand("timed out".to_string());
+        assert_eq!(err.to_string(), "Summary command error: timed out");
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        assert_eq!(ChronicleError::NoActivity.exit_code(), EXIT_NO_ACTIVITY);
+        assert_eq!(ChronicleError::Config("x".to_string()).exit_code(), 1);
+    }
+
     #[test]
     fn test_error_display_renderer() {
         let err = ChronicleError::Renderer("test renderer error".to_string());
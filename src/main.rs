@@ -1,3 +1,4 @@
+mod archive;
 mod cli;
 mod collectors;
 mod config;
@@ -7,7 +8,8 @@ mod models;
 mod renderer;
 mod state;
 
-use clap::{Parser, Subcommand};
+use clap::builder::RangedU64ValueParser;
+use clap::{Parser, Subcommand, ValueHint};
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -15,6 +17,14 @@ use std::path::PathBuf;
 #[command(about = "Generate daily chronicles from Git, TODOs, and notes", long_about = None)]
 #[command(version)]
 struct Cli {
+    /// Suppress status output and warnings (errors are still shown)
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Print per-source timing and counts
+    #[arg(short, long, global = true)]
+    verbose: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -34,30 +44,167 @@ enum Commands {
     /// Generate a daily chronicle
     Gen {
         /// Path to config file
-        #[arg(short, long)]
+        #[arg(short, long, value_hint = ValueHint::FilePath)]
         config: Option<PathBuf>,
 
+        /// Named profile from the config file to apply
+        #[arg(short, long)]
+        profile: Option<String>,
+
         /// Date for the chronicle (defaults to today)
         #[arg(long)]
         date: Option<String>,
 
-        /// Custom since timestamp (defaults to last run or 24h ago)
+        /// Start of the window: a duration back from now (48h, 3d, 1w) or an
+        /// RFC 3339 timestamp (defaults to 24h ago)
         #[arg(long)]
         since: Option<String>,
 
-        /// Only collect from specific sources (git, todos, notes)
+        /// Collect everything since the last successful run (falls back to 24h)
+        #[arg(long, conflicts_with = "since")]
+        since_last_run: bool,
+
+        /// Start the window at midnight of the chronicle date (in the configured
+        /// timezone, else local time)
+        #[arg(long, conflicts_with_all = ["since", "since_last_run"])]
+        today: bool,
+
+        /// First day of a date range to cover (YYYY-MM-DD)
+        #[arg(long, conflicts_with_all = ["date", "since", "since_last_run", "today"])]
+        from: Option<String>,
+
+        /// Last day of the range (defaults to open-ended); names the chronicle
+        #[arg(long, requires = "from")]
+        to: Option<String>,
+
+        /// Only collect from these sources, comma-separated (git, todos, notes,
+        /// calendar)
         #[arg(long)]
         only: Option<String>,
 
+        /// Collect from every source except these, comma-separated
+        #[arg(long, conflicts_with = "only")]
+        skip: Option<String>,
+
+        /// Only your own commits, by the user.name / user.email in each
+        /// repository's Git config
+        #[arg(long)]
+        author_me: bool,
+
+        /// Chronicle this repository instead of the configured ones (repeatable)
+        #[arg(long = "repo", value_name = "PATH", value_hint = ValueHint::DirPath)]
+        repos: Vec<PathBuf>,
+
+        /// Override limits.max_commits (including per-repository values) for this run
+        #[arg(long, value_name = "N", value_parser = positive_count())]
+        limit_commits: Option<usize>,
+
+        /// Override limits.max_note_files for this run
+        #[arg(long, value_name = "N", value_parser = positive_count())]
+        limit_notes: Option<usize>,
+
+        /// Formats to write, comma-separated: md (the chronicle), and json,
+        /// toml, or yaml (the collected data, next to it as
+        /// chronicle-YYYY-MM-DD.json and so on)
+        #[arg(
+            long,
+            value_delimiter = ',',
+            value_parser = ["md", "json", "toml", "yaml"],
+            default_value = "md"
+        )]
+        format: Vec<String>,
+
+        /// Layout of the Git activity: per repository, or one timeline
+        #[arg(long, value_parser = ["repos", "timeline"], default_value = "repos")]
+        view: String,
+
         /// Dry run - print to stdout instead of writing file
         #[arg(long)]
         dry_run: bool,
+
+        /// Exit with code 2 when there is no activity to report
+        #[arg(long)]
+        fail_on_empty: bool,
+
+        /// Fail instead of skipping a repository, TODO file, notes directory,
+        /// or calendar that cannot be read
+        #[arg(long)]
+        strict: bool,
+
+        /// Add a timestamped section to the day's chronicle if it exists
+        /// instead of overwriting it (collects since the last run unless a
+        /// window is given)
+        #[arg(long, conflicts_with = "watch")]
+        append: bool,
+
+        /// Keep running and print a fresh chronicle whenever a repository,
+        /// TODO file, notes directory, or calendar file changes
+        #[arg(long, conflicts_with_all = ["dry_run", "fail_on_empty"])]
+        watch: bool,
+
+        /// With --watch, also write each chronicle and update state
+        #[arg(long, requires = "watch")]
+        save: bool,
+
+        /// Open the written chronicle in the default application
+        #[arg(long, conflicts_with = "watch")]
+        open: bool,
     },
     /// Show commands
     Show {
         #[command(subcommand)]
         command: ShowCommands,
     },
+    /// Delete old chronicle files from the output directory
+    Clean {
+        /// Path to config file
+        #[arg(short, long, value_hint = ValueHint::FilePath)]
+        config: Option<PathBuf>,
+
+        /// Named profile from the config file to apply
+        #[arg(short, long)]
+        profile: Option<String>,
+
+        /// Remove chronicles older than this age (e.g. 90d, 12w)
+        #[arg(long, required_unless_present = "keep", conflicts_with = "keep")]
+        older_than: Option<String>,
+
+        /// Keep only this many most recent chronicles
+        #[arg(long)]
+        keep: Option<usize>,
+
+        /// List files that would be removed without deleting them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Combine the chronicles of a date range into one document
+    Merge {
+        /// Path to config file
+        #[arg(short, long, value_hint = ValueHint::FilePath)]
+        config: Option<PathBuf>,
+
+        /// Named profile from the config file to apply
+        #[arg(short, long)]
+        profile: Option<String>,
+
+        /// First day to include (YYYY-MM-DD)
+        #[arg(long)]
+        from: String,
+
+        /// Last day to include (YYYY-MM-DD)
+        #[arg(long)]
+        to: String,
+
+        /// File to write the merged chronicle to (prints to stdout when omitted)
+        #[arg(long, value_hint = ValueHint::FilePath)]
+        out: Option<PathBuf>,
+    },
+    /// Print a shell completion script to stdout
+    #[command(hide = true)]
+    Completions {
+        /// Shell to generate completions for
+        shell: clap_complete::Shell,
+    },
 }
 
 #[derive(Subcommand)]
@@ -65,8 +212,26 @@ enum ShowCommands {
     /// Display the most recent chronicle
     Latest {
         /// Path to config file
+        #[arg(short, long, value_hint = ValueHint::FilePath)]
+        config: Option<PathBuf>,
+
+        /// Named profile from the config file to apply
         #[arg(short, long)]
+        profile: Option<String>,
+
+        /// Open the chronicle in the default application instead of printing it
+        #[arg(long)]
+        open: bool,
+    },
+    /// Display the number of consecutive days with a chronicle
+    Streak {
+        /// Path to config file
+        #[arg(short, long, value_hint = ValueHint::FilePath)]
         config: Option<PathBuf>,
+
+        /// Named profile from the config file to apply
+        #[arg(short, long)]
+        profile: Option<String>,
     },
 }
 
@@ -75,45 +240,179 @@ enum ConfigCommands {
     /// Initialize chronicle.toml configuration file
     Init {
         /// Path where to create the config file
-        #[arg(long)]
+        #[arg(long, value_hint = ValueHint::FilePath)]
         path: Option<PathBuf>,
+        /// Look for repositories, TODO files, and notes directories and
+        /// ask which to include
+        #[arg(long, short = 'i')]
+        interactive: bool,
+    },
+    /// Print the effective configuration (including defaults)
+    Show {
+        /// Path to config file
+        #[arg(short, long, value_hint = ValueHint::FilePath)]
+        config: Option<PathBuf>,
+
+        /// Named profile from the config file to apply
+        #[arg(short, long)]
+        profile: Option<String>,
+    },
+    /// Validate the configuration file and exit
+    Check {
+        /// Path to config file
+        #[arg(short, long, value_hint = ValueHint::FilePath)]
+        config: Option<PathBuf>,
+
+        /// Named profile from the config file to apply
+        #[arg(short, long)]
+        profile: Option<String>,
+    },
+    /// Open the configuration file in $EDITOR and validate it afterwards
+    Edit {
+        /// Path to config file
+        #[arg(short, long, value_hint = ValueHint::FilePath)]
+        config: Option<PathBuf>,
     },
 }
 
 #[derive(Subcommand)]
 enum StateCommands {
+    /// Show tracked sources and when a chronicle was last generated
+    Show {
+        /// Path to the config file (defaults to chronicle.toml)
+        #[arg(short, long, value_hint = ValueHint::FilePath)]
+        config: Option<PathBuf>,
+
+        /// Named profile from the config file to apply
+        #[arg(short, long)]
+        profile: Option<String>,
+    },
     /// Reset state tracking (clears all incremental update tracking)
     Reset {
         /// Path to the config file (defaults to chronicle.toml)
-        #[arg(short, long)]
+        #[arg(short, long, value_hint = ValueHint::FilePath)]
         config: Option<PathBuf>,
+
+        /// Named profile from the config file to apply
+        #[arg(short, long)]
+        profile: Option<String>,
     },
 }
 
+/// Parser for counts that must be at least 1
+fn positive_count() -> RangedU64ValueParser<usize> {
+    RangedU64ValueParser::new().range(1..)
+}
+
 fn main() {
     let cli = Cli::parse();
 
+    display::set_verbosity(if cli.quiet {
+        display::Verbosity::Quiet
+    } else if cli.verbose {
+        display::Verbosity::Verbose
+    } else {
+        display::Verbosity::Normal
+    });
+
     let result = match cli.command {
         Commands::Config { command } => match command {
-            ConfigCommands::Init { path } => cli::config::init(path),
+            ConfigCommands::Init { path, interactive } => cli::config::init(path, interactive),
+            ConfigCommands::Show { config, profile } => cli::config::show(config, profile),
+            ConfigCommands::Check { config, profile } => cli::config::check(config, profile),
+            ConfigCommands::Edit { config } => cli::config::edit(config),
         },
         Commands::State { command } => match command {
-            StateCommands::Reset { config } => cli::state::reset(config),
+            StateCommands::Show { config, profile } => cli::state::show(config, profile),
+            StateCommands::Reset { config, profile } => cli::state::reset(config, profile),
         },
         Commands::Gen {
             config,
+            profile,
             date,
             since,
+            since_last_run,
+            today,
+            from,
+            to,
             only,
+            skip,
+            author_me,
+            repos,
+            limit_commits,
+            limit_notes,
+            format,
+            view,
             dry_run,
-        } => cli::gen::run(config, date, since, only, dry_run),
+            fail_on_empty,
+            strict,
+            append,
+            watch,
+            save,
+            open,
+        } => cli::gen::run(cli::gen::GenOptions {
+            config_path: config,
+            profile,
+            date,
+            since,
+            since_last_run,
+            today,
+            from,
+            to,
+            only,
+            skip,
+            author_me,
+            repos,
+            limit_commits,
+            limit_notes,
+            formats: format
+                .iter()
+                .map(|format| match format.as_str() {
+                    "json" => cli::gen::OutputFormat::Json,
+                    "toml" => cli::gen::OutputFormat::Toml,
+                    "yaml" => cli::gen::OutputFormat::Yaml,
+                    _ => cli::gen::OutputFormat::Markdown,
+                })
+                .collect(),
+            view: match view.as_str() {
+                "timeline" => renderer::View::Timeline,
+                _ => renderer::View::Repositories,
+            },
+            dry_run,
+            fail_on_empty,
+            strict,
+            append,
+            watch,
+            save,
+            open,
+        }),
         Commands::Show { command } => match command {
-            ShowCommands::Latest { config } => cli::show::latest(config),
+            ShowCommands::Latest {
+                config,
+                profile,
+                open,
+            } => cli::show::latest(config, profile, open),
+            ShowCommands::Streak { config, profile } => cli::show::streak(config, profile),
         },
+        Commands::Clean {
+            config,
+            profile,
+            older_than,
+            keep,
+            dry_run,
+        } => cli::clean::run(config, profile, older_than, keep, dry_run),
+        Commands::Merge {
+            config,
+            profile,
+            from,
+            to,
+            out,
+        } => cli::merge::run(config, profile, from, to, out),
+        Commands::Completions { shell } => cli::completions::run(shell),
     };
 
     if let Err(e) = result {
         eprintln!("Error: {}", e);
-        std::process::exit(1);
+        std::process::exit(e.exit_code());
     }
 }
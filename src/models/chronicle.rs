@@ -1,7 +1,9 @@
 use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
 
-use super::source::{ChangeKind, Note, Repository, Todo};
+use super::source::{ChangeKind, Event, Note, Repository, Todo, TodoStatus, TodoTotals};
 
 /// Aggregate chronicle for a specific date/time range
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -10,38 +12,182 @@ pub struct Chronicle {
     pub date: NaiveDate,
     /// Start time for incremental updates (only items since this time)
     pub since: DateTime<Utc>,
+    /// End of an explicit date range (only items up to this time)
+    #[serde(default)]
+    pub until: Option<DateTime<Utc>>,
     /// When this chronicle was generated
     pub generated_at: DateTime<Utc>,
+    /// Machine it was generated on, with `include_host`
+    #[serde(default)]
+    pub host: Option<String>,
     /// Git repositories with their activity
     pub repositories: Vec<Repository>,
     /// TODO items
     pub todos: Vec<Todo>,
+    /// Open and done TODOs in the TODO files, including unchanged ones
+    #[serde(default)]
+    pub todo_totals: TodoTotals,
     /// Note updates
     pub notes: Vec<Note>,
+    /// Updated notes left out over `max_notes_total_chars`
+    #[serde(default)]
+    pub notes_omitted: usize,
+    /// Calendar events that started in the chronicle's window
+    #[serde(default)]
+    pub events: Vec<Event>,
+    /// Sources the collectors skipped, and why
+    #[serde(default)]
+    pub warnings: Vec<CollectionWarning>,
+}
+
+/// A source a collector skipped or only partly read
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectionWarning {
+    /// Collector that reported it (`git`, `todos`, or `notes`)
+    pub source: String,
+    /// What was skipped and why
+    pub message: String,
+    /// Whether the source was left out or read with a fallback
+    #[serde(default)]
+    pub kind: WarningKind,
+}
+
+/// How much of a source a [`CollectionWarning`] cost
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningKind {
+    /// Nothing was collected from it (`gen --strict` fails on these)
+    #[default]
+    Skipped,
+    /// Collected, but with a fallback or without some detail
+    Degraded,
+}
+
+impl CollectionWarning {
+    /// A source that was skipped, with a message like
+    /// `Skipping repository '...': reason`
+    pub fn new(source: &str, message: String) -> Self {
+        Self {
+            source: source.to_string(),
+            message,
+            kind: WarningKind::Skipped,
+        }
+    }
+
+    /// A source that was read, but not fully
+    pub fn degraded(source: &str, message: String) -> Self {
+        Self {
+            kind: WarningKind::Degraded,
+            ..Self::new(source, message)
+        }
+    }
+
+    /// The message phrased as a failure, e.g. `cannot read repository
+    /// '...': reason`
+    pub fn as_failure(&self) -> String {
+        match self.message.strip_prefix("Skipping ") {
+            Some(rest) => format!("cannot read {}", rest),
+            None => self.message.clone(),
+        }
+    }
 }
 
 /// Summary statistics for a chronicle
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct ChronicleStats {
     /// Number of repositories with activity
     pub repo_count: usize,
     /// Total number of commits
     pub commit_count: usize,
+    /// Unique files changed per repository, summed across repositories
+    pub files_changed: usize,
     /// Number of new branches
     pub new_branch_count: usize,
     /// Number of new TODOs
     pub todos_new: usize,
     /// Number of completed TODOs
     pub todos_completed: usize,
+    /// Open TODOs in the TODO files, changed or not
+    pub todos_open: usize,
+    /// Done TODOs in the TODO files, changed or not
+    pub todos_done: usize,
     /// Number of note updates
     pub notes_count: usize,
+    /// Total words in updated notes
+    pub words_written: usize,
+    /// Lines added across all repositories
+    pub lines_added: usize,
+    /// Lines removed across all repositories
+    pub lines_removed: usize,
+}
+
+impl ChronicleStats {
+    /// Labelled counts, in Summary table order
+    pub fn rows(&self) -> [(&'static str, usize); 10] {
+        [
+            ("Repositories", self.repo_count),
+            ("Commits", self.commit_count),
+            ("Files Changed", self.files_changed),
+            ("New Branches", self.new_branch_count),
+            ("New TODOs", self.todos_new),
+            ("Completed TODOs", self.todos_completed),
+            ("Open TODOs", self.todos_open),
+            ("Done TODOs", self.todos_done),
+            ("Note Updates", self.notes_count),
+            ("Words Written", self.words_written),
+        ]
+    }
+
+    /// Add a later chronicle's stats to these
+    ///
+    /// Counts are summed; the open and done TODO totals are standing counts,
+    /// so the later ones replace them. The same repositories are often active
+    /// on both days, so only the larger repository count is kept: the number
+    /// of distinct repositories needs their paths.
+    pub fn add(&mut self, later: &ChronicleStats) {
+        self.repo_count = self.repo_count.max(later.repo_count);
+        self.commit_count += later.commit_count;
+        self.files_changed += later.files_changed;
+        self.new_branch_count += later.new_branch_count;
+        self.todos_new += later.todos_new;
+        self.todos_completed += later.todos_completed;
+        self.todos_open = later.todos_open;
+        self.todos_done = later.todos_done;
+        self.notes_count += later.notes_count;
+        self.words_written += later.words_written;
+        self.lines_added += later.lines_added;
+        self.lines_removed += later.lines_removed;
+    }
+
+    /// Rebuild stats from labelled counts (missing labels count as zero)
+    pub fn from_rows(rows: &HashMap<String, usize>) -> Self {
+        let count = |label: &str| rows.get(label).copied().unwrap_or(0);
+
+        Self {
+            repo_count: count("Repositories"),
+            commit_count: count("Commits"),
+            files_changed: count("Files Changed"),
+            new_branch_count: count("New Branches"),
+            todos_new: count("New TODOs"),
+            todos_completed: count("Completed TODOs"),
+            todos_open: count("Open TODOs"),
+            todos_done: count("Done TODOs"),
+            notes_count: count("Note Updates"),
+            words_written: count("Words Written"),
+            // Rendered as one "+added / -removed" row that isn't a count
+            lines_added: 0,
+            lines_removed: 0,
+        }
+    }
 }
 
 impl Chronicle {
-    /// Compute summary statistics from the chronicle data
-    pub fn stats(&self) -> ChronicleStats {
+    /// Compute summary statistics from the chronicle data, counting TODOs
+    /// that moved into one of the `completed` statuses as completed
+    pub fn stats(&self, completed: &[TodoStatus]) -> ChronicleStats {
         let repo_count = self.repositories.len();
         let commit_count = self.repositories.iter().map(|r| r.commit_count()).sum();
+        let files_changed = self.repositories.iter().map(|r| r.files_changed()).sum();
         let new_branch_count = self.repositories.iter().map(|r| r.new_branch_count()).sum();
 
         let todos_new = self
@@ -50,23 +196,118 @@ impl Chronicle {
             .filter(|t| t.change == ChangeKind::New)
             .count();
 
-        let todos_completed = self.todos.iter().filter(|t| t.was_completed()).count();
+        let todos_completed = self
+            .todos
+            .iter()
+            .filter(|t| t.was_completed(completed))
+            .count();
 
         let notes_count = self.notes.len();
+        let words_written = self.notes.iter().map(|n| n.word_count).sum();
+
+        let lines_added = self.repositories.iter().map(|r| r.lines_added).sum();
+        let lines_removed = self.repositories.iter().map(|r| r.lines_removed).sum();
 
         ChronicleStats {
             repo_count,
             commit_count,
+            files_changed,
             new_branch_count,
             todos_new,
             todos_completed,
+            todos_open: self.todo_totals.open,
+            todos_done: self.todo_totals.done,
             notes_count,
+            words_written,
+            lines_added,
+            lines_removed,
         }
     }
 
+    /// Keep at most `max` items (commits, TODOs, and notes), returning how
+    /// many were dropped
+    ///
+    /// New items are kept before modified (or removed) ones and those before
+    /// unchanged; within each kind, recent items are kept before older ones.
+    /// TODOs carry no timestamp and rank after timestamped items of the same
+    /// kind.
+    /// Branches and repositories left without commits are removed.
+    pub fn truncate(&mut self, max: usize) -> usize {
+        #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+        enum Item {
+            Commit(usize, usize, usize),
+            Todo(usize),
+            Note(usize),
+        }
+
+        let rank = |change: ChangeKind| match change {
+            ChangeKind::New => 0,
+            ChangeKind::Modified | ChangeKind::Removed => 1,
+            ChangeKind::Unchanged => 2,
+        };
+
+        let mut items = Vec::new();
+        for (r, repo) in self.repositories.iter().enumerate() {
+            for (b, branch) in repo.branches.iter().enumerate() {
+                for (c, commit) in branch.commits.iter().enumerate() {
+                    items.push((0, Some(commit.timestamp), Item::Commit(r, b, c)));
+                }
+            }
+        }
+        for (i, todo) in self.todos.iter().enumerate() {
+            items.push((rank(todo.change), None, Item::Todo(i)));
+        }
+        for (i, note) in self.notes.iter().enumerate() {
+            items.push((rank(note.change), Some(note.modified_at), Item::Note(i)));
+        }
+
+        if items.len() <= max {
+            return 0;
+        }
+
+        items.sort_by_key(|(rank, timestamp, _)| (*rank, Reverse(*timestamp)));
+        let dropped: HashSet<Item> = items
+            .split_off(max)
+            .into_iter()
+            .map(|(_, _, item)| item)
+            .collect();
+
+        for (r, repo) in self.repositories.iter_mut().enumerate() {
+            for (b, branch) in repo.branches.iter_mut().enumerate() {
+                let mut c = 0;
+                branch.commits.retain(|_| {
+                    c += 1;
+                    !dropped.contains(&Item::Commit(r, b, c - 1))
+                });
+            }
+            repo.branches.retain(|branch| !branch.commits.is_empty());
+            repo.tally_lines();
+        }
+        self.repositories
+            .retain(|repo| !repo.branches.is_empty() || repo.wip.is_some());
+
+        let mut i = 0;
+        self.todos.retain(|_| {
+            i += 1;
+            !dropped.contains(&Item::Todo(i - 1))
+        });
+
+        let mut i = 0;
+        self.notes.retain(|_| {
+            i += 1;
+            !dropped.contains(&Item::Note(i - 1))
+        });
+
+        dropped.len()
+    }
+
     /// Check if there's any activity in this chronicle
     pub fn has_activity(&self) -> bool {
-        !self.repositories.is_empty() || !self.todos.is_empty() || !self.notes.is_empty()
+        !self.repositories.is_empty()
+            || self.todos.iter().any(|t| t.change != ChangeKind::Unchanged)
+            || !self.notes.is_empty()
+            || self.notes_omitted > 0
+            || !self.events.is_empty()
     }
 }
 
@@ -75,26 +316,95 @@ mod tests {
     use super::*;
     use std::path::PathBuf;
 
-    use crate::models::source::{Branch, Commit, TodoStatus};
+    use crate::models::source::{Branch, Commit};
+
+    #[test]
+    fn test_collection_warning_kinds() {
+        let skipped =
+            CollectionWarning::new("git", "Skipping repository 'app': not found".to_string());
+        assert_eq!(skipped.kind, WarningKind::Skipped);
+        assert_eq!(
+            skipped.as_failure(),
+            "cannot read repository 'app': not found"
+        );
+
+        let degraded = CollectionWarning::degraded("git", "Base branch main not found".to_string());
+        assert_eq!(degraded.kind, WarningKind::Degraded);
+
+        // Warnings stored before kinds existed were all skips
+        let parsed: CollectionWarning =
+            serde_json::from_str(r#"{"source":"notes","message":"Skipping notes directory"}"#)
+                .unwrap();
+        assert_eq!(parsed.kind, WarningKind::Skipped);
+    }
 
     #[test]
     fn test_chronicle_stats_empty() {
         let chronicle = Chronicle {
             date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
             since: Utc::now(),
+            until: None,
             generated_at: Utc::now(),
+            host: None,
             repositories: vec![],
             todos: vec![],
+            todo_totals: TodoTotals::default(),
             notes: vec![],
+            notes_omitted: 0,
+            events: vec![],
+            warnings: vec![],
         };
 
-        let stats = chronicle.stats();
+        let stats = chronicle.stats(&[TodoStatus::Done]);
         assert_eq!(stats.repo_count, 0);
         assert_eq!(stats.commit_count, 0);
+        assert_eq!(stats.files_changed, 0);
         assert_eq!(stats.new_branch_count, 0);
         assert_eq!(stats.todos_new, 0);
         assert_eq!(stats.todos_completed, 0);
         assert_eq!(stats.notes_count, 0);
+        assert_eq!(stats.words_written, 0);
+    }
+
+    #[test]
+    fn test_chronicle_stats_rows_roundtrip() {
+        let stats = ChronicleStats {
+            repo_count: 1,
+            commit_count: 2,
+            files_changed: 8,
+            new_branch_count: 3,
+            todos_new: 4,
+            todos_completed: 5,
+            todos_open: 9,
+            todos_done: 10,
+            notes_count: 6,
+            words_written: 7,
+            lines_added: 0,
+            lines_removed: 0,
+        };
+
+        let rows: HashMap<String, usize> = stats
+            .rows()
+            .iter()
+            .map(|(label, count)| (label.to_string(), *count))
+            .collect();
+
+        assert_eq!(ChronicleStats::from_rows(&rows), stats);
+        assert_eq!(ChronicleStats::from_rows(&HashMap::new()).commit_count, 0);
+    }
+
+    #[test]
+    fn test_chronicle_stats_add() {
+        let day = |repo_count, commit_count, todos_open| ChronicleStats {
+            repo_count,
+            commit_count,
+            todos_open,
+            ..ChronicleStats::default()
+        };
+
+        let mut stats = day(2, 5, 10);
+        stats.add(&day(1, 3, 8));
+        assert_eq!(stats, day(2, 8, 8));
     }
 
     #[test]
@@ -102,32 +412,47 @@ mod tests {
         let chronicle = Chronicle {
             date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
             since: Utc::now(),
+            until: None,
             generated_at: Utc::now(),
+            host: None,
             repositories: vec![
                 Repository {
                     path: PathBuf::from("/test/repo1"),
                     name: "repo1".to_string(),
+                    description: None,
                     default_branch: "main".to_string(),
+                    current_branch: "main".to_string(),
                     branches: vec![
                         Branch {
                             name: "main".to_string(),
                             change: ChangeKind::Modified,
                             ahead: 0,
                             behind: 0,
+                            first_seen: None,
                             commits: vec![
                                 Commit {
                                     hash: "abc1234".to_string(),
                                     message: "Commit 1".to_string(),
+                                    body: None,
                                     author: "Author".to_string(),
                                     timestamp: Utc::now(),
                                     files: vec![],
+                                    binary_files: vec![],
+                                    additions: 0,
+                                    deletions: 0,
+                                    signed: false,
                                 },
                                 Commit {
                                     hash: "def5678".to_string(),
                                     message: "Commit 2".to_string(),
+                                    body: None,
                                     author: "Author".to_string(),
                                     timestamp: Utc::now(),
                                     files: vec![],
+                                    binary_files: vec![],
+                                    additions: 0,
+                                    deletions: 0,
+                                    signed: false,
                                 },
                             ],
                         },
@@ -136,33 +461,53 @@ mod tests {
                             change: ChangeKind::New,
                             ahead: 1,
                             behind: 0,
+                            first_seen: None,
                             commits: vec![Commit {
                                 hash: "ghi9012".to_string(),
                                 message: "Feature".to_string(),
+                                body: None,
                                 author: "Author".to_string(),
                                 timestamp: Utc::now(),
                                 files: vec![],
+                                binary_files: vec![],
+                                additions: 0,
+                                deletions: 0,
+                                signed: false,
                             }],
                         },
                     ],
+                    wip: None,
+                    lines_added: 0,
+                    lines_removed: 0,
                 },
                 Repository {
                     path: PathBuf::from("/test/repo2"),
                     name: "repo2".to_string(),
+                    description: None,
                     default_branch: "main".to_string(),
+                    current_branch: "main".to_string(),
                     branches: vec![Branch {
                         name: "main".to_string(),
                         change: ChangeKind::Modified,
                         ahead: 0,
                         behind: 0,
+                        first_seen: None,
                         commits: vec![Commit {
                             hash: "jkl3456".to_string(),
                             message: "Another commit".to_string(),
+                            body: None,
                             author: "Author".to_string(),
                             timestamp: Utc::now(),
-                            files: vec![],
+                            files: vec![PathBuf::from("src/lib.rs"), PathBuf::from("README.md")],
+                            binary_files: vec![],
+                            additions: 0,
+                            deletions: 0,
+                            signed: false,
                         }],
                     }],
+                    wip: None,
+                    lines_added: 0,
+                    lines_removed: 0,
                 },
             ],
             todos: vec![
@@ -171,49 +516,79 @@ mod tests {
                     status: TodoStatus::Pending,
                     change: ChangeKind::New,
                     previous_status: None,
+                    previous_content: None,
                     file: PathBuf::from("todo.txt"),
                     line: 1,
+                    depth: 0,
+                    tags: vec![],
+                    priority: None,
+                    deadline: None,
+                    also_in: vec![],
                 },
                 Todo {
                     content: "Completed task".to_string(),
                     status: TodoStatus::Done,
                     change: ChangeKind::Modified,
                     previous_status: Some(TodoStatus::Pending),
+                    previous_content: None,
                     file: PathBuf::from("todo.txt"),
                     line: 2,
+                    depth: 0,
+                    tags: vec![],
+                    priority: None,
+                    deadline: None,
+                    also_in: vec![],
                 },
                 Todo {
                     content: "Existing task".to_string(),
                     status: TodoStatus::Pending,
                     change: ChangeKind::Unchanged,
                     previous_status: Some(TodoStatus::Pending),
+                    previous_content: None,
                     file: PathBuf::from("todo.txt"),
                     line: 3,
+                    depth: 0,
+                    tags: vec![],
+                    priority: None,
+                    deadline: None,
+                    also_in: vec![],
                 },
             ],
+            todo_totals: TodoTotals::default(),
             notes: vec![
                 Note {
                     path: PathBuf::from("note1.md"),
                     change: ChangeKind::New,
+                    renamed_from: None,
                     modified_at: Utc::now(),
                     excerpt: "New note".to_string(),
+                    word_count: 2,
+                    char_count: 8,
                 },
                 Note {
                     path: PathBuf::from("note2.md"),
                     change: ChangeKind::Modified,
+                    renamed_from: None,
                     modified_at: Utc::now(),
                     excerpt: "Modified note".to_string(),
+                    word_count: 2,
+                    char_count: 13,
                 },
             ],
+            notes_omitted: 0,
+            events: vec![],
+            warnings: vec![],
         };
 
-        let stats = chronicle.stats();
+        let stats = chronicle.stats(&[TodoStatus::Done]);
         assert_eq!(stats.repo_count, 2);
         assert_eq!(stats.commit_count, 4);
+        assert_eq!(stats.files_changed, 2);
         assert_eq!(stats.new_branch_count, 1);
         assert_eq!(stats.todos_new, 1);
         assert_eq!(stats.todos_completed, 1);
         assert_eq!(stats.notes_count, 2);
+        assert_eq!(stats.words_written, 4);
     }
 
     #[test]
@@ -221,43 +596,176 @@ mod tests {
         let empty_chronicle = Chronicle {
             date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
             since: Utc::now(),
+            until: None,
             generated_at: Utc::now(),
+            host: None,
             repositories: vec![],
             todos: vec![],
+            todo_totals: TodoTotals::default(),
             notes: vec![],
+            notes_omitted: 0,
+            events: vec![],
+            warnings: vec![],
         };
         assert!(!empty_chronicle.has_activity());
 
         let chronicle_with_repos = Chronicle {
             date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
             since: Utc::now(),
+            until: None,
             generated_at: Utc::now(),
+            host: None,
             repositories: vec![Repository {
                 path: PathBuf::from("/test/repo"),
                 name: "test".to_string(),
+                description: None,
                 default_branch: "main".to_string(),
+                current_branch: "main".to_string(),
                 branches: vec![],
+                wip: None,
+                lines_added: 0,
+                lines_removed: 0,
             }],
             todos: vec![],
+            todo_totals: TodoTotals::default(),
             notes: vec![],
+            notes_omitted: 0,
+            events: vec![],
+            warnings: vec![],
         };
         assert!(chronicle_with_repos.has_activity());
 
         let chronicle_with_todos = Chronicle {
             date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
             since: Utc::now(),
+            until: None,
             generated_at: Utc::now(),
+            host: None,
             repositories: vec![],
             todos: vec![Todo {
                 content: "Task".to_string(),
                 status: TodoStatus::Pending,
                 change: ChangeKind::New,
                 previous_status: None,
+                previous_content: None,
                 file: PathBuf::from("todo.txt"),
                 line: 1,
+                depth: 0,
+                tags: vec![],
+                priority: None,
+                deadline: None,
+                also_in: vec![],
             }],
+            todo_totals: TodoTotals::default(),
             notes: vec![],
+            notes_omitted: 0,
+            events: vec![],
+            warnings: vec![],
         };
         assert!(chronicle_with_todos.has_activity());
+
+        // Unchanged TODOs listed by show_unchanged_todos are no activity
+        let mut chronicle_with_unchanged_todos = chronicle_with_todos;
+        chronicle_with_unchanged_todos.todos[0].change = ChangeKind::Unchanged;
+        assert!(!chronicle_with_unchanged_todos.has_activity());
+    }
+
+    #[test]
+    fn test_chronicle_truncate_keeps_new_and_recent_items() {
+        let now = Utc::now();
+        let hours_ago = |hours: i64| now - chrono::Duration::hours(hours);
+
+        let commit = |hash: &str, timestamp| Commit {
+            hash: hash.to_string(),
+            message: "Commit".to_string(),
+            body: None,
+            author: "Author".to_string(),
+            timestamp,
+            files: vec![],
+            binary_files: vec![],
+            additions: 0,
+            deletions: 0,
+            signed: false,
+        };
+        let repo = |name: &str, commits| Repository {
+            path: PathBuf::from(name),
+            name: name.to_string(),
+            description: None,
+            default_branch: "main".to_string(),
+            current_branch: "main".to_string(),
+            branches: vec![Branch {
+                name: "main".to_string(),
+                change: ChangeKind::Modified,
+                ahead: 0,
+                behind: 0,
+                first_seen: None,
+                commits,
+            }],
+            wip: None,
+            lines_added: 0,
+            lines_removed: 0,
+        };
+        let todo = |content: &str, change| Todo {
+            content: content.to_string(),
+            status: TodoStatus::Pending,
+            change,
+            previous_status: None,
+            previous_content: None,
+            file: PathBuf::from("todo.txt"),
+            line: 1,
+            depth: 0,
+            tags: vec![],
+            priority: None,
+            deadline: None,
+            also_in: vec![],
+        };
+        let note = |path: &str, change, modified_at| Note {
+            path: PathBuf::from(path),
+            change,
+            renamed_from: None,
+            modified_at,
+            excerpt: String::new(),
+            word_count: 0,
+            char_count: 0,
+        };
+
+        let mut chronicle = Chronicle {
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            since: hours_ago(24),
+            until: None,
+            generated_at: now,
+            host: None,
+            repositories: vec![
+                repo(
+                    "repo1",
+                    vec![
+                        commit("aaa1111", hours_ago(1)),
+                        commit("bbb2222", hours_ago(2)),
+                    ],
+                ),
+                repo("repo2", vec![commit("ccc3333", hours_ago(5))]),
+            ],
+            todos: vec![
+                todo("Unchanged task", ChangeKind::Unchanged),
+                todo("New task", ChangeKind::New),
+            ],
+            todo_totals: TodoTotals::default(),
+            notes: vec![
+                note("modified.md", ChangeKind::Modified, now),
+                note("new.md", ChangeKind::New, hours_ago(3)),
+            ],
+            notes_omitted: 0,
+            events: vec![],
+            warnings: vec![],
+        };
+
+        assert_eq!(chronicle.clone().truncate(7), 0);
+
+        assert_eq!(chronicle.truncate(3), 4);
+        assert_eq!(chronicle.repositories.len(), 1);
+        assert_eq!(chronicle.repositories[0].commit_count(), 2);
+        assert!(chronicle.todos.is_empty());
+        assert_eq!(chronicle.notes.len(), 1);
+        assert_eq!(chronicle.notes[0].path, PathBuf::from("new.md"));
     }
 }
@@ -1,10 +1,14 @@
 //! Data models module
 //!
 //! Defines domain models for Git, TODO, Notes, and Chronicle.
-//! Includes ChangeKind, Commit, Branch, Repository, Todo, Note, Chronicle.
+//! Includes ChangeKind, Commit, Branch, Repository, Todo, Note, Event,
+//! Chronicle, CollectionWarning.
 
 pub mod chronicle;
 pub mod source;
 
-pub use chronicle::Chronicle;
-pub use source::{Branch, ChangeKind, Commit, Note, Repository, Todo, TodoStatus};
+pub use chronicle::{Chronicle, ChronicleStats, CollectionWarning, WarningKind};
+pub use source::{
+    Branch, ChangeKind, Commit, Event, Note, Repository, Todo, TodoLocation, TodoStatus,
+    TodoTotals, TodoTransition, WorkInProgress,
+};
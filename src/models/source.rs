@@ -1,13 +1,15 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-/// Indicates whether an item is new, modified, or unchanged
+/// Indicates whether an item is new, modified, unchanged, or removed
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ChangeKind {
     New,
     Modified,
     Unchanged,
+    /// Gone since the last run (notes only)
+    Removed,
 }
 
 // ============================================================================
@@ -19,14 +21,29 @@ pub enum ChangeKind {
 pub struct Commit {
     /// Short commit hash (7 characters)
     pub hash: String,
-    /// Commit message (first line, max 72 chars)
+    /// Commit subject (first line, in full)
     pub message: String,
+    /// Rest of the commit message after the subject, if any
+    #[serde(default)]
+    pub body: Option<String>,
     /// Commit author name
     pub author: String,
     /// Commit timestamp
     pub timestamp: DateTime<Utc>,
-    /// List of files changed in this commit
+    /// List of text files changed in this commit
     pub files: Vec<PathBuf>,
+    /// Binary files (images, PDFs, ...) changed in this commit
+    #[serde(default)]
+    pub binary_files: Vec<PathBuf>,
+    /// Lines added in text files
+    #[serde(default)]
+    pub additions: usize,
+    /// Lines removed in text files
+    #[serde(default)]
+    pub deletions: usize,
+    /// Whether the commit carries a signature (not verified)
+    #[serde(default)]
+    pub signed: bool,
 }
 
 /// A Git branch with its commits
@@ -40,10 +57,21 @@ pub struct Branch {
     pub ahead: usize,
     /// Commits behind default branch
     pub behind: usize,
+    /// When chronicle first saw this branch, if known
+    #[serde(default)]
+    pub first_seen: Option<DateTime<Utc>>,
     /// List of commits on this branch
     pub commits: Vec<Commit>,
 }
 
+impl Branch {
+    /// Whole days since the branch was first seen, if known
+    pub fn age_days(&self, now: DateTime<Utc>) -> Option<i64> {
+        self.first_seen
+            .map(|first_seen| (now - first_seen).num_days().max(0))
+    }
+}
+
 /// A Git repository with its branches
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Repository {
@@ -51,10 +79,43 @@ pub struct Repository {
     pub path: PathBuf,
     /// Repository name (derived from path)
     pub name: String,
-    /// Default branch name (usually "main" or "master")
+    /// One-line description from `.git/description` or the README's title
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Default branch, the base for ahead/behind counts: where
+    /// `origin/HEAD` points, else the current branch
     pub default_branch: String,
+    /// Branch checked out at HEAD, "(detached HEAD)" when on none
+    #[serde(default)]
+    pub current_branch: String,
     /// All branches with commits
     pub branches: Vec<Branch>,
+    /// Uncommitted changes, reported when no branch has new commits
+    pub wip: Option<WorkInProgress>,
+    /// Lines added by the repository's commits
+    #[serde(default)]
+    pub lines_added: usize,
+    /// Lines removed by the repository's commits
+    #[serde(default)]
+    pub lines_removed: usize,
+}
+
+/// Uncommitted changes in a repository's working tree and index
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkInProgress {
+    /// Files with modified, renamed, or retyped content
+    pub modified: usize,
+    /// New files, staged or untracked
+    pub added: usize,
+    /// Deleted files
+    pub deleted: usize,
+}
+
+impl WorkInProgress {
+    /// Whether there are no uncommitted changes
+    pub fn is_empty(&self) -> bool {
+        self.modified + self.added + self.deleted == 0
+    }
 }
 
 impl Repository {
@@ -64,12 +125,11 @@ impl Repository {
     }
 
     /// Total number of unique files changed across all commits
-    #[allow(dead_code)]
     pub fn files_changed(&self) -> usize {
         let mut files = std::collections::HashSet::new();
         for branch in &self.branches {
             for commit in &branch.commits {
-                for file in &commit.files {
+                for file in commit.files.iter().chain(&commit.binary_files) {
                     files.insert(file);
                 }
             }
@@ -77,6 +137,21 @@ impl Repository {
         files.len()
     }
 
+    /// Recount `lines_added` and `lines_removed` from the commits, counting
+    /// a commit on several branches once
+    pub fn tally_lines(&mut self) {
+        let mut seen = std::collections::HashSet::new();
+        let (mut added, mut removed) = (0, 0);
+        for commit in self.branches.iter().flat_map(|b| &b.commits) {
+            if seen.insert(&commit.hash) {
+                added += commit.additions;
+                removed += commit.deletions;
+            }
+        }
+        self.lines_added = added;
+        self.lines_removed = removed;
+    }
+
     /// Number of new branches
     pub fn new_branch_count(&self) -> usize {
         self.branches
@@ -91,7 +166,7 @@ impl Repository {
 // ============================================================================
 
 /// Status of a TODO item
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TodoStatus {
     /// Pending: - [ ]
     Pending,
@@ -112,18 +187,108 @@ pub struct Todo {
     pub change: ChangeKind,
     /// Previous status (for change detection)
     pub previous_status: Option<TodoStatus>,
+    /// Text before an edit that left the status alone
+    #[serde(default)]
+    pub previous_content: Option<String>,
+    /// Source file path
+    pub file: PathBuf,
+    /// Line number in file
+    pub line: usize,
+    /// Nesting level under parent TODOs (0 = top level)
+    pub depth: usize,
+    /// `#tag` labels, stripped from `content` (without the `#`)
+    pub tags: Vec<String>,
+    /// Priority letter from a todo.txt `(A)` or org `[#A]` marker, `A` being
+    /// the highest
+    #[serde(default)]
+    pub priority: Option<char>,
+    /// Due date from an org `DEADLINE:`
+    #[serde(default)]
+    pub deadline: Option<NaiveDate>,
+    /// Other places the same TODO appears (collapsed by `dedupe_todos`)
+    #[serde(default)]
+    pub also_in: Vec<TodoLocation>,
+}
+
+/// Where a TODO item was found
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TodoLocation {
     /// Source file path
     pub file: PathBuf,
     /// Line number in file
     pub line: usize,
 }
 
+/// A status change of a TODO item between two runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TodoTransition {
+    /// Pending → In Progress
+    Started,
+    /// Pending or In Progress → Done
+    Completed,
+    /// Done → Pending or In Progress
+    Reopened,
+    /// In Progress → Pending
+    Paused,
+}
+
+impl TodoTransition {
+    /// Classify the change from `previous` to `current` (None if unchanged)
+    pub fn between(previous: TodoStatus, current: TodoStatus) -> Option<Self> {
+        use TodoStatus::*;
+
+        match (previous, current) {
+            (Pending, InProgress) => Some(Self::Started),
+            (Pending, Done) | (InProgress, Done) => Some(Self::Completed),
+            (Done, Pending) | (Done, InProgress) => Some(Self::Reopened),
+            (InProgress, Pending) => Some(Self::Paused),
+            (Pending, Pending) | (InProgress, InProgress) | (Done, Done) => None,
+        }
+    }
+}
+
+/// Open and done TODOs across all TODO files, changed or not
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TodoTotals {
+    /// In a status that isn't one of the completed statuses
+    pub open: usize,
+    /// In one of the completed statuses
+    pub done: usize,
+}
+
+impl TodoTotals {
+    /// Count `todos` into these totals, as done when in one of the
+    /// `completed` statuses
+    pub fn add(&mut self, todos: &[Todo], completed: &[TodoStatus]) {
+        for todo in todos {
+            if completed.contains(&todo.status) {
+                self.done += 1;
+            } else {
+                self.open += 1;
+            }
+        }
+    }
+}
+
 impl Todo {
-    /// Check if this TODO was just completed
-    pub fn was_completed(&self) -> bool {
-        self.status == TodoStatus::Done
-            && self.previous_status.is_some()
-            && self.previous_status != Some(TodoStatus::Done)
+    /// Status transition since the previous run, if the status changed
+    pub fn transition(&self) -> Option<TodoTransition> {
+        self.previous_status
+            .and_then(|previous| TodoTransition::between(previous, self.status))
+    }
+
+    /// Check if this TODO was just completed, i.e. moved into one of the
+    /// `completed` statuses from another one
+    pub fn was_completed(&self, completed: &[TodoStatus]) -> bool {
+        completed.contains(&self.status)
+            && self
+                .previous_status
+                .is_some_and(|previous| !completed.contains(&previous))
+    }
+
+    /// Check if only this TODO's text changed since the previous run
+    pub fn was_edited(&self) -> bool {
+        self.transition().is_none() && self.previous_content.is_some()
     }
 }
 
@@ -136,12 +301,35 @@ impl Todo {
 pub struct Note {
     /// Path to the note file
     pub path: PathBuf,
-    /// Whether this note is new or modified
+    /// Whether this note is new, modified, or removed
     pub change: ChangeKind,
-    /// Last modified timestamp
+    /// Previous path, if the note was renamed or moved since the last run
+    pub renamed_from: Option<PathBuf>,
+    /// Last modified timestamp (as last seen, for removed notes)
     pub modified_at: DateTime<Utc>,
     /// Excerpt from the note (respects max_chars_per_item limit)
     pub excerpt: String,
+    /// Words in the whole note
+    pub word_count: usize,
+    /// Characters in the whole note
+    pub char_count: usize,
+}
+
+/// A calendar event from an ICS file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Event {
+    /// ICS file the event was read from
+    pub file: PathBuf,
+    /// Event title
+    pub summary: String,
+    /// When the event starts (midnight for all-day events)
+    pub start: DateTime<Utc>,
+    /// When the event ends, if given
+    pub end: Option<DateTime<Utc>>,
+    /// Whether the event spans whole days rather than a time range
+    pub all_day: bool,
+    /// Where the event takes place, if given
+    pub location: Option<String>,
 }
 
 #[cfg(test)]
@@ -153,27 +341,40 @@ mod tests {
         let repo = Repository {
             path: PathBuf::from("/test/repo"),
             name: "test-repo".to_string(),
+            description: None,
             default_branch: "main".to_string(),
+            current_branch: "main".to_string(),
             branches: vec![
                 Branch {
                     name: "main".to_string(),
                     change: ChangeKind::Modified,
                     ahead: 0,
                     behind: 0,
+                    first_seen: None,
                     commits: vec![
                         Commit {
                             hash: "abc1234".to_string(),
                             message: "First commit".to_string(),
+                            body: None,
                             author: "Test Author".to_string(),
                             timestamp: Utc::now(),
                             files: vec![],
+                            binary_files: vec![],
+                            additions: 0,
+                            deletions: 0,
+                            signed: false,
                         },
                         Commit {
                             hash: "def5678".to_string(),
                             message: "Second commit".to_string(),
+                            body: None,
                             author: "Test Author".to_string(),
                             timestamp: Utc::now(),
                             files: vec![],
+                            binary_files: vec![],
+                            additions: 0,
+                            deletions: 0,
+                            signed: false,
                         },
                     ],
                 },
@@ -182,15 +383,24 @@ mod tests {
                     change: ChangeKind::New,
                     ahead: 1,
                     behind: 0,
+                    first_seen: None,
                     commits: vec![Commit {
                         hash: "ghi9012".to_string(),
                         message: "Feature commit".to_string(),
+                        body: None,
                         author: "Test Author".to_string(),
                         timestamp: Utc::now(),
                         files: vec![],
+                        binary_files: vec![],
+                        additions: 0,
+                        deletions: 0,
+                        signed: false,
                     }],
                 },
             ],
+            wip: None,
+            lines_added: 0,
+            lines_removed: 0,
         };
 
         assert_eq!(repo.commit_count(), 3);
@@ -201,13 +411,16 @@ mod tests {
         let repo = Repository {
             path: PathBuf::from("/test/repo"),
             name: "test-repo".to_string(),
+            description: None,
             default_branch: "main".to_string(),
+            current_branch: "main".to_string(),
             branches: vec![
                 Branch {
                     name: "main".to_string(),
                     change: ChangeKind::Modified,
                     ahead: 0,
                     behind: 0,
+                    first_seen: None,
                     commits: vec![],
                 },
                 Branch {
@@ -215,6 +428,7 @@ mod tests {
                     change: ChangeKind::New,
                     ahead: 1,
                     behind: 0,
+                    first_seen: None,
                     commits: vec![],
                 },
                 Branch {
@@ -222,9 +436,13 @@ mod tests {
                     change: ChangeKind::New,
                     ahead: 2,
                     behind: 0,
+                    first_seen: None,
                     commits: vec![],
                 },
             ],
+            wip: None,
+            lines_added: 0,
+            lines_removed: 0,
         };
 
         assert_eq!(repo.new_branch_count(), 2);
@@ -235,29 +453,45 @@ mod tests {
         let repo = Repository {
             path: PathBuf::from("/test/repo"),
             name: "test-repo".to_string(),
+            description: None,
             default_branch: "main".to_string(),
+            current_branch: "main".to_string(),
             branches: vec![Branch {
                 name: "main".to_string(),
                 change: ChangeKind::Modified,
                 ahead: 0,
                 behind: 0,
+                first_seen: None,
                 commits: vec![
                     Commit {
                         hash: "abc1234".to_string(),
                         message: "First commit".to_string(),
+                        body: None,
                         author: "Test Author".to_string(),
                         timestamp: Utc::now(),
                         files: vec![PathBuf::from("file1.rs"), PathBuf::from("file2.rs")],
+                        binary_files: vec![],
+                        additions: 0,
+                        deletions: 0,
+                        signed: false,
                     },
                     Commit {
                         hash: "def5678".to_string(),
                         message: "Second commit".to_string(),
+                        body: None,
                         author: "Test Author".to_string(),
                         timestamp: Utc::now(),
                         files: vec![PathBuf::from("file2.rs"), PathBuf::from("file3.rs")],
+                        binary_files: vec![],
+                        additions: 0,
+                        deletions: 0,
+                        signed: false,
                     },
                 ],
             }],
+            wip: None,
+            lines_added: 0,
+            lines_removed: 0,
         };
 
         assert_eq!(repo.files_changed(), 3);
@@ -270,29 +504,147 @@ mod tests {
             status: TodoStatus::Done,
             change: ChangeKind::Modified,
             previous_status: Some(TodoStatus::Pending),
+            previous_content: None,
             file: PathBuf::from("todo.txt"),
             line: 1,
+            depth: 0,
+            tags: vec![],
+            priority: None,
+            deadline: None,
+            also_in: vec![],
         };
-        assert!(completed_todo.was_completed());
+        assert!(completed_todo.was_completed(&[TodoStatus::Done]));
 
         let already_done_todo = Todo {
             content: "Test task".to_string(),
             status: TodoStatus::Done,
             change: ChangeKind::Unchanged,
             previous_status: Some(TodoStatus::Done),
+            previous_content: None,
             file: PathBuf::from("todo.txt"),
             line: 1,
+            depth: 0,
+            tags: vec![],
+            priority: None,
+            deadline: None,
+            also_in: vec![],
         };
-        assert!(!already_done_todo.was_completed());
+        assert!(!already_done_todo.was_completed(&[TodoStatus::Done]));
 
         let new_done_todo = Todo {
             content: "Test task".to_string(),
             status: TodoStatus::Done,
             change: ChangeKind::New,
             previous_status: None,
+            previous_content: None,
+            file: PathBuf::from("todo.txt"),
+            line: 1,
+            depth: 0,
+            tags: vec![],
+            priority: None,
+            deadline: None,
+            also_in: vec![],
+        };
+        assert!(!new_done_todo.was_completed(&[TodoStatus::Done]));
+
+        // Resolved in progress counts when configured
+        let started_todo = Todo {
+            status: TodoStatus::InProgress,
+            ..completed_todo.clone()
+        };
+        assert!(!started_todo.was_completed(&[TodoStatus::Done]));
+        assert!(started_todo.was_completed(&[TodoStatus::Done, TodoStatus::InProgress]));
+        assert!(!completed_todo.was_completed(&[TodoStatus::Done, TodoStatus::Pending]));
+    }
+
+    #[test]
+    fn test_todo_transition_between_all_pairs() {
+        use TodoStatus::*;
+
+        let cases = [
+            (Pending, Pending, None),
+            (Pending, InProgress, Some(TodoTransition::Started)),
+            (Pending, Done, Some(TodoTransition::Completed)),
+            (InProgress, Pending, Some(TodoTransition::Paused)),
+            (InProgress, InProgress, None),
+            (InProgress, Done, Some(TodoTransition::Completed)),
+            (Done, Pending, Some(TodoTransition::Reopened)),
+            (Done, InProgress, Some(TodoTransition::Reopened)),
+            (Done, Done, None),
+        ];
+
+        for (previous, current, expected) in cases {
+            assert_eq!(
+                TodoTransition::between(previous, current),
+                expected,
+                "{:?} -> {:?}",
+                previous,
+                current
+            );
+        }
+    }
+
+    #[test]
+    fn test_todo_transition_without_previous_status() {
+        let todo = Todo {
+            content: "Test task".to_string(),
+            status: TodoStatus::InProgress,
+            change: ChangeKind::New,
+            previous_status: None,
+            previous_content: None,
             file: PathBuf::from("todo.txt"),
             line: 1,
+            depth: 0,
+            tags: vec![],
+            priority: None,
+            deadline: None,
+            also_in: vec![],
+        };
+        assert_eq!(todo.transition(), None);
+    }
+
+    #[test]
+    fn test_repository_tally_lines_counts_shared_commits_once() {
+        let commit = |hash: &str, additions, deletions| Commit {
+            hash: hash.to_string(),
+            message: "Change".to_string(),
+            body: None,
+            author: "Test Author".to_string(),
+            timestamp: Utc::now(),
+            files: vec![],
+            binary_files: vec![],
+            additions,
+            deletions,
+            signed: false,
+        };
+        let branch = |name: &str, commits| Branch {
+            name: name.to_string(),
+            change: ChangeKind::Modified,
+            ahead: 0,
+            behind: 0,
+            first_seen: None,
+            commits,
         };
-        assert!(!new_done_todo.was_completed());
+        let mut repo = Repository {
+            path: PathBuf::from("/test/repo"),
+            name: "test-repo".to_string(),
+            description: None,
+            default_branch: "main".to_string(),
+            current_branch: "main".to_string(),
+            branches: vec![
+                branch("main", vec![commit("abc1234", 10, 2)]),
+                branch(
+                    "feature",
+                    vec![commit("def5678", 5, 5), commit("abc1234", 10, 2)],
+                ),
+            ],
+            wip: None,
+            lines_added: 0,
+            lines_removed: 0,
+        };
+
+        repo.tally_lines();
+
+        assert_eq!((repo.lines_added, repo.lines_removed), (15, 7));
     }
 }
@@ -0,0 +1,132 @@
+//! Data exports
+//!
+//! Serializes a chronicle as JSON, TOML, or YAML for other tools, all
+//! through the same serde representation.
+
+use crate::error::{ChronicleError, Result};
+use crate::models::Chronicle;
+
+/// The chronicle as pretty-printed JSON
+pub fn to_json(chronicle: &Chronicle) -> Result<String> {
+    Ok(serde_json::to_string_pretty(chronicle)?)
+}
+
+/// The chronicle as TOML
+pub fn to_toml(chronicle: &Chronicle) -> Result<String> {
+    toml::to_string_pretty(chronicle)
+        .map_err(|e| ChronicleError::Renderer(format!("Failed to serialize as TOML: {}", e)))
+}
+
+/// The chronicle as YAML
+pub fn to_yaml(chronicle: &Chronicle) -> Result<String> {
+    serde_yaml_ng::to_string(chronicle)
+        .map_err(|e| ChronicleError::Renderer(format!("Failed to serialize as YAML: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use std::path::PathBuf;
+
+    use crate::models::{ChangeKind, Note, Todo, TodoStatus, TodoTotals};
+
+    fn create_chronicle() -> Chronicle {
+        Chronicle {
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            since: Utc.with_ymd_and_hms(2024, 1, 14, 9, 0, 0).unwrap(),
+            until: None,
+            generated_at: Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap(),
+            host: Some("laptop".to_string()),
+            repositories: vec![],
+            todos: vec![Todo {
+                content: "Ship: \"v2\"".to_string(),
+                status: TodoStatus::Pending,
+                change: ChangeKind::New,
+                previous_status: None,
+                previous_content: None,
+                file: PathBuf::from("todo.md"),
+                line: 1,
+                depth: 0,
+                tags: vec!["release".to_string()],
+                priority: Some('A'),
+                deadline: None,
+                also_in: vec![],
+            }],
+            todo_totals: TodoTotals { open: 1, done: 0 },
+            notes: vec![Note {
+                path: PathBuf::from("notes/today.md"),
+                change: ChangeKind::Modified,
+                renamed_from: None,
+                modified_at: Utc.with_ymd_and_hms(2024, 1, 15, 8, 0, 0).unwrap(),
+                excerpt: "First line\nsecond line".to_string(),
+                word_count: 4,
+                char_count: 22,
+            }],
+            notes_omitted: 0,
+            events: vec![],
+            warnings: vec![],
+        }
+    }
+
+    #[test]
+    fn test_to_yaml() {
+        let yaml = to_yaml(&create_chronicle()).unwrap();
+
+        assert!(yaml.starts_with("date: 2024-01-15\n"));
+        assert!(yaml.contains("\nuntil: null\n"));
+        assert!(yaml.contains("\nrepositories: []\n"));
+        assert!(yaml.contains("\ntodo_totals:\n  open: 1\n  done: 0\n"));
+    }
+
+    #[test]
+    fn test_to_yaml_round_trips() {
+        let mut chronicle = create_chronicle();
+        // Text that plain YAML would read back as another type, or that
+        // needs escaping
+        chronicle.todos[0].tags = [
+            "true",
+            "null",
+            "~",
+            "123",
+            "1e3",
+            "- x",
+            "a: b",
+            "\u{85}\u{9f}",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+        chronicle.host = Some("yes".to_string());
+
+        let parsed: Chronicle = serde_yaml_ng::from_str(&to_yaml(&chronicle).unwrap()).unwrap();
+        assert_eq!(parsed.date, chronicle.date);
+        assert_eq!(parsed.since, chronicle.since);
+        assert_eq!(parsed.host, chronicle.host);
+        assert_eq!(parsed.todos[0].content, chronicle.todos[0].content);
+        assert_eq!(parsed.todos[0].tags, chronicle.todos[0].tags);
+        assert_eq!(parsed.todos[0].priority, chronicle.todos[0].priority);
+        assert_eq!(parsed.notes[0].excerpt, chronicle.notes[0].excerpt);
+    }
+
+    #[test]
+    fn test_to_toml_round_trips() {
+        let chronicle = create_chronicle();
+        let toml = to_toml(&chronicle).unwrap();
+        assert!(toml.contains("date = \"2024-01-15\""));
+
+        let parsed: Chronicle = toml::from_str(&toml).unwrap();
+        assert_eq!(parsed.date, chronicle.date);
+        assert_eq!(parsed.since, chronicle.since);
+        assert_eq!(parsed.todos[0].content, chronicle.todos[0].content);
+        assert_eq!(parsed.notes[0].excerpt, chronicle.notes[0].excerpt);
+    }
+
+    #[test]
+    fn test_to_json_round_trips() {
+        let chronicle = create_chronicle();
+        let parsed: Chronicle = serde_json::from_str(&to_json(&chronicle).unwrap()).unwrap();
+        assert_eq!(parsed.generated_at, chronicle.generated_at);
+        assert_eq!(parsed.host, chronicle.host);
+    }
+}
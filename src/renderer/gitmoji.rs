@@ -0,0 +1,151 @@
+//! Gitmoji shortcodes
+//!
+//! Converts `:shortcode:` tokens (as used by gitmoji.dev) to their emoji,
+//! the way GitHub shows them in commit messages.
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// `:shortcode:` tokens
+static SHORTCODE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r":([a-z0-9_+-]+):").unwrap());
+
+/// Known shortcodes: the gitmoji set plus a few common GitHub ones
+const EMOJI: &[(&str, &str)] = &[
+    ("+1", "👍"),
+    ("adhesive_bandage", "🩹"),
+    ("airplane", "✈️"),
+    ("alembic", "⚗️"),
+    ("alien", "👽️"),
+    ("ambulance", "🚑️"),
+    ("arrow_down", "⬇️"),
+    ("arrow_up", "⬆️"),
+    ("art", "🎨"),
+    ("beers", "🍻"),
+    ("bento", "🍱"),
+    ("bookmark", "🔖"),
+    ("boom", "💥"),
+    ("bricks", "🧱"),
+    ("bug", "🐛"),
+    ("building_construction", "🏗️"),
+    ("bulb", "💡"),
+    ("busts_in_silhouette", "👥"),
+    ("camera_flash", "📸"),
+    ("card_file_box", "🗃️"),
+    ("chart_with_upwards_trend", "📈"),
+    ("children_crossing", "🚸"),
+    ("closed_lock_with_key", "🔐"),
+    ("clown_face", "🤡"),
+    ("coffin", "⚰️"),
+    ("construction", "🚧"),
+    ("construction_worker", "👷"),
+    ("dizzy", "💫"),
+    ("egg", "🥚"),
+    ("fire", "🔥"),
+    ("globe_with_meridians", "🌐"),
+    ("goal_net", "🥅"),
+    ("green_heart", "💚"),
+    ("hammer", "🔨"),
+    ("heavy_check_mark", "✔️"),
+    ("heavy_minus_sign", "➖"),
+    ("heavy_plus_sign", "➕"),
+    ("iphone", "📱"),
+    ("label", "🏷️"),
+    ("lipstick", "💄"),
+    ("lock", "🔒️"),
+    ("loud_sound", "🔊"),
+    ("mag", "🔍️"),
+    ("memo", "📝"),
+    ("money_with_wings", "💸"),
+    ("monocle_face", "🧐"),
+    ("mute", "🔇"),
+    ("necktie", "👔"),
+    ("package", "📦️"),
+    ("page_facing_up", "📄"),
+    ("passport_control", "🛂"),
+    ("pencil", "📝"),
+    ("pencil2", "✏️"),
+    ("poop", "💩"),
+    ("pushpin", "📌"),
+    ("recycle", "♻️"),
+    ("rewind", "⏪️"),
+    ("rocket", "🚀"),
+    ("rotating_light", "🚨"),
+    ("safety_vest", "🦺"),
+    ("see_no_evil", "🙈"),
+    ("seedling", "🌱"),
+    ("sparkles", "✨"),
+    ("speech_balloon", "💬"),
+    ("stethoscope", "🩺"),
+    ("tada", "🎉"),
+    ("technologist", "🧑‍💻"),
+    ("test_tube", "🧪"),
+    ("thread", "🧵"),
+    ("triangular_flag_on_post", "🚩"),
+    ("truck", "🚚"),
+    ("twisted_rightwards_arrows", "🔀"),
+    ("warning", "⚠️"),
+    ("wastebasket", "🗑️"),
+    ("wheelchair", "♿️"),
+    ("white_check_mark", "✅"),
+    ("wrench", "🔧"),
+    ("x", "❌"),
+    ("zap", "⚡️"),
+];
+
+/// Emoji for a shortcode name (without the colons)
+fn emoji(name: &str) -> Option<&'static str> {
+    EMOJI
+        .binary_search_by_key(&name, |(shortcode, _)| shortcode)
+        .ok()
+        .map(|index| EMOJI[index].1)
+}
+
+/// Replace known `:shortcode:` tokens with their emoji
+///
+/// Unknown shortcodes and anything inside a `code span` are left as is.
+pub fn replace_shortcodes(text: &str) -> String {
+    let mut output = String::new();
+    let mut last = 0;
+
+    for captures in SHORTCODE.captures_iter(text) {
+        let token = captures.get(0).unwrap();
+        let Some(emoji) = emoji(&captures[1]) else {
+            continue;
+        };
+        if text[..token.start()].matches('`').count() % 2 == 1 {
+            continue;
+        }
+
+        output.push_str(&text[last..token.start()]);
+        output.push_str(emoji);
+        last = token.end();
+    }
+
+    output.push_str(&text[last..]);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_is_sorted() {
+        assert!(EMOJI.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn test_replace_shortcodes() {
+        assert_eq!(replace_shortcodes(":sparkles: Add export"), "✨ Add export");
+        assert_eq!(replace_shortcodes(":bug: Fix :zap: path"), "🐛 Fix ⚡️ path");
+        assert_eq!(
+            replace_shortcodes(":not_an_emoji: stays"),
+            ":not_an_emoji: stays"
+        );
+        assert_eq!(
+            replace_shortcodes("Parse `:tada:` literally"),
+            "Parse `:tada:` literally"
+        );
+        assert_eq!(replace_shortcodes("Time 10:30:00"), "Time 10:30:00");
+    }
+}
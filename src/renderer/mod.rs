@@ -2,20 +2,101 @@
 //!
 //! Generates daily chronicle output in Markdown format.
 //! Renders sections: Summary, Git Activity, TODOs, Notes.
+//!
+//! Change markers are emitted as ` ← **LABEL**` so the terminal display can
+//! color them per label (see `display::formatter`).
 
-use chrono::{DateTime, NaiveDate, Utc};
+pub mod export;
+mod gitmoji;
 
-use crate::config::Config;
-use crate::models::{Branch, ChangeKind, Chronicle, Note, Repository, Todo, TodoStatus};
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use crate::archive::demote_headings;
+use crate::config::{Config, Display, TodoGrouping};
+use crate::models::{
+    Branch, ChangeKind, Chronicle, ChronicleStats, CollectionWarning, Commit, Event, Note,
+    Repository, Todo, TodoStatus, TodoTransition, WorkInProgress,
+};
+
+/// Most highlights shown at the top of a chronicle
+const MAX_HIGHLIGHTS: usize = 5;
+
+/// Longest repository description shown under its heading
+const MAX_DESCRIPTION_CHARS: usize = 100;
+
+/// Length of the longest bar in the repository breakdown
+const BREAKDOWN_WIDTH: usize = 20;
+
+/// How the Git activity is laid out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum View {
+    /// Commits grouped per repository (and per branch or author)
+    #[default]
+    Repositories,
+    /// Commits from all repositories interleaved by time
+    Timeline,
+}
 
 /// Markdown renderer for chronicles
 pub struct Renderer<'a> {
     config: &'a Config,
+    /// Date and stats of the previous chronicle, for `show_deltas`
+    previous: Option<(NaiveDate, ChronicleStats)>,
+    /// Consecutive days with a chronicle, including this one, for `show_streak`
+    streak: Option<usize>,
+    /// Items dropped by `max_total_items`, noted in a footer
+    omitted: usize,
+    /// Layout of the Git activity
+    view: View,
+    /// Paragraph from `summary_command`, shown under the Summary
+    summary_text: Option<String>,
 }
 
 impl<'a> Renderer<'a> {
     pub fn new(config: &'a Config) -> Self {
-        Self { config }
+        Self {
+            config,
+            previous: None,
+            streak: None,
+            omitted: 0,
+            view: View::default(),
+            summary_text: None,
+        }
+    }
+
+    /// Compare the Summary against a previous chronicle's stats
+    pub fn with_previous(mut self, date: NaiveDate, stats: ChronicleStats) -> Self {
+        self.previous = Some((date, stats));
+        self
+    }
+
+    /// Show a streak of consecutive days in the Summary
+    pub fn with_streak(mut self, days: usize) -> Self {
+        self.streak = Some(days);
+        self
+    }
+
+    /// Note in a footer that `count` items were left out
+    pub fn with_omitted(mut self, count: usize) -> Self {
+        self.omitted = count;
+        self
+    }
+
+    /// Lay out the Git activity as `view`
+    pub fn with_view(mut self, view: View) -> Self {
+        self.view = view;
+        self
+    }
+
+    /// Show a prose summary (from `summary_command`) under the Summary
+    pub fn with_summary_text(mut self, text: String) -> Self {
+        self.summary_text = Some(text);
+        self
     }
 
     /// Render a complete chronicle to Markdown
@@ -27,16 +108,79 @@ impl<'a> Renderer<'a> {
             &chronicle.date,
             chronicle.generated_at,
             chronicle.since,
+            chronicle.until,
         ));
+        if let Some(host) = &chronicle.host {
+            output.push_str(&format!("\n**Host:** {}", host));
+        }
         output.push_str("\n\n");
 
+        // A quiet day written anyway gets its message instead of sections
+        if self.config.write_empty && !chronicle.has_activity() {
+            output.push_str(&self.config.empty_message);
+            output.push_str("\n\n");
+            if self.config.display.show_warnings && !chronicle.warnings.is_empty() {
+                output.push_str(&Self::render_warnings(&chronicle.warnings));
+                output.push_str("\n\n");
+            }
+            return output.trim_end().to_string();
+        }
+
+        // Highlights
+        if self.config.display.show_highlights {
+            let highlights = self.render_highlights(chronicle);
+            if !highlights.is_empty() {
+                output.push_str(&highlights);
+                output.push_str("\n\n");
+            }
+        }
+
         // Summary
         output.push_str(&self.render_summary(chronicle));
         output.push_str("\n\n");
 
+        output.push_str(&self.render_sections(chronicle));
+        output.trim_end().to_string()
+    }
+
+    /// Render a section to append to an existing chronicle (`gen --append`):
+    /// its own generation time and window, then the activity sections one
+    /// level under `## Update`, without highlights or a Summary
+    ///
+    /// The chronicle's Summary is left as it was, so the update says it isn't
+    /// counted there.
+    pub fn render_update(&self, chronicle: &Chronicle) -> String {
+        let mut output = String::from("---\n\n## Update\n\n");
+
+        output.push_str(&format!(
+            "**Generated:** {}\n",
+            self.format_timestamp(chronicle.generated_at)
+        ));
+        output.push_str(&format!(
+            "**Since:** {}\n",
+            self.format_timestamp(chronicle.since)
+        ));
+        if let Some(host) = &chronicle.host {
+            output.push_str(&format!("**Host:** {}\n", host));
+        }
+        output
+            .push_str("\n*Not counted in the Summary above, which covers the first run only*\n\n");
+
+        output.push_str(&demote_headings(&self.render_sections(chronicle)));
+        output.trim_end().to_string()
+    }
+
+    /// Render the activity sections (Git, TODOs, notes, events, warnings)
+    fn render_sections(&self, chronicle: &Chronicle) -> String {
+        let mut output = String::new();
+
         // Git Activity
         if !chronicle.repositories.is_empty() {
-            output.push_str(&self.render_git_activity(&chronicle.repositories));
+            let activity = match self.view {
+                View::Repositories => self.render_git_activity(&chronicle.repositories),
+                View::Timeline => self.render_timeline(&chronicle.repositories),
+            };
+            output.push_str(&activity);
             output.push_str("\n\n");
         }
 
@@ -47,12 +191,32 @@ impl<'a> Renderer<'a> {
         }
 
         // Notes
-        if !chronicle.notes.is_empty() {
-            output.push_str(&self.render_notes(&chronicle.notes));
+        if !chronicle.notes.is_empty() || chronicle.notes_omitted > 0 {
+            output.push_str(&self.render_notes(&chronicle.notes, chronicle.notes_omitted));
             output.push_str("\n\n");
         }
 
-        output.trim_end().to_string()
+        // Events
+        if !chronicle.events.is_empty() {
+            output.push_str(&self.render_events(&chronicle.events, chronicle.date));
+            output.push_str("\n\n");
+        }
+
+        // Warnings
+        if self.config.display.show_warnings && !chronicle.warnings.is_empty() {
+            output.push_str(&Self::render_warnings(&chronicle.warnings));
+            output.push_str("\n\n");
+        }
+
+        if self.omitted > 0 {
+            output.push_str(&format!(
+                "---\n\n*... {} item{} omitted (max_total_items)*\n",
+                self.omitted,
+                if self.omitted == 1 { "" } else { "s" }
+            ));
+        }
+
+        output
     }
 
     /// Render header section
@@ -61,39 +225,190 @@ impl<'a> Renderer<'a> {
         date: &NaiveDate,
         generated_at: DateTime<Utc>,
         since: DateTime<Utc>,
+        until: Option<DateTime<Utc>>,
     ) -> String {
         let mut output = String::new();
 
-        output.push_str(&format!("# Chronicle: {}\n\n", date.format("%Y-%m-%d")));
+        // A range spanning several days is titled with its first day too
+        let first_day = since
+            .with_timezone(&self.config.timezone.unwrap_or(Tz::UTC))
+            .date_naive();
+        if until.is_some() && first_day < *date {
+            output.push_str(&format!(
+                "# Chronicle: {} – {}\n\n",
+                first_day.format("%Y-%m-%d"),
+                date.format("%Y-%m-%d")
+            ));
+        } else {
+            output.push_str(&format!("# Chronicle: {}\n\n", date.format("%Y-%m-%d")));
+        }
+
         output.push_str(&format!(
             "**Generated:** {}\n",
-            generated_at.format("%Y-%m-%d %H:%M:%S UTC")
-        ));
-        output.push_str(&format!(
-            "**Since:** {}",
-            since.format("%Y-%m-%d %H:%M:%S UTC")
+            self.format_timestamp(generated_at)
         ));
+        output.push_str(&format!("**Since:** {}", self.format_timestamp(since)));
+        if let Some(until) = until {
+            output.push_str(&format!("\n**Until:** {}", self.format_timestamp(until)));
+        }
 
         output
     }
 
+    /// Format a timestamp in the configured timezone (UTC by default)
+    fn format_timestamp(&self, timestamp: DateTime<Utc>) -> String {
+        let timezone = self.config.timezone.unwrap_or(Tz::UTC);
+        timestamp
+            .with_timezone(&timezone)
+            .format("%Y-%m-%d %H:%M:%S %Z")
+            .to_string()
+    }
+
+    /// Link issue references using `issue_url_template`, if configured
+    fn linkify(&self, text: &str) -> String {
+        match &self.config.issue_url_template {
+            Some(template) => linkify_issues(text, template),
+            None => text.to_string(),
+        }
+    }
+
+    /// Commit message as rendered: shortcodes converted when
+    /// `render_gitmoji` is set, then cut to `max_commit_message_chars`
+    fn commit_message(&self, message: &str) -> String {
+        let message = if self.config.display.render_gitmoji {
+            gitmoji::replace_shortcodes(message)
+        } else {
+            message.to_string()
+        };
+        truncate_message(&message, self.config.limits.max_commit_message_chars)
+    }
+
+    /// Render up to five highlights: the most active repository, newly
+    /// completed TODOs, and the largest updated note
+    ///
+    /// Returns an empty string when nothing qualifies.
+    fn render_highlights(&self, chronicle: &Chronicle) -> String {
+        let mut highlights = Vec::new();
+
+        // Ties go to the name that sorts first
+        let names = display_names(&chronicle.repositories);
+        let name = |repo: &Repository| names[repo.path.as_path()].as_str();
+        if let Some(repo) = chronicle
+            .repositories
+            .iter()
+            .filter(|repo| repo.commit_count() > 0)
+            .max_by(|a, b| {
+                a.commit_count()
+                    .cmp(&b.commit_count())
+                    .then(name(b).cmp(name(a)))
+            })
+        {
+            let commits = repo.commit_count();
+            highlights.push(format!(
+                "Most active repository: **{}** ({} commit{})",
+                name(repo),
+                commits,
+                if commits == 1 { "" } else { "s" }
+            ));
+        }
+
+        let largest_note = chronicle
+            .notes
+            .iter()
+            .filter(|note| note.word_count > 0)
+            .max_by(|a, b| a.word_count.cmp(&b.word_count).then(b.path.cmp(&a.path)));
+
+        // Leave room for the note after the completed TODOs
+        let todo_slots = MAX_HIGHLIGHTS - highlights.len() - usize::from(largest_note.is_some());
+        for todo in chronicle
+            .todos
+            .iter()
+            .filter(|todo| todo.was_completed(&self.config.completed_statuses))
+            .take(todo_slots)
+        {
+            highlights.push(format!("Completed: {}", self.linkify(&todo.content)));
+        }
+
+        if let Some(note) = largest_note {
+            highlights.push(format!(
+                "Largest note update: `{}` ({} words)",
+                note.path.display(),
+                format_count(note.word_count)
+            ));
+        }
+
+        if highlights.is_empty() {
+            return String::new();
+        }
+
+        let mut output = String::from("## Highlights\n\n");
+        for highlight in highlights {
+            output.push_str(&format!("- {}\n", highlight));
+        }
+
+        output.trim_end().to_string()
+    }
+
+    /// Render what the collectors skipped, one item per warning
+    fn render_warnings(warnings: &[CollectionWarning]) -> String {
+        let mut output = String::from("## Warnings\n\n");
+        for warning in warnings {
+            output.push_str(&format!("- **{}**: {}\n", warning.source, warning.message));
+        }
+        output.trim_end().to_string()
+    }
+
     /// Render summary statistics table
     fn render_summary(&self, chronicle: &Chronicle) -> String {
-        let stats = chronicle.stats();
+        let stats = chronicle.stats(&self.config.completed_statuses);
         let mut output = String::new();
 
         output.push_str("## Summary\n\n");
-        output.push_str("| Category | Count |\n");
-        output.push_str("|----------|-------|\n");
-        output.push_str(&format!("| Repositories | {} |\n", stats.repo_count));
-        output.push_str(&format!("| Commits | {} |\n", stats.commit_count));
-        output.push_str(&format!("| New Branches | {} |\n", stats.new_branch_count));
-        output.push_str(&format!("| New TODOs | {} |\n", stats.todos_new));
-        output.push_str(&format!(
-            "| Completed TODOs | {} |\n",
-            stats.todos_completed
-        ));
-        output.push_str(&format!("| Note Updates | {} |", stats.notes_count));
+        output.push_str(&render_stats_table(&stats, true));
+        if !chronicle.warnings.is_empty() {
+            output.push_str(&format!(
+                "\n| Warnings | {} |",
+                format_count(chronicle.warnings.len())
+            ));
+        }
+
+        if self.config.display.show_repo_breakdown {
+            let breakdown = render_repo_breakdown(&chronicle.repositories);
+            if !breakdown.is_empty() {
+                output.push_str(&format!("\n\n{}", breakdown));
+            }
+        }
+
+        if let Some(text) = &self.summary_text {
+            output.push_str(&format!("\n\n{}", text));
+        }
+
+        if self.config.display.show_deltas {
+            if let Some((date, previous)) = &self.previous {
+                output.push_str(&format!(
+                    "\n\n*Compared to {}: {}*",
+                    date.format("%Y-%m-%d"),
+                    render_deltas(&stats, previous)
+                ));
+            }
+        }
+
+        if self.config.display.show_streak {
+            if let Some(days) = self.streak {
+                output.push_str(&format!("\n\n*Day {} streak*", days));
+            }
+        }
+
+        if let Some(stale_days) = self.config.display.stale_branch_days {
+            let stale = stale_branches(&chronicle.repositories, stale_days, chronicle.generated_at);
+            if !stale.is_empty() {
+                output.push_str(&format!(
+                    "\n\n**Stale branches** (over {} days): {}",
+                    stale_days,
+                    stale.join(", ")
+                ));
+            }
+        }
 
         output
     }
@@ -104,21 +419,94 @@ impl<'a> Renderer<'a> {
 
         output.push_str("## Git Activity\n");
 
+        let names = display_names(repositories);
         for repo in repositories {
             output.push('\n');
-            output.push_str(&self.render_repository(repo));
+            output.push_str(&self.render_repository(repo, &names[repo.path.as_path()]));
+        }
+
+        output
+    }
+
+    /// Render commits from all repositories in time order, under a heading
+    /// per day
+    ///
+    /// A commit reachable from several branches is listed once, with the
+    /// first branch it was collected from.
+    fn render_timeline(&self, repositories: &[Repository]) -> String {
+        let mut output = String::new();
+
+        output.push_str("## Timeline\n");
+
+        let mut seen = std::collections::HashSet::new();
+        let mut entries: Vec<(&Repository, &Branch, &Commit)> = Vec::new();
+        for repo in repositories {
+            for branch in &repo.branches {
+                for commit in &branch.commits {
+                    if seen.insert((&repo.path, &commit.hash)) {
+                        entries.push((repo, branch, commit));
+                    }
+                }
+            }
+        }
+
+        entries.sort_by_key(|(_, _, commit)| commit.timestamp);
+
+        let names = display_names(repositories);
+        let timezone = self.config.timezone.unwrap_or(Tz::UTC);
+        let mut current_day = None;
+        for (repo, branch, commit) in entries {
+            let local = commit.timestamp.with_timezone(&timezone);
+
+            if current_day != Some(local.date_naive()) {
+                current_day = Some(local.date_naive());
+                output.push_str(&format!("\n### {}\n\n", local.format("%Y-%m-%d")));
+            }
+
+            let display = self.config.display_for(&repo.path);
+            let author_info = if display.show_authors {
+                format!(" — *{}*", commit.author)
+            } else {
+                String::new()
+            };
+
+            output.push_str(&format!(
+                "- {} **{}** `{}`{} {} (`{}`){}  \n",
+                local.format("%H:%M"),
+                names[repo.path.as_path()],
+                commit.hash,
+                signature_marker(commit, &display),
+                self.linkify(&self.commit_message(&commit.message)),
+                branch.name,
+                author_info
+            ));
         }
 
         output
     }
 
-    /// Render a single repository
-    fn render_repository(&self, repo: &Repository) -> String {
+    /// Render a single repository under `name` (see [`display_names`])
+    fn render_repository(&self, repo: &Repository, name: &str) -> String {
         let mut output = String::new();
 
-        output.push_str(&format!("### {}\n\n", repo.name));
+        output.push_str(&format!("### {}\n\n", name));
+        if let Some(description) = &repo.description {
+            let description = truncate_message(description, MAX_DESCRIPTION_CHARS);
+            output.push_str(&format!("*{}*\n\n", escape_emphasis(&description)));
+        }
         output.push_str(&format!("**Path:** `{}`\n\n", repo.path.display()));
 
+        if let Some(wip) = &repo.wip {
+            output.push_str(&Self::render_wip(wip));
+        }
+
+        let display = self.config.display_for(&repo.path);
+        let limits = self.config.limits_for(&repo.path);
+        if display.group_by_author {
+            output.push_str(&self.render_authors(repo));
+            return output;
+        }
+
         // Sort branches: default first, then by commit count
         let mut sorted_branches = repo.branches.clone();
         sorted_branches.sort_by(|a, b| {
@@ -132,20 +520,100 @@ impl<'a> Renderer<'a> {
         });
 
         for branch in &sorted_branches {
-            output.push_str(&self.render_branch(branch, &repo.default_branch));
+            output.push_str(&self.render_branch(
+                branch,
+                &repo.default_branch,
+                &repo.current_branch,
+                &display,
+                limits.max_changed_files,
+            ));
+            output.push('\n');
+        }
+
+        output
+    }
+
+    /// Render uncommitted changes as a one-line note
+    fn render_wip(wip: &WorkInProgress) -> String {
+        let counts: Vec<String> = [
+            (wip.modified, "modified"),
+            (wip.added, "added"),
+            (wip.deleted, "deleted"),
+        ]
+        .iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, label)| format!("{} {}", count, label))
+        .collect();
+
+        format!("**WIP:** uncommitted changes ({})\n\n", counts.join(", "))
+    }
+
+    /// Render a repository's commits grouped by author, most active first
+    ///
+    /// A commit reachable from several branches is listed once, under the
+    /// first branch it was collected from.
+    fn render_authors(&self, repo: &Repository) -> String {
+        let mut output = String::new();
+        let display = self.config.display_for(&repo.path);
+
+        let mut seen = std::collections::HashSet::new();
+        let mut by_author: Vec<(&str, Vec<(&Commit, &str)>)> = Vec::new();
+        for branch in &repo.branches {
+            for commit in &branch.commits {
+                if !seen.insert(&commit.hash) {
+                    continue;
+                }
+
+                match by_author
+                    .iter_mut()
+                    .find(|(author, _)| *author == commit.author)
+                {
+                    Some((_, commits)) => commits.push((commit, &branch.name)),
+                    None => by_author.push((&commit.author, vec![(commit, &branch.name)])),
+                }
+            }
+        }
+
+        by_author
+            .sort_by(|(a_name, a), (b_name, b)| b.len().cmp(&a.len()).then(a_name.cmp(b_name)));
+
+        for (author, commits) in by_author {
+            output.push_str(&format!(
+                "#### {} ({} commit{})\n\n",
+                author,
+                commits.len(),
+                if commits.len() == 1 { "" } else { "s" }
+            ));
+
+            for (commit, branch) in commits {
+                output.push_str(&format!(
+                    "- `{}`{} {} (`{}`)  \n",
+                    commit.hash,
+                    signature_marker(commit, &display),
+                    self.linkify(&self.commit_message(&commit.message)),
+                    branch
+                ));
+            }
             output.push('\n');
         }
 
         output
     }
 
-    /// Render a single branch
-    fn render_branch(&self, branch: &Branch, default_branch: &str) -> String {
+    /// Render a single branch, listing at most `max_files` changed files
+    fn render_branch(
+        &self,
+        branch: &Branch,
+        default_branch: &str,
+        current_branch: &str,
+        display: &Display,
+        max_files: usize,
+    ) -> String {
         let mut output = String::new();
 
         // Branch header
         let change_marker = match branch.change {
-            ChangeKind::New => " ← NEW",
+            ChangeKind::New => " ← **NEW**",
             _ => "",
         };
 
@@ -156,59 +624,110 @@ impl<'a> Renderer<'a> {
                 String::new()
             };
 
+        let current = if branch.name == current_branch {
+            " (current)"
+        } else {
+            ""
+        };
+
+        let age = match branch.age_days(Utc::now()) {
+            Some(days) if branch.name != default_branch && days > 0 => {
+                format!(" ({})", format_age(days))
+            }
+            _ => String::new(),
+        };
+
         output.push_str(&format!(
-            "#### `{}`{}{}\n\n",
-            branch.name, ahead_behind, change_marker
+            "#### `{}`{}{}{}{}\n\n",
+            branch.name, current, ahead_behind, age, change_marker
         ));
 
         // Commits
         if !branch.commits.is_empty() {
             for commit in &branch.commits {
-                let author_info = if self.config.display.show_authors {
+                let author_info = if display.show_authors {
                     format!(" — *{}*", commit.author)
                 } else {
                     String::new()
                 };
 
-                output.push_str(&format!(
-                    "- `{}` {}{}  \n",
-                    commit.hash, commit.message, author_info
-                ));
+                let line = format!(
+                    "- `{}`{} {}{}",
+                    commit.hash,
+                    signature_marker(commit, display),
+                    self.commit_message(&commit.message),
+                    author_info
+                );
+                let line = match display.max_line_width {
+                    Some(width) => wrap_line(&line, width),
+                    None => line,
+                };
+                let line = self.linkify(&line);
+
+                output.push_str(&format!("{}  \n", line));
             }
 
             // Changed files
             let all_files: std::collections::HashSet<_> =
                 branch.commits.iter().flat_map(|c| &c.files).collect();
+            let binary_count = if display.hide_binary_files {
+                0
+            } else {
+                branch
+                    .commits
+                    .iter()
+                    .flat_map(|c| &c.binary_files)
+                    .collect::<std::collections::HashSet<_>>()
+                    .len()
+            };
 
-            if !all_files.is_empty() {
+            if !all_files.is_empty() || binary_count > 0 {
                 output.push('\n');
-                output.push_str(
-                    &self.render_changed_files(&all_files.into_iter().collect::<Vec<_>>()),
-                );
+                output.push_str(&Self::render_changed_files(
+                    &all_files.into_iter().collect::<Vec<_>>(),
+                    binary_count,
+                    max_files,
+                ));
             }
         }
 
         output
     }
 
-    /// Render changed files as collapsible details
-    fn render_changed_files(&self, files: &[&std::path::PathBuf]) -> String {
+    /// Render changed files as collapsible details, with binary files
+    /// summarized in a single line
+    fn render_changed_files(
+        files: &[&std::path::PathBuf],
+        binary_count: usize,
+        max_files: usize,
+    ) -> String {
         let mut output = String::new();
 
-        let max_files = self.config.limits.max_changed_files;
+        // Sorted, so re-rendering the same day gives the same file
+        let mut files = files.to_vec();
+        files.sort();
+
         let file_count = files.len();
         let display_count = file_count.min(max_files);
 
         output.push_str("<details>\n");
         output.push_str(&format!(
             "<summary>Changed files ({})</summary>\n\n",
-            file_count
+            file_count + binary_count
         ));
 
         for file in files.iter().take(display_count) {
             output.push_str(&format!("- `{}`\n", file.display()));
         }
 
+        if binary_count > 0 {
+            output.push_str(&format!(
+                "- *{} binary file{}*\n",
+                binary_count,
+                if binary_count == 1 { "" } else { "s" }
+            ));
+        }
+
         if file_count > max_files {
             output.push_str(&format!(
                 "\n*... and {} more files*\n",
@@ -227,29 +746,71 @@ impl<'a> Renderer<'a> {
 
         output.push_str("## TODOs\n");
 
-        // Group by file
-        let mut todos_by_file = std::collections::HashMap::new();
-        for todo in todos {
-            todos_by_file
-                .entry(&todo.file)
-                .or_insert_with(Vec::new)
-                .push(todo);
-        }
-
-        for (file, file_todos) in todos_by_file {
-            output.push('\n');
-            output.push_str(&format!("### `{}`\n\n", file.display()));
-
-            for todo in file_todos {
-                output.push_str(&self.render_todo(todo));
+        match self.config.display.todo_grouping {
+            TodoGrouping::File => {
+                // Group by file, in path order
+                let mut todos_by_file = std::collections::BTreeMap::new();
+                for todo in todos {
+                    todos_by_file
+                        .entry(&todo.file)
+                        .or_insert_with(Vec::new)
+                        .push(todo);
+                }
+
+                for (file, mut file_todos) in todos_by_file {
+                    output.push('\n');
+                    output.push_str(&format!("### `{}`\n\n", file.display()));
+
+                    // In file order, so nesting follows the source
+                    file_todos.sort_by_key(|todo| todo.line);
+
+                    // Unchanged parents may be omitted, so never nest deeper than
+                    // one level below the previous item
+                    let mut previous_depth: Option<usize> = None;
+                    for todo in file_todos {
+                        let depth =
+                            previous_depth.map_or(0, |previous| todo.depth.min(previous + 1));
+                        output.push_str(&self.render_todo(todo, depth));
+                        previous_depth = Some(depth);
+                    }
+                }
+            }
+            TodoGrouping::Tag => {
+                // Group by tag (an item appears under each of its tags)
+                let mut todos_by_tag = std::collections::BTreeMap::new();
+                let mut untagged = Vec::new();
+                for todo in todos {
+                    if todo.tags.is_empty() {
+                        untagged.push(todo);
+                    }
+                    for tag in &todo.tags {
+                        todos_by_tag
+                            .entry(format!("#{}", tag))
+                            .or_insert_with(Vec::new)
+                            .push(todo);
+                    }
+                }
+
+                let groups = todos_by_tag
+                    .into_iter()
+                    .chain((!untagged.is_empty()).then(|| ("untagged".to_string(), untagged)));
+
+                for (heading, group_todos) in groups {
+                    output.push('\n');
+                    output.push_str(&format!("### {}\n\n", heading));
+
+                    for todo in group_todos {
+                        output.push_str(&self.render_todo(todo, 0));
+                    }
+                }
             }
         }
 
         output
     }
 
-    /// Render a single TODO
-    fn render_todo(&self, todo: &Todo) -> String {
+    /// Render a single TODO, indented `depth` levels
+    fn render_todo(&self, todo: &Todo, depth: usize) -> String {
         let status_marker = match todo.status {
             TodoStatus::Pending => "[ ]",
             TodoStatus::Done => "[x]",
@@ -257,17 +818,98 @@ impl<'a> Renderer<'a> {
         };
 
         let change_marker = match todo.change {
-            ChangeKind::New => " ← NEW",
-            ChangeKind::Modified if todo.was_completed() => " ← DONE",
-            ChangeKind::Modified => " ← MODIFIED",
-            ChangeKind::Unchanged => "",
+            ChangeKind::New => " ← **NEW**",
+            ChangeKind::Modified if todo.was_completed(&self.config.completed_statuses) => {
+                " ← **DONE**"
+            }
+            ChangeKind::Modified => match todo.transition() {
+                Some(TodoTransition::Started) => " ← **STARTED**",
+                Some(TodoTransition::Reopened) => " ← **REOPENED**",
+                Some(TodoTransition::Paused) => " ← **PAUSED**",
+                None if todo.was_edited() => " ← **EDITED**",
+                // Done without counting as completed (see completed_statuses)
+                Some(TodoTransition::Completed) | None => " ← **MODIFIED**",
+            },
+            ChangeKind::Unchanged | ChangeKind::Removed => "",
+        };
+
+        // Tags are shown by the group heading when grouping by tag
+        let tags: String = match self.config.display.todo_grouping {
+            TodoGrouping::File => todo.tags.iter().map(|tag| format!(" `#{}`", tag)).collect(),
+            TodoGrouping::Tag => String::new(),
+        };
+
+        let priority = todo
+            .priority
+            .map(|priority| format!("({}) ", priority))
+            .unwrap_or_default();
+
+        let deadline = todo
+            .deadline
+            .map(|deadline| format!(" *(due {})*", deadline))
+            .unwrap_or_default();
+
+        let also_in = if todo.also_in.is_empty() {
+            String::new()
+        } else {
+            let locations: Vec<String> = todo
+                .also_in
+                .iter()
+                .map(|location| format!("`{}:{}`", location.file.display(), location.line))
+                .collect();
+            format!(" *(also in {})*", locations.join(", "))
         };
 
-        format!("- {} {}{}  \n", status_marker, todo.content, change_marker)
+        format!(
+            "{}- {} {}{}{}{}{}{}  \n",
+            "  ".repeat(depth),
+            status_marker,
+            priority,
+            self.linkify(&todo.content),
+            tags,
+            deadline,
+            also_in,
+            change_marker
+        )
+    }
+
+    /// Render Events section, with the date on events not on `date`
+    fn render_events(&self, events: &[Event], date: NaiveDate) -> String {
+        let timezone = self.config.timezone.unwrap_or(Tz::UTC);
+        let mut output = String::new();
+
+        output.push_str("## Events\n\n");
+
+        for event in events {
+            let start = event.start.with_timezone(&timezone);
+            let when = if event.all_day {
+                "All day".to_string()
+            } else {
+                let mut when = start.format("%H:%M").to_string();
+                if start.date_naive() != date {
+                    when = format!("{} {}", start.format("%Y-%m-%d"), when);
+                }
+                if let Some(end) = event.end {
+                    when.push_str(&format!(
+                        "–{}",
+                        end.with_timezone(&timezone).format("%H:%M")
+                    ));
+                }
+                when
+            };
+
+            output.push_str(&format!("- **{}** {}", when, event.summary));
+            if let Some(location) = &event.location {
+                output.push_str(&format!(" *({})*", location));
+            }
+            output.push('\n');
+        }
+
+        output
     }
 
     /// Render Notes section
-    fn render_notes(&self, notes: &[Note]) -> String {
+    fn render_notes(&self, notes: &[Note], omitted: usize) -> String {
         let mut output = String::new();
 
         output.push_str("## Notes\n\n");
@@ -277,17 +919,40 @@ impl<'a> Renderer<'a> {
             output.push('\n');
         }
 
+        if omitted > 0 {
+            output.push_str(&format!(
+                "*... {} note{} omitted (max_notes_total_chars)*\n\n",
+                omitted,
+                if omitted == 1 { "" } else { "s" }
+            ));
+        }
+
         output
     }
 
     /// Render a single note
     fn render_note(&self, note: &Note) -> String {
-        let change_marker = match note.change {
-            ChangeKind::New => " ← new",
-            ChangeKind::Modified => " ← modified",
-            ChangeKind::Unchanged => "",
+        let change_marker = match (&note.renamed_from, note.change) {
+            (Some(old_path), _) => {
+                // Name only when the note stayed in the same directory
+                let old_name = old_path
+                    .file_name()
+                    .filter(|_| old_path.parent() == note.path.parent())
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| old_path.display().to_string());
+                format!(" ← **renamed** from `{}`", old_name)
+            }
+            (None, ChangeKind::New) => " ← **new**".to_string(),
+            (None, ChangeKind::Modified) => " ← **modified**".to_string(),
+            (None, ChangeKind::Removed) => " ← **removed**".to_string(),
+            (None, ChangeKind::Unchanged) => String::new(),
         };
 
+        // Nothing left to excerpt
+        if note.change == ChangeKind::Removed {
+            return format!("### `{}`{}\n", note.path.display(), change_marker);
+        }
+
         let mut output = String::new();
         output.push_str(&format!(
             "### `{}`{}\n\n",
@@ -295,60 +960,1181 @@ impl<'a> Renderer<'a> {
             change_marker
         ));
         output.push_str(&format!(
-            "*Modified: {}*\n\n",
-            note.modified_at.format("%Y-%m-%d %H:%M:%S UTC")
+            "*Modified: {}* · *{} words*\n\n",
+            self.format_timestamp(note.modified_at),
+            format_count(note.word_count)
         ));
-        output.push_str(&format!("{}\n", note.excerpt));
+        output.push_str(&format!("{}\n", self.linkify(&note.excerpt)));
 
         output
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::models::Commit;
-    use std::path::PathBuf;
+/// Issue keys like `PROJ-123` and issue numbers like `#456`, at the start of
+/// a word
+static ISSUE_REF: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(^|[^\w/#`\[-])([A-Z][A-Z0-9]+-[0-9]+|#[0-9]+)\b").unwrap());
+
+/// Turn issue references into Markdown links, with `{key}` in the template
+/// replaced by the key (`PROJ-123`) or number (`456` for `#456`)
+///
+/// References inside code spans, link text, or link destinations are left
+/// alone.
+fn linkify_issues(text: &str, template: &str) -> String {
+    let mut output = String::new();
+    let mut last = 0;
+
+    for captures in ISSUE_REF.captures_iter(text) {
+        let reference = captures.get(2).unwrap();
+
+        // Inside a `code span` or a `](destination)`
+        let before = &text[..reference.start()];
+        if before.matches('`').count() % 2 == 1 || before.ends_with("](") {
+            continue;
+        }
 
-    fn create_test_config() -> Config {
-        Config::default()
+        let key = reference.as_str().trim_start_matches('#');
+        output.push_str(&text[last..reference.start()]);
+        output.push_str(&format!(
+            "[{}]({})",
+            reference.as_str(),
+            template.replace("{key}", key)
+        ));
+        last = reference.end();
     }
 
-    #[test]
-    fn test_render_header() {
-        let config = create_test_config();
-        let renderer = Renderer::new(&config);
+    output.push_str(&text[last..]);
+    output
+}
 
-        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
-        let generated_at = Utc::now();
-        let since = Utc::now() - chrono::Duration::hours(24);
+/// Describe how each Summary count changed, e.g. `+3 Commits, -1 Note Updates`
+fn render_deltas(current: &ChronicleStats, previous: &ChronicleStats) -> String {
+    let deltas: Vec<String> = current
+        .rows()
+        .iter()
+        .zip(previous.rows())
+        .filter(|((_, now), (_, before))| now != before)
+        .map(|((label, now), (_, before))| {
+            let sign = if *now > before { '+' } else { '-' };
+            format!("{}{} {}", sign, format_count(now.abs_diff(before)), label)
+        })
+        .collect();
+
+    if deltas.is_empty() {
+        "no change".to_string()
+    } else {
+        deltas.join(", ")
+    }
+}
 
-        let output = renderer.render_header(&date, generated_at, since);
+/// ` 🔏` after a signed commit's hash, when `show_signatures` is on
+fn signature_marker(commit: &Commit, display: &Display) -> &'static str {
+    if display.show_signatures && commit.signed {
+        " 🔏"
+    } else {
+        ""
+    }
+}
 
-        assert!(output.contains("# Chronicle: 2024-01-15"));
-        assert!(output.contains("**Generated:**"));
-        assert!(output.contains("**Since:**"));
+/// Cut a commit subject to at most `max_chars` characters, at the last word
+/// boundary that fits and marked with `...`
+///
+/// A subject without a space to cut at is cut mid-word.
+fn truncate_message(message: &str, max_chars: usize) -> String {
+    if message.chars().count() <= max_chars {
+        return message.to_string();
     }
 
-    #[test]
-    fn test_render_summary() {
-        let config = create_test_config();
-        let renderer = Renderer::new(&config);
+    // Leave room for the ellipsis
+    let keep = max_chars.saturating_sub(3);
+    let limit = message
+        .char_indices()
+        .nth(keep)
+        .map_or(message.len(), |(index, _)| index);
+    let cut = if message[limit..].starts_with(char::is_whitespace) {
+        limit
+    } else {
+        message[..limit]
+            .rfind(char::is_whitespace)
+            .filter(|&index| !message[..index].trim().is_empty())
+            .unwrap_or(limit)
+    };
+
+    format!("{}...", message[..cut].trim_end())
+}
 
-        let chronicle = Chronicle {
-            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
-            since: Utc::now(),
-            generated_at: Utc::now(),
-            repositories: vec![],
-            todos: vec![],
-            notes: vec![],
-        };
+/// Escape the characters that would open or close Markdown emphasis, so
+/// `text` can be wrapped in `*...*`
+fn escape_emphasis(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '*' | '_' | '`' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
 
-        let output = renderer.render_summary(&chronicle);
+/// Render the Summary's `| Category | Count |` table for `stats`, with the
+/// Lines Changed row if `with_lines`
+pub fn render_stats_table(stats: &ChronicleStats, with_lines: bool) -> String {
+    let mut output = String::new();
 
-        assert!(output.contains("## Summary"));
-        assert!(output.contains("| Repositories | 0 |"));
-        assert!(output.contains("| Commits | 0 |"));
+    output.push_str("| Category | Count |\n");
+    output.push_str("|----------|-------|");
+    for (label, count) in stats.rows() {
+        output.push_str(&format!("\n| {} | {} |", label, format_count(count)));
+    }
+    if with_lines {
+        output.push_str(&format!(
+            "\n| Lines Changed | +{} / -{} |",
+            format_count(stats.lines_added),
+            format_count(stats.lines_removed)
+        ));
+    }
+
+    output
+}
+
+/// Render commits per repository as bars scaled to the busiest one, e.g.
+/// `app  ████████ 12`, in a code block so the bars line up
+///
+/// Repositories without commits are left out; returns an empty string when
+/// none has any.
+fn render_repo_breakdown(repositories: &[Repository]) -> String {
+    let mut counts: Vec<(&Repository, usize)> = repositories
+        .iter()
+        .map(|repo| (repo, repo.commit_count()))
+        .filter(|(_, commits)| *commits > 0)
+        .collect();
+    let Some(max) = counts.iter().map(|(_, commits)| *commits).max() else {
+        return String::new();
+    };
+    counts.sort_by_key(|(_, commits)| std::cmp::Reverse(*commits));
+
+    let names = display_names(repositories);
+    let name_width = counts
+        .iter()
+        .map(|(repo, _)| names[repo.path.as_path()].chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut output = String::from("```text\n");
+    for (repo, commits) in counts {
+        // Every repository with commits gets at least one block
+        let length = (commits * BREAKDOWN_WIDTH).div_ceil(max);
+        output.push_str(&format!(
+            "{:<width$}  {} {}\n",
+            names[repo.path.as_path()],
+            "█".repeat(length),
+            format_count(commits),
+            width = name_width
+        ));
+    }
+    output.push_str("```");
+
+    output
+}
+
+/// Heading names for `repositories`, by path
+///
+/// A name shared by several repositories gets the shortest trailing part of
+/// each path that tells them apart, e.g. `app (work/app)` and
+/// `app (personal/app)`.
+fn display_names(repositories: &[Repository]) -> HashMap<&Path, String> {
+    let mut names = HashMap::new();
+
+    for repo in repositories {
+        let namesakes: Vec<&Path> = repositories
+            .iter()
+            .filter(|other| other.name == repo.name && other.path != repo.path)
+            .map(|other| other.path.as_path())
+            .collect();
+        if namesakes.is_empty() {
+            names.insert(repo.path.as_path(), repo.name.clone());
+            continue;
+        }
+
+        let components: Vec<_> = repo.path.components().collect();
+        let suffix = (1..=components.len())
+            .map(|len| {
+                components[components.len() - len..]
+                    .iter()
+                    .collect::<PathBuf>()
+            })
+            .find(|suffix| namesakes.iter().all(|other| !other.ends_with(suffix)))
+            .unwrap_or_else(|| repo.path.clone());
+        names.insert(
+            repo.path.as_path(),
+            format!("{} ({})", repo.name, suffix.display()),
+        );
+    }
+
+    names
+}
+
+/// Format a branch age (e.g. `14 days old`)
+fn format_age(days: i64) -> String {
+    format!("{} day{} old", days, if days == 1 { "" } else { "s" })
+}
+
+/// Feature branches first seen at least `stale_days` before `now`, as
+/// `` `repo/branch` (N days old) ``
+fn stale_branches(
+    repositories: &[Repository],
+    stale_days: usize,
+    now: DateTime<Utc>,
+) -> Vec<String> {
+    let mut stale = Vec::new();
+
+    let names = display_names(repositories);
+    for repo in repositories {
+        for branch in &repo.branches {
+            if branch.name == repo.default_branch {
+                continue;
+            }
+            if let Some(days) = branch.age_days(now) {
+                if days >= stale_days as i64 {
+                    stale.push(format!(
+                        "`{}/{}` ({})",
+                        names[repo.path.as_path()],
+                        branch.name,
+                        format_age(days)
+                    ));
+                }
+            }
+        }
+    }
+
+    stale
+}
+
+/// Format a count with thousands separators (e.g. `1,234`)
+fn format_count(count: usize) -> String {
+    let digits = count.to_string();
+    let mut output = String::new();
+
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            output.push(',');
+        }
+        output.push(digit);
+    }
+
+    output
+}
+
+/// Wrap a list item at word boundaries, indenting continuation lines
+///
+/// Words longer than `width` are kept whole on their own line.
+fn wrap_line(line: &str, width: usize) -> String {
+    const INDENT: &str = "  ";
+
+    let mut output = String::new();
+    let mut column = 0;
+
+    for word in line.split_whitespace() {
+        let word_width = word.chars().count();
+
+        if column > 0 && column + 1 + word_width > width {
+            output.push('\n');
+            output.push_str(INDENT);
+            column = INDENT.len();
+        } else if column > 0 {
+            output.push(' ');
+            column += 1;
+        }
+
+        output.push_str(word);
+        column += word_width;
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{TodoLocation, TodoTotals};
+    use chrono::TimeZone;
+    use std::path::PathBuf;
+
+    fn create_test_config() -> Config {
+        Config::default()
+    }
+
+    /// Repository `name` with `commits` commits on its main branch
+    fn repo_with_commits(name: &str, commits: usize) -> Repository {
+        let commit = || Commit {
+            hash: "abc1234".to_string(),
+            message: "Commit".to_string(),
+            body: None,
+            author: "Author".to_string(),
+            timestamp: Utc::now(),
+            files: vec![],
+            binary_files: vec![],
+            additions: 0,
+            deletions: 0,
+            signed: false,
+        };
+        Repository {
+            path: PathBuf::from(name),
+            name: name.to_string(),
+            description: None,
+            default_branch: "main".to_string(),
+            current_branch: "main".to_string(),
+            branches: vec![Branch {
+                name: "main".to_string(),
+                change: ChangeKind::Modified,
+                ahead: 0,
+                behind: 0,
+                first_seen: None,
+                commits: (0..commits).map(|_| commit()).collect(),
+            }],
+            wip: None,
+            lines_added: 0,
+            lines_removed: 0,
+        }
+    }
+
+    #[test]
+    fn test_render_header() {
+        let config = create_test_config();
+        let renderer = Renderer::new(&config);
+
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let generated_at = Utc::now();
+        let since = Utc::now() - chrono::Duration::hours(24);
+
+        let output = renderer.render_header(&date, generated_at, since, None);
+
+        assert!(output.contains("# Chronicle: 2024-01-15"));
+        assert!(output.contains("**Generated:**"));
+        assert!(output.contains("**Since:**"));
+    }
+
+    #[test]
+    fn test_render_header_in_timezone() {
+        let mut config = create_test_config();
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let generated_at = Utc.with_ymd_and_hms(2024, 1, 15, 20, 30, 0).unwrap();
+        let since = Utc.with_ymd_and_hms(2024, 1, 14, 23, 0, 0).unwrap();
+
+        let output = Renderer::new(&config).render_header(&date, generated_at, since, None);
+        assert!(output.contains("**Generated:** 2024-01-15 20:30:00 UTC"));
+
+        config.timezone = Some(chrono_tz::Europe::Berlin);
+        let output = Renderer::new(&config).render_header(&date, generated_at, since, None);
+        assert!(output.contains("**Generated:** 2024-01-15 21:30:00 CET"));
+        assert!(output.contains("**Since:** 2024-01-15 00:00:00 CET"));
+    }
+
+    #[test]
+    fn test_render_header_with_range() {
+        let config = create_test_config();
+        let renderer = Renderer::new(&config);
+
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let generated_at = Utc.with_ymd_and_hms(2024, 1, 20, 9, 0, 0).unwrap();
+        let since = Utc.with_ymd_and_hms(2024, 1, 8, 0, 0, 0).unwrap();
+        let until = Utc.with_ymd_and_hms(2024, 1, 15, 23, 59, 59).unwrap();
+
+        let output = renderer.render_header(&date, generated_at, since, Some(until));
+
+        assert!(output.starts_with("# Chronicle: 2024-01-08 – 2024-01-15\n"));
+        assert!(output.ends_with("**Until:** 2024-01-15 23:59:59 UTC"));
+
+        // A single day keeps the plain title
+        let since = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        let output = renderer.render_header(&date, generated_at, since, Some(until));
+        assert!(output.starts_with("# Chronicle: 2024-01-15\n"));
+    }
+
+    #[test]
+    fn test_render_todos_nesting() {
+        let config = create_test_config();
+        let renderer = Renderer::new(&config);
+
+        let todo = |content: &str, line: usize, depth: usize| Todo {
+            content: content.to_string(),
+            status: TodoStatus::Pending,
+            change: ChangeKind::New,
+            previous_status: None,
+            previous_content: None,
+            file: PathBuf::from("todo.md"),
+            line,
+            depth,
+            tags: vec![],
+            priority: None,
+            deadline: None,
+            also_in: vec![],
+        };
+
+        // "Orphan" lost its unchanged parents, so it nests at most one level
+        let todos = vec![
+            todo("Parent", 1, 0),
+            todo("Child", 2, 1),
+            todo("Other", 5, 0),
+            todo("Orphan", 8, 2),
+        ];
+
+        let output = renderer.render_todos(&todos);
+
+        assert!(output.contains("- [ ] Parent ← **NEW**  \n  - [ ] Child"));
+        assert!(output.contains("\n- [ ] Other"));
+        assert!(output.contains("\n  - [ ] Orphan"));
+    }
+
+    #[test]
+    fn test_render_todos_file_sections_sorted() {
+        let config = create_test_config();
+        let renderer = Renderer::new(&config);
+
+        let todo = |file: &str| Todo {
+            content: format!("Task in {}", file),
+            status: TodoStatus::Pending,
+            change: ChangeKind::New,
+            previous_status: None,
+            previous_content: None,
+            file: PathBuf::from(file),
+            line: 1,
+            depth: 0,
+            tags: vec![],
+            priority: None,
+            deadline: None,
+            also_in: vec![],
+        };
+        let todos = vec![todo("work.md"), todo("inbox.md"), todo("personal.md")];
+
+        let output = renderer.render_todos(&todos);
+
+        let headings: Vec<&str> = output
+            .lines()
+            .filter(|line| line.starts_with("### "))
+            .collect();
+        assert_eq!(
+            headings,
+            vec!["### `inbox.md`", "### `personal.md`", "### `work.md`"]
+        );
+    }
+
+    #[test]
+    fn test_render_todos_sorted_by_file_and_line() {
+        let config = create_test_config();
+        let renderer = Renderer::new(&config);
+
+        let todo = |file: &str, line: usize| Todo {
+            content: format!("{}:{}", file, line),
+            status: TodoStatus::Pending,
+            change: ChangeKind::New,
+            previous_status: None,
+            previous_content: None,
+            file: PathBuf::from(file),
+            line,
+            depth: 0,
+            tags: vec![],
+            priority: None,
+            deadline: None,
+            also_in: vec![],
+        };
+        let todos = vec![
+            todo("b.md", 7),
+            todo("a.md", 3),
+            todo("b.md", 2),
+            todo("a.md", 1),
+        ];
+
+        let output = renderer.render_todos(&todos);
+
+        let lines: Vec<&str> = output
+            .lines()
+            .filter(|line| line.starts_with("### ") || line.starts_with("- "))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "### `a.md`",
+                "- [ ] a.md:1 ← **NEW**  ",
+                "- [ ] a.md:3 ← **NEW**  ",
+                "### `b.md`",
+                "- [ ] b.md:2 ← **NEW**  ",
+                "- [ ] b.md:7 ← **NEW**  ",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_todos_grouped_by_tag() {
+        let mut config = create_test_config();
+        config.display.todo_grouping = TodoGrouping::Tag;
+        let renderer = Renderer::new(&config);
+
+        let todo = |content: &str, tags: &[&str]| Todo {
+            content: content.to_string(),
+            status: TodoStatus::Pending,
+            change: ChangeKind::New,
+            previous_status: None,
+            previous_content: None,
+            file: PathBuf::from("todo.md"),
+            line: 1,
+            depth: 0,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            priority: None,
+            deadline: None,
+            also_in: vec![],
+        };
+
+        let todos = vec![
+            todo("Ship release", &["work", "urgent"]),
+            todo("Water plants", &[]),
+            todo("Book venue", &["work"]),
+        ];
+
+        let output = renderer.render_todos(&todos);
+
+        let urgent = output.find("### #urgent").unwrap();
+        let work = output.find("### #work").unwrap();
+        let untagged = output.find("### untagged").unwrap();
+        assert!(urgent < work && work < untagged);
+        assert_eq!(output.matches("Ship release").count(), 2);
+        assert!(output[untagged..].contains("Water plants"));
+        assert!(!output.contains("`#work`"));
+    }
+
+    #[test]
+    fn test_render_todo_shows_tags_when_grouped_by_file() {
+        let config = create_test_config();
+        let renderer = Renderer::new(&config);
+
+        let todo = Todo {
+            content: "Ship release".to_string(),
+            status: TodoStatus::Pending,
+            change: ChangeKind::New,
+            previous_status: None,
+            previous_content: None,
+            file: PathBuf::from("todo.md"),
+            line: 1,
+            depth: 0,
+            tags: vec!["work".to_string()],
+            priority: None,
+            deadline: None,
+            also_in: vec![],
+        };
+
+        let output = renderer.render_todo(&todo, 0);
+
+        assert!(output.contains("- [ ] Ship release `#work` ← **NEW**"));
+    }
+
+    #[test]
+    fn test_render_todo_lists_other_locations() {
+        let config = create_test_config();
+        let renderer = Renderer::new(&config);
+
+        let todo = Todo {
+            content: "Ship release".to_string(),
+            status: TodoStatus::Pending,
+            change: ChangeKind::New,
+            previous_status: None,
+            previous_content: None,
+            file: PathBuf::from("daily.md"),
+            line: 1,
+            depth: 0,
+            tags: vec![],
+            priority: None,
+            deadline: None,
+            also_in: vec![
+                TodoLocation {
+                    file: PathBuf::from("project.md"),
+                    line: 4,
+                },
+                TodoLocation {
+                    file: PathBuf::from("inbox.md"),
+                    line: 2,
+                },
+            ],
+        };
+
+        let output = renderer.render_todo(&todo, 0);
+
+        assert!(output
+            .contains("- [ ] Ship release *(also in `project.md:4`, `inbox.md:2`)* ← **NEW**"));
+    }
+
+    #[test]
+    fn test_render_summary() {
+        let config = create_test_config();
+        let renderer = Renderer::new(&config);
+
+        let chronicle = Chronicle {
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            since: Utc::now(),
+            until: None,
+            generated_at: Utc::now(),
+            host: None,
+            repositories: vec![],
+            todos: vec![],
+            todo_totals: TodoTotals { open: 23, done: 4 },
+            notes: vec![],
+            notes_omitted: 0,
+            events: vec![],
+            warnings: vec![],
+        };
+
+        let output = renderer.render_summary(&chronicle);
+
+        assert!(output.contains("## Summary"));
+        assert!(output.contains("| Open TODOs | 23 |"));
+        assert!(output.contains("| Done TODOs | 4 |"));
+        assert!(output.contains("| Repositories | 0 |"));
+        assert!(output.contains("| Commits | 0 |"));
+        assert!(output.contains("| Files Changed | 0 |"));
+        assert!(output.contains("| Lines Changed | +0 / -0 |"));
+        assert!(output.contains("| Words Written | 0 |"));
+        assert!(!output.contains("Compared to"));
+    }
+
+    #[test]
+    fn test_render_summary_with_deltas() {
+        let mut config = create_test_config();
+        config.display.show_deltas = true;
+
+        let chronicle = Chronicle {
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            since: Utc::now(),
+            until: None,
+            generated_at: Utc::now(),
+            host: None,
+            repositories: vec![],
+            todos: vec![],
+            todo_totals: TodoTotals::default(),
+            notes: vec![],
+            notes_omitted: 0,
+            events: vec![],
+            warnings: vec![],
+        };
+
+        let mut previous = chronicle.stats(&config.completed_statuses);
+        previous.commit_count = 3;
+        previous.notes_count = 1;
+        let previous_date = NaiveDate::from_ymd_opt(2024, 1, 14).unwrap();
+
+        let output = Renderer::new(&config)
+            .with_previous(previous_date, previous)
+            .render_summary(&chronicle);
+        assert!(output.contains("*Compared to 2024-01-14: -3 Commits, -1 Note Updates*"));
+
+        let output = Renderer::new(&config)
+            .with_previous(previous_date, chronicle.stats(&config.completed_statuses))
+            .render_summary(&chronicle);
+        assert!(output.contains("*Compared to 2024-01-14: no change*"));
+
+        // Without a previous chronicle the line is omitted
+        let output = Renderer::new(&config).render_summary(&chronicle);
+        assert!(!output.contains("Compared to"));
+    }
+
+    #[test]
+    fn test_render_summary_with_streak() {
+        let mut config = create_test_config();
+
+        let chronicle = Chronicle {
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            since: Utc::now(),
+            until: None,
+            generated_at: Utc::now(),
+            host: None,
+            repositories: vec![],
+            todos: vec![],
+            todo_totals: TodoTotals::default(),
+            notes: vec![],
+            notes_omitted: 0,
+            events: vec![],
+            warnings: vec![],
+        };
+
+        // Off by default
+        let output = Renderer::new(&config)
+            .with_streak(14)
+            .render_summary(&chronicle);
+        assert!(!output.contains("streak"));
+
+        config.display.show_streak = true;
+        let output = Renderer::new(&config)
+            .with_streak(14)
+            .render_summary(&chronicle);
+        assert!(output.ends_with("\n\n*Day 14 streak*"));
+    }
+
+    #[test]
+    fn test_render_events() {
+        let mut config = create_test_config();
+        config.timezone = Some(chrono_tz::Europe::Berlin);
+        let renderer = Renderer::new(&config);
+
+        let at = |text: &str| {
+            DateTime::parse_from_rfc3339(text)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        let event = |summary: &str, start, end, all_day| Event {
+            file: PathBuf::from("work.ics"),
+            summary: summary.to_string(),
+            start,
+            end,
+            all_day,
+            location: None,
+        };
+        let events = vec![
+            Event {
+                location: Some("Room 1".to_string()),
+                ..event(
+                    "Standup",
+                    at("2024-01-15T08:00:00Z"),
+                    Some(at("2024-01-15T08:30:00Z")),
+                    false,
+                )
+            },
+            event("Offsite", at("2024-01-15T23:00:00Z"), None, true),
+            event("Late call", at("2024-01-14T21:00:00Z"), None, false),
+        ];
+
+        let output = renderer.render_events(&events, NaiveDate::from_ymd_opt(2024, 1, 15).unwrap());
+        assert_eq!(
+            output,
+            "## Events\n\n- **09:00–09:30** Standup *(Room 1)*\n- **All day** Offsite\n- **2024-01-14 22:00** Late call\n"
+        );
+    }
+
+    #[test]
+    fn test_render_host() {
+        let config = create_test_config();
+        let renderer = Renderer::new(&config);
+
+        let mut chronicle = Chronicle {
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            since: Utc.with_ymd_and_hms(2024, 1, 14, 12, 0, 0).unwrap(),
+            until: None,
+            generated_at: Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap(),
+            host: None,
+            repositories: vec![],
+            todos: vec![],
+            todo_totals: TodoTotals::default(),
+            notes: vec![],
+            notes_omitted: 0,
+            events: vec![],
+            warnings: vec![],
+        };
+        assert!(!renderer.render(&chronicle).contains("**Host:**"));
+
+        chronicle.host = Some("laptop".to_string());
+        assert!(renderer
+            .render(&chronicle)
+            .contains("**Since:** 2024-01-14 12:00:00 UTC\n**Host:** laptop\n\n## Summary"));
+        assert!(renderer
+            .render_update(&chronicle)
+            .contains("**Since:** 2024-01-14 12:00:00 UTC\n**Host:** laptop"));
+    }
+
+    #[test]
+    fn test_render_update() {
+        let config = create_test_config();
+        let chronicle = Chronicle {
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            since: Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap(),
+            until: None,
+            generated_at: Utc.with_ymd_and_hms(2024, 1, 15, 17, 0, 0).unwrap(),
+            host: None,
+            repositories: vec![repo_with_commits("app", 1)],
+            todos: vec![],
+            todo_totals: TodoTotals::default(),
+            notes: vec![],
+            notes_omitted: 0,
+            events: vec![],
+            warnings: vec![],
+        };
+
+        let output = Renderer::new(&config).render_update(&chronicle);
+        assert!(output.starts_with("---\n\n## Update\n\n**Generated:** 2024-01-15 17:00:00 UTC\n"));
+        assert!(
+            output.contains("*Not counted in the Summary above, which covers the first run only*")
+        );
+        assert!(output.contains("\n### Git Activity\n"));
+        assert!(output.contains("\n#### app\n"));
+        assert!(!output.contains("\n## Git Activity"));
+        assert!(!output.contains("## Summary"));
+    }
+
+    #[test]
+    fn test_render_warnings() {
+        let mut config = create_test_config();
+
+        let chronicle = Chronicle {
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            since: Utc::now(),
+            until: None,
+            generated_at: Utc::now(),
+            host: None,
+            repositories: vec![],
+            todos: vec![],
+            todo_totals: TodoTotals::default(),
+            notes: vec![],
+            notes_omitted: 0,
+            events: vec![],
+            warnings: vec![CollectionWarning::new(
+                "todos",
+                "Skipping TODO file 'gone.md': not found".to_string(),
+            )],
+        };
+
+        // Counted in the Summary, listed only when asked for
+        let output = Renderer::new(&config).render(&chronicle);
+        assert!(output.contains("| Warnings | 1 |"));
+        assert!(!output.contains("## Warnings"));
+
+        config.display.show_warnings = true;
+        let output = Renderer::new(&config).render(&chronicle);
+        assert!(
+            output.ends_with("## Warnings\n\n- **todos**: Skipping TODO file 'gone.md': not found")
+        );
+    }
+
+    #[test]
+    fn test_render_notes_omitted() {
+        let config = create_test_config();
+
+        let chronicle = Chronicle {
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            since: Utc::now(),
+            until: None,
+            generated_at: Utc::now(),
+            host: None,
+            repositories: vec![],
+            todos: vec![],
+            todo_totals: TodoTotals::default(),
+            notes: vec![],
+            notes_omitted: 3,
+            events: vec![],
+            warnings: vec![],
+        };
+
+        let output = Renderer::new(&config).render(&chronicle);
+        assert!(output.contains("## Notes\n\n*... 3 notes omitted (max_notes_total_chars)*"));
+    }
+
+    #[test]
+    fn test_render_omitted_footer() {
+        let config = create_test_config();
+
+        let chronicle = Chronicle {
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            since: Utc::now(),
+            until: None,
+            generated_at: Utc::now(),
+            host: None,
+            repositories: vec![],
+            todos: vec![],
+            todo_totals: TodoTotals::default(),
+            notes: vec![],
+            notes_omitted: 0,
+            events: vec![],
+            warnings: vec![],
+        };
+
+        let output = Renderer::new(&config).render(&chronicle);
+        assert!(!output.contains("omitted"));
+
+        let output = Renderer::new(&config).with_omitted(12).render(&chronicle);
+        assert!(output.ends_with("*... 12 items omitted (max_total_items)*"));
+    }
+
+    #[test]
+    fn test_linkify_issues() {
+        let template = "https://jira.example.com/browse/{key}";
+
+        assert_eq!(
+            linkify_issues("Fix login (PROJ-123), closes #45", template),
+            "Fix login ([PROJ-123](https://jira.example.com/browse/PROJ-123)), \
+             closes [#45](https://jira.example.com/browse/45)"
+        );
+
+        // Not references: lowercase, single letter, embedded, code, links
+        for text in [
+            "proj-123 and X-1",
+            "ABC-123abc and issue#4",
+            "`PROJ-1` stays code",
+            "[see](notes/PROJ-2.md) and [PROJ-3](url)",
+            "a-PROJ-4",
+        ] {
+            assert_eq!(linkify_issues(text, template), text);
+        }
+    }
+
+    #[test]
+    fn test_render_links_issues_when_configured() {
+        let mut config = create_test_config();
+
+        let todo = Todo {
+            content: "Review PROJ-7".to_string(),
+            status: TodoStatus::Pending,
+            change: ChangeKind::New,
+            previous_status: None,
+            previous_content: None,
+            file: PathBuf::from("todo.md"),
+            line: 1,
+            depth: 0,
+            tags: vec![],
+            priority: None,
+            deadline: None,
+            also_in: vec![],
+        };
+
+        let output = Renderer::new(&config).render_todo(&todo, 0);
+        assert!(output.contains("- [ ] Review PROJ-7"));
+
+        config.issue_url_template = Some("https://issues.example.com/{key}".to_string());
+        let output = Renderer::new(&config).render_todo(&todo, 0);
+        assert!(output.contains("- [ ] Review [PROJ-7](https://issues.example.com/PROJ-7)"));
+    }
+
+    #[test]
+    fn test_render_repo_breakdown() {
+        assert_eq!(render_repo_breakdown(&[repo_with_commits("idle", 0)]), "");
+
+        let repositories = vec![
+            repo_with_commits("app", 5),
+            repo_with_commits("website", 20),
+            repo_with_commits("idle", 0),
+            repo_with_commits("cli", 1),
+        ];
+        assert_eq!(
+            render_repo_breakdown(&repositories),
+            format!(
+                "```text\n\
+                 website  {} 20\n\
+                 app      {} 5\n\
+                 cli      {} 1\n\
+                 ```",
+                "█".repeat(20),
+                "█".repeat(5),
+                "█"
+            )
+        );
+
+        let mut config = create_test_config();
+        config.display.show_repo_breakdown = true;
+        let chronicle = Chronicle {
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            since: Utc::now(),
+            until: None,
+            generated_at: Utc::now(),
+            host: None,
+            repositories,
+            todos: vec![],
+            todo_totals: TodoTotals::default(),
+            notes: vec![],
+            notes_omitted: 0,
+            events: vec![],
+            warnings: vec![],
+        };
+        let output = Renderer::new(&config).render_summary(&chronicle);
+        assert!(output.contains(&format!("|\n\n```text\nwebsite  {} 20", "█".repeat(20))));
+    }
+
+    #[test]
+    fn test_render_highlights() {
+        let config = create_test_config();
+        let renderer = Renderer::new(&config);
+
+        let todo = |content: &str, previous| Todo {
+            content: content.to_string(),
+            status: TodoStatus::Done,
+            change: ChangeKind::Modified,
+            previous_status: Some(previous),
+            previous_content: None,
+            file: PathBuf::from("todo.md"),
+            line: 1,
+            depth: 0,
+            tags: vec![],
+            priority: None,
+            deadline: None,
+            also_in: vec![],
+        };
+        let note = |path: &str, word_count| Note {
+            path: PathBuf::from(path),
+            change: ChangeKind::Modified,
+            renamed_from: None,
+            modified_at: Utc::now(),
+            excerpt: String::new(),
+            word_count,
+            char_count: 0,
+        };
+
+        let mut chronicle = Chronicle {
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            since: Utc::now(),
+            until: None,
+            generated_at: Utc::now(),
+            host: None,
+            repositories: vec![],
+            todos: vec![],
+            todo_totals: TodoTotals::default(),
+            notes: vec![],
+            notes_omitted: 0,
+            events: vec![],
+            warnings: vec![],
+        };
+        assert_eq!(renderer.render_highlights(&chronicle), "");
+
+        chronicle.repositories = vec![
+            repo_with_commits("beta", 2),
+            repo_with_commits("alpha", 2),
+            repo_with_commits("gamma", 1),
+        ];
+        chronicle.todos = ["One", "Two", "Three", "Four"]
+            .into_iter()
+            .map(|content| todo(content, TodoStatus::Pending))
+            .chain([todo("Already done", TodoStatus::Done)])
+            .collect();
+        chronicle.notes = vec![note("short.md", 10), note("long.md", 1500)];
+
+        assert_eq!(
+            renderer.render_highlights(&chronicle),
+            "## Highlights\n\n\
+             - Most active repository: **alpha** (2 commits)\n\
+             - Completed: One\n\
+             - Completed: Two\n\
+             - Completed: Three\n\
+             - Largest note update: `long.md` (1,500 words)"
+        );
+
+        // Repositories sharing a name are told apart as in their headings
+        let mut work = repo_with_commits("alpha", 1);
+        work.path = PathBuf::from("/work/alpha");
+        let mut personal = repo_with_commits("alpha", 3);
+        personal.path = PathBuf::from("/personal/alpha");
+        chronicle.repositories = vec![work, personal];
+        assert!(renderer
+            .render_highlights(&chronicle)
+            .contains("- Most active repository: **alpha (personal/alpha)** (3 commits)\n"));
+    }
+
+    #[test]
+    fn test_render_timeline() {
+        let config = create_test_config();
+        let renderer = Renderer::new(&config).with_view(View::Timeline);
+
+        let at = |day: u32, hour: u32| Utc.with_ymd_and_hms(2024, 1, day, hour, 30, 0).unwrap();
+        let commit = |hash: &str, timestamp| Commit {
+            hash: hash.to_string(),
+            message: format!("Commit {}", hash),
+            body: None,
+            author: "Author".to_string(),
+            timestamp,
+            files: vec![],
+            binary_files: vec![],
+            additions: 0,
+            deletions: 0,
+            signed: false,
+        };
+        let repo = |name: &str, branches: Vec<Branch>| Repository {
+            path: PathBuf::from(name),
+            name: name.to_string(),
+            description: None,
+            default_branch: "main".to_string(),
+            current_branch: "main".to_string(),
+            branches,
+            wip: None,
+            lines_added: 0,
+            lines_removed: 0,
+        };
+        let branch = |name: &str, commits| Branch {
+            name: name.to_string(),
+            change: ChangeKind::Modified,
+            ahead: 0,
+            behind: 0,
+            first_seen: None,
+            commits,
+        };
+
+        let chronicle = Chronicle {
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            since: at(14, 0),
+            until: None,
+            generated_at: at(15, 23),
+            host: None,
+            repositories: vec![
+                repo(
+                    "api",
+                    vec![
+                        branch("main", vec![commit("aaa", at(15, 9))]),
+                        branch("feature", vec![commit("aaa", at(15, 9))]),
+                    ],
+                ),
+                repo(
+                    "web",
+                    vec![branch(
+                        "main",
+                        vec![commit("ccc", at(15, 8)), commit("bbb", at(14, 17))],
+                    )],
+                ),
+            ],
+            todos: vec![],
+            todo_totals: TodoTotals::default(),
+            notes: vec![],
+            notes_omitted: 0,
+            events: vec![],
+            warnings: vec![],
+        };
+
+        let output = renderer.render(&chronicle);
+
+        assert!(!output.contains("## Git Activity"));
+        assert!(output.contains(
+            "## Timeline\n\n\
+             ### 2024-01-14\n\n\
+             - 17:30 **web** `bbb` Commit bbb (`main`) — *Author*  \n\n\
+             ### 2024-01-15\n\n\
+             - 08:30 **web** `ccc` Commit ccc (`main`) — *Author*  \n\
+             - 09:30 **api** `aaa` Commit aaa (`main`) — *Author*"
+        ));
+        assert_eq!(output.matches("`aaa`").count(), 1);
+    }
+
+    #[test]
+    fn test_display_names_disambiguate_shared_names() {
+        let repo = |path: &str| Repository {
+            path: PathBuf::from(path),
+            name: Path::new(path)
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .to_string(),
+            description: None,
+            default_branch: "main".to_string(),
+            current_branch: "main".to_string(),
+            branches: vec![],
+            wip: None,
+            lines_added: 0,
+            lines_removed: 0,
+        };
+        let repositories = vec![
+            repo("/home/me/work/app"),
+            repo("/home/me/personal/app"),
+            repo("/src/a/web"),
+            repo("/src/b/a/web"),
+            repo("/home/me/api"),
+        ];
+
+        let names = display_names(&repositories);
+        let name = |path: &str| names[Path::new(path)].as_str();
+
+        assert_eq!(name("/home/me/work/app"), "app (work/app)");
+        assert_eq!(name("/home/me/personal/app"), "app (personal/app)");
+        assert_eq!(name("/src/a/web"), "web (src/a/web)");
+        assert_eq!(name("/src/b/a/web"), "web (b/a/web)");
+        assert_eq!(name("/home/me/api"), "api");
     }
 
     #[test]
@@ -361,14 +2147,28 @@ mod tests {
             status: TodoStatus::Pending,
             change: ChangeKind::New,
             previous_status: None,
+            previous_content: None,
             file: PathBuf::from("todo.md"),
             line: 1,
+            depth: 0,
+            tags: vec![],
+            priority: None,
+            deadline: None,
+            also_in: vec![],
         };
 
-        let output = renderer.render_todo(&todo);
+        let output = renderer.render_todo(&todo, 0);
 
         assert!(output.contains("- [ ] Buy milk"));
-        assert!(output.contains("← NEW"));
+        assert!(output.contains("← **NEW**"));
+
+        let todo = Todo {
+            priority: Some('A'),
+            ..todo
+        };
+        assert!(renderer
+            .render_todo(&todo, 0)
+            .contains("- [ ] (A) Buy milk"));
     }
 
     #[test]
@@ -381,14 +2181,133 @@ mod tests {
             status: TodoStatus::Done,
             change: ChangeKind::Modified,
             previous_status: Some(TodoStatus::Pending),
+            previous_content: None,
             file: PathBuf::from("todo.md"),
             line: 1,
+            depth: 0,
+            tags: vec![],
+            priority: None,
+            deadline: None,
+            also_in: vec![],
         };
 
-        let output = renderer.render_todo(&todo);
+        let output = renderer.render_todo(&todo, 0);
 
         assert!(output.contains("- [x] Buy milk"));
-        assert!(output.contains("← DONE"));
+        assert!(output.contains("← **DONE**"));
+    }
+
+    #[test]
+    fn test_render_todo_completed_statuses() {
+        let mut config = create_test_config();
+        config.completed_statuses = vec![TodoStatus::Done, TodoStatus::InProgress];
+        let renderer = Renderer::new(&config);
+
+        let todo = Todo {
+            content: "Buy milk".to_string(),
+            status: TodoStatus::InProgress,
+            change: ChangeKind::Modified,
+            previous_status: Some(TodoStatus::Pending),
+            previous_content: None,
+            file: PathBuf::from("todo.md"),
+            line: 1,
+            depth: 0,
+            tags: vec![],
+            priority: None,
+            deadline: None,
+            also_in: vec![],
+        };
+        assert!(renderer.render_todo(&todo, 0).contains("← **DONE**"));
+
+        // Already completed, so finishing it is not another completion
+        let finished = Todo {
+            status: TodoStatus::Done,
+            previous_status: Some(TodoStatus::InProgress),
+            ..todo
+        };
+        assert!(renderer
+            .render_todo(&finished, 0)
+            .contains("← **MODIFIED**"));
+    }
+
+    #[test]
+    fn test_render_todo_transitions() {
+        let config = create_test_config();
+        let renderer = Renderer::new(&config);
+
+        let cases = [
+            (
+                TodoStatus::Pending,
+                TodoStatus::InProgress,
+                "[~]",
+                "← **STARTED**",
+            ),
+            (
+                TodoStatus::InProgress,
+                TodoStatus::Done,
+                "[x]",
+                "← **DONE**",
+            ),
+            (
+                TodoStatus::Done,
+                TodoStatus::Pending,
+                "[ ]",
+                "← **REOPENED**",
+            ),
+            (
+                TodoStatus::Done,
+                TodoStatus::InProgress,
+                "[~]",
+                "← **REOPENED**",
+            ),
+            (
+                TodoStatus::InProgress,
+                TodoStatus::Pending,
+                "[ ]",
+                "← **PAUSED**",
+            ),
+        ];
+
+        for (previous, current, checkbox, marker) in cases {
+            let todo = Todo {
+                content: "Buy milk".to_string(),
+                status: current,
+                change: ChangeKind::Modified,
+                previous_status: Some(previous),
+                previous_content: None,
+                file: PathBuf::from("todo.md"),
+                line: 1,
+                depth: 0,
+                tags: vec![],
+                priority: None,
+                deadline: None,
+                also_in: vec![],
+            };
+
+            let output = renderer.render_todo(&todo, 0);
+
+            assert!(output.contains(&format!("- {} Buy milk", checkbox)));
+            assert!(output.contains(marker), "{:?} -> {:?}", previous, current);
+        }
+
+        // Same status, different text
+        let edited = Todo {
+            content: "Buy oat milk".to_string(),
+            status: TodoStatus::Done,
+            change: ChangeKind::Modified,
+            previous_status: Some(TodoStatus::Done),
+            previous_content: Some("Buy milk".to_string()),
+            file: PathBuf::from("todo.md"),
+            line: 1,
+            depth: 0,
+            tags: vec![],
+            priority: None,
+            deadline: None,
+            also_in: vec![],
+        };
+        assert!(renderer
+            .render_todo(&edited, 0)
+            .contains("- [x] Buy oat milk ← **EDITED**"));
     }
 
     #[test]
@@ -399,15 +2318,69 @@ mod tests {
         let note = Note {
             path: PathBuf::from("notes/idea.md"),
             change: ChangeKind::New,
+            renamed_from: None,
             modified_at: Utc::now(),
             excerpt: "This is a great idea.".to_string(),
+            word_count: 5,
+            char_count: 21,
         };
 
         let output = renderer.render_note(&note);
 
         assert!(output.contains("### `notes/idea.md`"));
-        assert!(output.contains("← new"));
+        assert!(output.contains("← **new**"));
         assert!(output.contains("This is a great idea."));
+        assert!(output.contains("*5 words*"));
+    }
+
+    #[test]
+    fn test_render_renamed_note() {
+        let config = create_test_config();
+        let renderer = Renderer::new(&config);
+
+        let mut note = Note {
+            path: PathBuf::from("notes/big-idea.md"),
+            change: ChangeKind::Modified,
+            renamed_from: Some(PathBuf::from("notes/idea.md")),
+            modified_at: Utc::now(),
+            excerpt: "This is a great idea.".to_string(),
+            word_count: 5,
+            char_count: 21,
+        };
+
+        let output = renderer.render_note(&note);
+        assert!(output.contains("### `notes/big-idea.md` ← **renamed** from `idea.md`"));
+
+        note.renamed_from = Some(PathBuf::from("inbox/idea.md"));
+        let output = renderer.render_note(&note);
+        assert!(output.contains("← **renamed** from `inbox/idea.md`"));
+    }
+
+    #[test]
+    fn test_render_removed_note() {
+        let config = create_test_config();
+        let renderer = Renderer::new(&config);
+
+        let note = Note {
+            path: PathBuf::from("notes/old.md"),
+            change: ChangeKind::Removed,
+            renamed_from: None,
+            modified_at: Utc::now(),
+            excerpt: String::new(),
+            word_count: 0,
+            char_count: 0,
+        };
+
+        let output = renderer.render_note(&note);
+        assert_eq!(output, "### `notes/old.md` ← **removed**\n");
+    }
+
+    #[test]
+    fn test_format_count() {
+        assert_eq!(format_count(0), "0");
+        assert_eq!(format_count(999), "999");
+        assert_eq!(format_count(1234), "1,234");
+        assert_eq!(format_count(1234567), "1,234,567");
     }
 
     #[test]
@@ -420,23 +2393,380 @@ mod tests {
             change: ChangeKind::New,
             ahead: 2,
             behind: 0,
+            first_seen: None,
             commits: vec![Commit {
                 hash: "abc1234".to_string(),
                 message: "Add feature".to_string(),
+                body: None,
                 author: "Test Author".to_string(),
                 timestamp: Utc::now(),
                 files: vec![],
+                binary_files: vec![],
+                additions: 0,
+                deletions: 0,
+                signed: false,
             }],
         };
 
-        let output = renderer.render_branch(&branch, "main");
+        let output = renderer.render_branch(
+            &branch,
+            "main",
+            "main",
+            &config.display,
+            config.limits.max_changed_files,
+        );
 
-        assert!(output.contains("#### `feature`"));
-        assert!(output.contains("← NEW"));
+        assert!(output.contains("#### `feature` (ahead 2"));
+        assert!(output.contains("← **NEW**"));
         assert!(output.contains("ahead 2"));
+
+        let output = renderer.render_branch(
+            &branch,
+            "main",
+            "feature",
+            &config.display,
+            config.limits.max_changed_files,
+        );
+        assert!(output.contains("#### `feature` (current) (ahead 2, behind 0)"));
         assert!(output.contains("`abc1234` Add feature"));
     }
 
+    #[test]
+    fn test_render_branch_counts_files_beyond_limit() {
+        let mut config = create_test_config();
+        config.limits.max_changed_files = 5;
+        let renderer = Renderer::new(&config);
+
+        let commit = |hash: &str, files: &[&str]| Commit {
+            hash: hash.to_string(),
+            message: "Change".to_string(),
+            body: None,
+            author: "Test Author".to_string(),
+            timestamp: Utc::now(),
+            files: files.iter().map(PathBuf::from).collect(),
+            binary_files: vec![],
+            additions: 0,
+            deletions: 0,
+            signed: false,
+        };
+        let branch = Branch {
+            name: "main".to_string(),
+            change: ChangeKind::Modified,
+            ahead: 0,
+            behind: 0,
+            first_seen: None,
+            commits: vec![
+                commit("aaa1111", &["a.rs", "b.rs"]),
+                commit("bbb2222", &["b.rs", "c.rs", "d.rs"]),
+            ],
+        };
+
+        let output = renderer.render_branch(&branch, "main", "main", &config.display, 3);
+
+        assert!(output.contains("Changed files (4)"));
+        assert_eq!(output.matches("- `").count(), 2 + 3);
+        assert!(output.contains("*... and 1 more files*"));
+    }
+
+    #[test]
+    fn test_render_changed_files_sorted() {
+        let files = [
+            PathBuf::from("src/main.rs"),
+            PathBuf::from("README.md"),
+            PathBuf::from("src/cli/gen.rs"),
+        ];
+        let refs: Vec<&PathBuf> = files.iter().collect();
+
+        let output = Renderer::render_changed_files(&refs, 0, 10);
+
+        let listed: Vec<&str> = output
+            .lines()
+            .filter_map(|line| line.strip_prefix("- `"))
+            .collect();
+        assert_eq!(
+            listed,
+            vec!["README.md`", "src/cli/gen.rs`", "src/main.rs`"]
+        );
+    }
+
+    #[test]
+    fn test_render_branch_gitmoji() {
+        let mut config = create_test_config();
+        let branch = Branch {
+            name: "main".to_string(),
+            change: ChangeKind::Modified,
+            ahead: 0,
+            behind: 0,
+            first_seen: None,
+            commits: vec![Commit {
+                hash: "abc1234".to_string(),
+                message: ":sparkles: Add export :unknown:".to_string(),
+                body: None,
+                author: "Test Author".to_string(),
+                timestamp: Utc::now(),
+                files: vec![],
+                binary_files: vec![],
+                additions: 0,
+                deletions: 0,
+                signed: false,
+            }],
+        };
+
+        let output = Renderer::new(&config).render_branch(
+            &branch,
+            "main",
+            "main",
+            &config.display,
+            config.limits.max_changed_files,
+        );
+        assert!(output.contains("`abc1234` :sparkles: Add export :unknown:"));
+
+        config.display.render_gitmoji = true;
+        let output = Renderer::new(&config).render_branch(
+            &branch,
+            "main",
+            "main",
+            &config.display,
+            config.limits.max_changed_files,
+        );
+        assert!(output.contains("`abc1234` ✨ Add export :unknown:"));
+    }
+
+    #[test]
+    fn test_render_branch_signatures() {
+        let mut config = create_test_config();
+        let commit = |hash: &str, signed| Commit {
+            hash: hash.to_string(),
+            message: "Change".to_string(),
+            body: None,
+            author: "Test Author".to_string(),
+            timestamp: Utc::now(),
+            files: vec![],
+            binary_files: vec![],
+            additions: 0,
+            deletions: 0,
+            signed,
+        };
+        let branch = Branch {
+            name: "main".to_string(),
+            change: ChangeKind::Modified,
+            ahead: 0,
+            behind: 0,
+            first_seen: None,
+            commits: vec![commit("aaa1111", true), commit("bbb2222", false)],
+        };
+        let render = |config: &Config| {
+            Renderer::new(config).render_branch(
+                &branch,
+                "main",
+                "main",
+                &config.display,
+                config.limits.max_changed_files,
+            )
+        };
+
+        assert!(!render(&config).contains('🔏'));
+
+        config.display.show_signatures = true;
+        let output = render(&config);
+        assert!(output.contains("- `aaa1111` 🔏 Change"));
+        assert!(output.contains("- `bbb2222` Change"));
+    }
+
+    #[test]
+    fn test_render_branch_age() {
+        let config = create_test_config();
+        let renderer = Renderer::new(&config);
+
+        let mut branch = Branch {
+            name: "feature".to_string(),
+            change: ChangeKind::Modified,
+            ahead: 1,
+            behind: 0,
+            first_seen: Some(Utc::now() - chrono::Duration::days(14)),
+            commits: vec![],
+        };
+
+        let output = renderer.render_branch(
+            &branch,
+            "main",
+            "main",
+            &config.display,
+            config.limits.max_changed_files,
+        );
+        assert!(output.contains("#### `feature` (ahead 1, behind 0) (14 days old)"));
+
+        // The default branch and branches seen today have no age
+        let output = renderer.render_branch(
+            &branch,
+            "feature",
+            "feature",
+            &config.display,
+            config.limits.max_changed_files,
+        );
+        assert!(!output.contains("old"));
+        branch.first_seen = Some(Utc::now());
+        let output = renderer.render_branch(
+            &branch,
+            "main",
+            "main",
+            &config.display,
+            config.limits.max_changed_files,
+        );
+        assert!(!output.contains("old"));
+    }
+
+    #[test]
+    fn test_render_summary_warns_about_stale_branches() {
+        let mut config = create_test_config();
+        config.display.stale_branch_days = Some(30);
+
+        let now = Utc::now();
+        let branch = |name: &str, days: i64| Branch {
+            name: name.to_string(),
+            change: ChangeKind::Modified,
+            ahead: 0,
+            behind: 0,
+            first_seen: Some(now - chrono::Duration::days(days)),
+            commits: vec![],
+        };
+        let chronicle = Chronicle {
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            since: now,
+            until: None,
+            generated_at: now,
+            host: None,
+            repositories: vec![Repository {
+                path: PathBuf::from("/repo"),
+                name: "repo".to_string(),
+                description: None,
+                default_branch: "main".to_string(),
+                current_branch: "main".to_string(),
+                branches: vec![branch("main", 90), branch("old", 45), branch("fresh", 3)],
+                wip: None,
+                lines_added: 0,
+                lines_removed: 0,
+            }],
+            todos: vec![],
+            todo_totals: TodoTotals::default(),
+            notes: vec![],
+            notes_omitted: 0,
+            events: vec![],
+            warnings: vec![],
+        };
+
+        let output = Renderer::new(&config).render_summary(&chronicle);
+        assert!(output.ends_with("**Stale branches** (over 30 days): `repo/old` (45 days old)"));
+
+        // Repositories sharing a name are told apart as in their headings
+        let mut namesakes = chronicle.clone();
+        namesakes.repositories[0].path = PathBuf::from("/work/repo");
+        let mut personal = namesakes.repositories[0].clone();
+        personal.path = PathBuf::from("/personal/repo");
+        personal.branches.truncate(1);
+        namesakes.repositories.push(personal);
+        let output = Renderer::new(&config).render_summary(&namesakes);
+        assert!(output.ends_with("`repo (work/repo)/old` (45 days old)"));
+
+        config.display.stale_branch_days = None;
+        let output = Renderer::new(&config).render_summary(&chronicle);
+        assert!(!output.contains("Stale"));
+    }
+
+    #[test]
+    fn test_render_branch_groups_binary_files() {
+        let mut config = create_test_config();
+        let branch = Branch {
+            name: "main".to_string(),
+            change: ChangeKind::Modified,
+            ahead: 0,
+            behind: 0,
+            first_seen: None,
+            commits: vec![Commit {
+                hash: "abc1234".to_string(),
+                message: "Add logo".to_string(),
+                body: None,
+                author: "Test Author".to_string(),
+                timestamp: Utc::now(),
+                files: vec![PathBuf::from("README.md")],
+                binary_files: vec![PathBuf::from("logo.png"), PathBuf::from("guide.pdf")],
+                additions: 0,
+                deletions: 0,
+                signed: false,
+            }],
+        };
+
+        let output = Renderer::new(&config).render_branch(
+            &branch,
+            "main",
+            "main",
+            &config.display,
+            config.limits.max_changed_files,
+        );
+        assert!(output.contains("<summary>Changed files (3)</summary>"));
+        assert!(output.contains("- `README.md`\n- *2 binary files*\n"));
+        assert!(!output.contains("logo.png"));
+
+        config.display.hide_binary_files = true;
+        let output = Renderer::new(&config).render_branch(
+            &branch,
+            "main",
+            "main",
+            &config.display,
+            config.limits.max_changed_files,
+        );
+        assert!(output.contains("<summary>Changed files (1)</summary>"));
+        assert!(!output.contains("binary"));
+    }
+
+    #[test]
+    fn test_render_branch_wraps_long_commit_lines() {
+        let mut config = create_test_config();
+        config.display.show_authors = false;
+        config.display.max_line_width = Some(24);
+        let renderer = Renderer::new(&config);
+
+        let branch = Branch {
+            name: "main".to_string(),
+            change: ChangeKind::Modified,
+            ahead: 0,
+            behind: 0,
+            first_seen: None,
+            commits: vec![Commit {
+                hash: "abc1234".to_string(),
+                message: "Refactor the collector pipeline".to_string(),
+                body: None,
+                author: "Alice".to_string(),
+                timestamp: Utc::now(),
+                files: vec![],
+                binary_files: vec![],
+                additions: 0,
+                deletions: 0,
+                signed: false,
+            }],
+        };
+
+        let output = renderer.render_branch(
+            &branch,
+            "main",
+            "main",
+            &config.display,
+            config.limits.max_changed_files,
+        );
+
+        assert!(output.contains("- `abc1234` Refactor the\n  collector pipeline  \n"));
+    }
+
+    #[test]
+    fn test_wrap_line() {
+        assert_eq!(wrap_line("- short", 20), "- short");
+        assert_eq!(wrap_line("- one two three", 9), "- one two\n  three");
+        assert_eq!(
+            wrap_line("- averyveryverylongword x", 5),
+            "-\n  averyveryverylongword\n  x"
+        );
+    }
+
     #[test]
     fn test_render_with_author() {
         let mut config = create_test_config();
@@ -448,16 +2778,28 @@ mod tests {
             change: ChangeKind::Modified,
             ahead: 0,
             behind: 0,
+            first_seen: None,
             commits: vec![Commit {
                 hash: "abc1234".to_string(),
                 message: "Fix bug".to_string(),
+                body: None,
                 author: "Alice".to_string(),
                 timestamp: Utc::now(),
                 files: vec![],
+                binary_files: vec![],
+                additions: 0,
+                deletions: 0,
+                signed: false,
             }],
         };
 
-        let output = renderer.render_branch(&branch, "main");
+        let output = renderer.render_branch(
+            &branch,
+            "main",
+            "main",
+            &config.display,
+            config.limits.max_changed_files,
+        );
 
         assert!(output.contains("— *Alice*"));
     }
@@ -473,17 +2815,226 @@ mod tests {
             change: ChangeKind::Modified,
             ahead: 0,
             behind: 0,
+            first_seen: None,
             commits: vec![Commit {
                 hash: "abc1234".to_string(),
                 message: "Fix bug".to_string(),
+                body: None,
                 author: "Alice".to_string(),
                 timestamp: Utc::now(),
                 files: vec![],
+                binary_files: vec![],
+                additions: 0,
+                deletions: 0,
+                signed: false,
+            }],
+        };
+
+        let output = renderer.render_branch(
+            &branch,
+            "main",
+            "main",
+            &config.display,
+            config.limits.max_changed_files,
+        );
+
+        assert!(!output.contains("Alice"));
+    }
+
+    #[test]
+    fn test_render_repository_with_per_repo_author_override() {
+        let mut config = create_test_config();
+        config.display.show_authors = true;
+        config.repos = vec![crate::config::RepoConfig {
+            show_authors: Some(false),
+            .."/test/repo".into()
+        }];
+        let renderer = Renderer::new(&config);
+
+        let repo = Repository {
+            path: PathBuf::from("/test/repo"),
+            name: "repo".to_string(),
+            description: None,
+            default_branch: "main".to_string(),
+            current_branch: "main".to_string(),
+            branches: vec![Branch {
+                name: "main".to_string(),
+                change: ChangeKind::Modified,
+                ahead: 0,
+                behind: 0,
+                first_seen: None,
+                commits: vec![Commit {
+                    hash: "abc1234".to_string(),
+                    message: "Fix bug".to_string(),
+                    body: None,
+                    author: "Alice".to_string(),
+                    timestamp: Utc::now(),
+                    files: vec![],
+                    binary_files: vec![],
+                    additions: 0,
+                    deletions: 0,
+                    signed: false,
+                }],
             }],
+            wip: None,
+            lines_added: 0,
+            lines_removed: 0,
         };
 
-        let output = renderer.render_branch(&branch, "main");
+        let output = renderer.render_repository(&repo, &repo.name);
 
+        assert!(output.contains("Fix bug"));
         assert!(!output.contains("Alice"));
     }
+
+    #[test]
+    fn test_render_repository_description() {
+        let config = create_test_config();
+        let renderer = Renderer::new(&config);
+
+        let repo = Repository {
+            path: PathBuf::from("/test/repo"),
+            name: "repo".to_string(),
+            description: Some("Internal tools".to_string()),
+            default_branch: "main".to_string(),
+            current_branch: "main".to_string(),
+            branches: vec![],
+            wip: None,
+            lines_added: 0,
+            lines_removed: 0,
+        };
+
+        let output = renderer.render_repository(&repo, &repo.name);
+
+        assert!(output.starts_with("### repo\n\n*Internal tools*\n\n**Path:** `/test/repo`"));
+
+        let repo = Repository {
+            description: Some(format!("The *fast* my_app {}", "word ".repeat(30))),
+            ..repo
+        };
+        let output = renderer.render_repository(&repo, &repo.name);
+        let description = output.lines().nth(2).unwrap();
+        assert!(description.starts_with("*The \\*fast\\* my\\_app word"));
+        assert!(description.ends_with("word...*"));
+    }
+
+    #[test]
+    fn test_render_repository_grouped_by_author() {
+        let mut config = create_test_config();
+        config.display.group_by_author = true;
+        let renderer = Renderer::new(&config);
+
+        let commit = |hash: &str, message: &str, author: &str| Commit {
+            hash: hash.to_string(),
+            message: message.to_string(),
+            body: None,
+            author: author.to_string(),
+            timestamp: Utc::now(),
+            files: vec![],
+            binary_files: vec![],
+            additions: 0,
+            deletions: 0,
+            signed: false,
+        };
+
+        let repo = Repository {
+            path: PathBuf::from("/test/repo"),
+            name: "repo".to_string(),
+            description: None,
+            default_branch: "main".to_string(),
+            current_branch: "main".to_string(),
+            branches: vec![
+                Branch {
+                    name: "main".to_string(),
+                    change: ChangeKind::Modified,
+                    ahead: 0,
+                    behind: 0,
+                    first_seen: None,
+                    commits: vec![
+                        commit("aaa1111", "Fix bug", "Alice"),
+                        commit("bbb2222", "Add docs", "Bob"),
+                    ],
+                },
+                Branch {
+                    name: "feature".to_string(),
+                    change: ChangeKind::New,
+                    ahead: 2,
+                    behind: 0,
+                    first_seen: None,
+                    commits: vec![
+                        commit("ccc3333", "Add feature", "Bob"),
+                        commit("aaa1111", "Fix bug", "Alice"),
+                    ],
+                },
+            ],
+            wip: None,
+            lines_added: 0,
+            lines_removed: 0,
+        };
+
+        let output = renderer.render_repository(&repo, &repo.name);
+
+        let bob = output.find("#### Bob (2 commits)").unwrap();
+        let alice = output.find("#### Alice (1 commit)").unwrap();
+        assert!(bob < alice);
+        assert!(output.contains("- `ccc3333` Add feature (`feature`)"));
+        assert_eq!(output.matches("aaa1111").count(), 1);
+        assert!(!output.contains("#### `main`"));
+    }
+
+    #[test]
+    fn test_render_repository_work_in_progress() {
+        let config = create_test_config();
+        let renderer = Renderer::new(&config);
+
+        let repo = Repository {
+            path: PathBuf::from("/test/repo"),
+            name: "repo".to_string(),
+            description: None,
+            default_branch: "main".to_string(),
+            current_branch: "main".to_string(),
+            branches: vec![],
+            wip: Some(WorkInProgress {
+                modified: 3,
+                added: 1,
+                deleted: 0,
+            }),
+            lines_added: 0,
+            lines_removed: 0,
+        };
+
+        let output = renderer.render_repository(&repo, &repo.name);
+
+        assert!(output.contains("**WIP:** uncommitted changes (3 modified, 1 added)"));
+        assert!(!output.contains("####"));
+    }
+
+    #[test]
+    fn test_truncate_message_at_word_boundary() {
+        assert_eq!(truncate_message("Fix the bug", 72), "Fix the bug");
+        assert_eq!(truncate_message("Fix the bug", 11), "Fix the bug");
+        assert_eq!(
+            truncate_message("Refactor the parser for better errors", 20),
+            "Refactor the..."
+        );
+        assert_eq!(
+            truncate_message("Refactor the parser", 16),
+            "Refactor the..."
+        );
+
+        // No space to cut at: cut mid-word
+        assert_eq!(truncate_message("Supercalifragilistic", 10), "Superca...");
+        assert_eq!(truncate_message("Überraschungsei öffnen", 8), "Überr...");
+    }
+
+    #[test]
+    fn test_commit_message_truncated_at_render_time() {
+        let mut config = create_test_config();
+        config.limits.max_commit_message_chars = 20;
+        let renderer = Renderer::new(&config);
+
+        let message = "Refactor the parser for better errors";
+        assert_eq!(renderer.commit_message(message), "Refactor the...");
+        assert_eq!(renderer.commit_message("Short"), "Short");
+    }
 }
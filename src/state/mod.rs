@@ -1,37 +1,64 @@
 //! State persistence module
 //!
 //! Tracks "last run" timestamps per source to enable incremental updates.
-//! Stores state in JSON format (.chronicle-state.json).
+//! Stores state in JSON format (.chronicle-state.json), optionally
+//! gzip-compressed.
 
 pub mod types;
 
-pub use types::{BranchState, SourceState, State};
+pub use types::{BranchState, NoteFileState, SourceState, State, TodoItemState};
 
 use crate::error::{ChronicleError, Result};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use std::fs;
-use std::path::Path;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Magic bytes at the start of every gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Starts a key holding a percent-encoded non-UTF-8 path; NUL never
+/// appears in a real path, so these can't collide with plain keys
+const ENCODED_KEY_PREFIX: char = '\0';
 
 /// Load state from JSON file, returning default state if file doesn't exist
+///
+/// Gzip-compressed files are detected by their magic bytes and decompressed
+/// transparently.
 pub fn load(path: &Path) -> Result<State> {
     if !path.exists() {
         return Ok(State::default());
     }
 
-    let content = fs::read_to_string(path).map_err(|e| {
+    let read_error = |e: std::io::Error| {
         ChronicleError::State(format!(
             "Cannot read state from '{}': {}",
             path.display(),
             e
         ))
-    })?;
+    };
+
+    let bytes = fs::read(path).map_err(read_error)?;
+
+    let state: State = if bytes.starts_with(&GZIP_MAGIC) {
+        let mut content = String::new();
+        GzDecoder::new(bytes.as_slice())
+            .read_to_string(&mut content)
+            .map_err(read_error)?;
+        serde_json::from_str(&content)?
+    } else {
+        serde_json::from_slice(&bytes)?
+    };
 
-    let state: State = serde_json::from_str(&content)?;
     Ok(state)
 }
 
-/// Save state to JSON file with pretty formatting
-pub fn save(state: &State, path: &Path) -> Result<()> {
+/// Save state to JSON file with pretty formatting, or as compact gzipped
+/// JSON when `compress` is set
+pub fn save(state: &State, path: &Path, compress: bool) -> Result<()> {
     // Update last_updated timestamp
     let mut updated_state = state.clone();
     updated_state.last_updated = Utc::now();
@@ -43,10 +70,21 @@ pub fn save(state: &State, path: &Path) -> Result<()> {
         }
     }
 
-    let json = serde_json::to_string_pretty(&updated_state)?;
-    fs::write(path, json).map_err(|e| {
+    let write_error = |e: std::io::Error| {
         ChronicleError::State(format!("Cannot write state to '{}': {}", path.display(), e))
-    })?;
+    };
+
+    let content = if compress {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(&serde_json::to_vec(&updated_state)?)
+            .map_err(write_error)?;
+        encoder.finish().map_err(write_error)?
+    } else {
+        serde_json::to_vec_pretty(&updated_state)?
+    };
+
+    fs::write(path, content).map_err(write_error)?;
 
     Ok(())
 }
@@ -56,11 +94,80 @@ pub fn get_source<'a>(state: &'a State, source_name: &str) -> Option<&'a SourceS
     state.sources.get(source_name)
 }
 
+/// State key for a path: the path itself when it is valid UTF-8, else its
+/// raw bytes with the invalid ones percent-encoded
+///
+/// Unlike `to_string_lossy`, distinct paths never share a key, and the key
+/// converts back with [`key_path`].
+pub fn path_key(path: &Path) -> String {
+    if let Some(key) = path.to_str() {
+        return key.to_string();
+    }
+
+    let mut key = String::from(ENCODED_KEY_PREFIX);
+    for chunk in path.as_os_str().as_encoded_bytes().utf8_chunks() {
+        key.push_str(&chunk.valid().replace('%', "%25"));
+        for byte in chunk.invalid() {
+            key.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    key
+}
+
+/// Path a state key was made from (see [`path_key`])
+pub fn key_path(key: &str) -> PathBuf {
+    let Some(encoded) = key.strip_prefix(ENCODED_KEY_PREFIX) else {
+        return PathBuf::from(key);
+    };
+
+    let mut bytes = Vec::new();
+    let mut rest = encoded.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let escaped = (byte == b'%')
+            .then(|| tail.get(..2))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                bytes.push(byte);
+                rest = &tail[2..];
+            }
+            None => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    path_from_bytes(bytes)
+}
+
+#[cfg(unix)]
+fn path_from_bytes(bytes: Vec<u8>) -> PathBuf {
+    use std::os::unix::ffi::OsStringExt;
+    PathBuf::from(std::ffi::OsString::from_vec(bytes))
+}
+
+/// Elsewhere non-UTF-8 paths are ill-formed UTF-16, which only round-trips
+/// approximately
+#[cfg(not(unix))]
+fn path_from_bytes(bytes: Vec<u8>) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(&bytes).into_owned())
+}
+
 /// Update state for a specific source
 pub fn update_source(state: &mut State, source_name: String, source_state: SourceState) {
     state.sources.insert(source_name, source_state);
 }
 
+/// Time of the last run: when a chronicle was last generated, or for older
+/// state files the most recent `last_checked` across all sources
+pub fn last_run(state: &State) -> Option<DateTime<Utc>> {
+    state
+        .last_generated
+        .or_else(|| state.sources.values().map(SourceState::last_checked).max())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -91,7 +198,7 @@ mod tests {
         };
         update_source(&mut state, "test-repo".to_string(), git_state);
 
-        save(&state, &state_path).unwrap();
+        save(&state, &state_path, false).unwrap();
         assert!(state_path.exists());
 
         let loaded = load(&state_path).unwrap();
@@ -100,6 +207,32 @@ mod tests {
         assert!(loaded.sources.contains_key("test-repo"));
     }
 
+    #[test]
+    fn test_save_and_load_compressed_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let state_path = temp_dir.path().join("state.json");
+
+        let mut state = State::default();
+        let git_state = SourceState::Git {
+            last_checked: Utc::now(),
+            default_branch: "main".to_string(),
+            branches: HashMap::new(),
+        };
+        update_source(&mut state, "test-repo".to_string(), git_state);
+
+        save(&state, &state_path, true).unwrap();
+        assert!(fs::read(&state_path).unwrap().starts_with(&GZIP_MAGIC));
+
+        let loaded = load(&state_path).unwrap();
+        assert_eq!(loaded.sources.len(), 1);
+        assert!(loaded.sources.contains_key("test-repo"));
+
+        // Switching compression off again rewrites plain JSON
+        save(&loaded, &state_path, false).unwrap();
+        assert!(fs::read_to_string(&state_path).unwrap().starts_with('{'));
+        assert_eq!(load(&state_path).unwrap().sources.len(), 1);
+    }
+
     #[test]
     fn test_save_creates_parent_directory() {
         let temp_dir = TempDir::new().unwrap();
@@ -110,7 +243,7 @@ mod tests {
             .join("state.json");
 
         let state = State::default();
-        save(&state, &state_path).unwrap();
+        save(&state, &state_path, false).unwrap();
         assert!(state_path.exists());
     }
 
@@ -125,7 +258,7 @@ mod tests {
         // Small delay to ensure timestamp changes
         std::thread::sleep(std::time::Duration::from_millis(10));
 
-        save(&original_state, &state_path).unwrap();
+        save(&original_state, &state_path, false).unwrap();
         let loaded = load(&state_path).unwrap();
 
         assert!(loaded.last_updated > original_time);
@@ -156,7 +289,11 @@ mod tests {
         let todo_state = SourceState::Todo {
             last_checked: Utc::now(),
             last_modified: Utc::now(),
-            item_hashes: vec!["hash1".to_string()],
+            items: vec![TodoItemState {
+                content: "Task".to_string(),
+                status: crate::models::TodoStatus::Pending,
+                line: 1,
+            }],
         };
         update_source(&mut state, "todo.txt".to_string(), todo_state);
 
@@ -164,6 +301,65 @@ mod tests {
         assert!(state.sources.contains_key("todo.txt"));
     }
 
+    #[test]
+    fn test_last_run() {
+        let mut state = State::default();
+        assert!(last_run(&state).is_none());
+
+        let earlier = Utc::now() - chrono::Duration::hours(5);
+        let later = Utc::now() - chrono::Duration::hours(1);
+
+        update_source(
+            &mut state,
+            "repo".to_string(),
+            SourceState::Git {
+                last_checked: earlier,
+                default_branch: "main".to_string(),
+                branches: HashMap::new(),
+            },
+        );
+        update_source(
+            &mut state,
+            "notes".to_string(),
+            SourceState::Notes {
+                last_checked: later,
+                files: HashMap::new(),
+            },
+        );
+
+        assert_eq!(last_run(&state), Some(later));
+
+        // An explicit generation time takes precedence
+        let generated = Utc::now() - chrono::Duration::hours(3);
+        state.last_generated = Some(generated);
+        assert_eq!(last_run(&state), Some(generated));
+    }
+
+    #[test]
+    fn test_path_key_keeps_utf8_paths() {
+        let path = Path::new("/notes/café 100%.md");
+        assert_eq!(path_key(path), "/notes/café 100%.md");
+        assert_eq!(key_path(&path_key(path)), path);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_path_key_non_utf8_paths() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let latin1 = Path::new(OsStr::from_bytes(b"/notes/caf\xe9 100%.md"));
+        let other = Path::new(OsStr::from_bytes(b"/notes/caf\xe8 100%.md"));
+
+        // Lossy conversion would give both the same key
+        assert_eq!(latin1.to_string_lossy(), other.to_string_lossy());
+        assert_ne!(path_key(latin1), path_key(other));
+        assert_eq!(path_key(latin1), "\0/notes/caf%E9 100%25.md");
+
+        assert_eq!(key_path(&path_key(latin1)), latin1);
+        assert_eq!(key_path(&path_key(other)), other);
+    }
+
     #[test]
     fn test_load_invalid_json() {
         let temp_dir = TempDir::new().unwrap();
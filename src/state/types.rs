@@ -1,7 +1,9 @@
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
 
+use crate::models::TodoStatus;
+
 /// State tracking for incremental updates
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct State {
@@ -11,6 +13,14 @@ pub struct State {
     /// Last time state was updated
     pub last_updated: DateTime<Utc>,
 
+    /// Last time `gen` wrote a chronicle (None for older state files)
+    #[serde(default)]
+    pub last_generated: Option<DateTime<Utc>>,
+
+    /// Machine that wrote the last chronicle, with `include_host`
+    #[serde(default)]
+    pub last_host: Option<String>,
+
     /// Per-source state tracking
     pub sources: HashMap<String, SourceState>,
 }
@@ -29,15 +39,28 @@ pub enum SourceState {
     Todo {
         last_checked: DateTime<Utc>,
         last_modified: DateTime<Utc>,
-        item_hashes: Vec<String>,
+        #[serde(alias = "item_hashes", deserialize_with = "deserialize_todo_items")]
+        items: Vec<TodoItemState>,
     },
     #[serde(rename = "notes")]
     Notes {
         last_checked: DateTime<Utc>,
-        files: HashMap<String, DateTime<Utc>>,
+        #[serde(deserialize_with = "deserialize_note_files")]
+        files: HashMap<String, NoteFileState>,
     },
 }
 
+impl SourceState {
+    /// When this source was last checked
+    pub fn last_checked(&self) -> DateTime<Utc> {
+        match self {
+            SourceState::Git { last_checked, .. }
+            | SourceState::Todo { last_checked, .. }
+            | SourceState::Notes { last_checked, .. } => *last_checked,
+        }
+    }
+}
+
 /// State for a Git branch
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BranchState {
@@ -51,11 +74,122 @@ pub struct BranchState {
     pub first_seen: Option<DateTime<Utc>>,
 }
 
+/// State for a single TODO item
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TodoItemState {
+    /// TODO content text
+    pub content: String,
+
+    /// Status when last seen
+    pub status: TodoStatus,
+
+    /// Line number in file when last seen
+    pub line: usize,
+}
+
+/// State for a single note file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoteFileState {
+    /// Modification time when last seen
+    pub modified_at: DateTime<Utc>,
+
+    /// Hash of the file content (for rename detection; None for older state)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+}
+
+/// A note file as stored on disk: structured, or a bare modification time
+/// from older state files
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredNoteFile {
+    File(NoteFileState),
+    Legacy(DateTime<Utc>),
+}
+
+/// Deserialize note files, migrating bare timestamps on the fly
+fn deserialize_note_files<'de, D>(
+    deserializer: D,
+) -> Result<HashMap<String, NoteFileState>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let stored = HashMap::<String, StoredNoteFile>::deserialize(deserializer)?;
+
+    Ok(stored
+        .into_iter()
+        .map(|(path, file)| {
+            let file = match file {
+                StoredNoteFile::File(file) => file,
+                StoredNoteFile::Legacy(modified_at) => NoteFileState {
+                    modified_at,
+                    content_hash: None,
+                },
+            };
+            (path, file)
+        })
+        .collect())
+}
+
+/// A TODO item as stored on disk: structured, or a legacy
+/// `"Status:file:line:content"` hash string from older state files
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredTodoItem {
+    Item(TodoItemState),
+    Legacy(String),
+}
+
+/// Deserialize TODO items, migrating legacy hash strings on the fly
+fn deserialize_todo_items<'de, D>(deserializer: D) -> Result<Vec<TodoItemState>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let stored = Vec::<StoredTodoItem>::deserialize(deserializer)?;
+
+    Ok(stored
+        .into_iter()
+        .filter_map(|item| match item {
+            StoredTodoItem::Item(item) => Some(item),
+            StoredTodoItem::Legacy(hash) => parse_legacy_hash(&hash),
+        })
+        .collect())
+}
+
+/// Parse a legacy `"Status:file:line:content"` hash into a structured item
+///
+/// The file path may itself contain `:` (e.g. `C:\todo.md`), so the line
+/// number is taken from the first `:<digits>:` segment after the status.
+fn parse_legacy_hash(hash: &str) -> Option<TodoItemState> {
+    let (status, rest) = hash.split_once(':')?;
+    let status = match status {
+        "Pending" => TodoStatus::Pending,
+        "Done" => TodoStatus::Done,
+        "InProgress" => TodoStatus::InProgress,
+        _ => return None,
+    };
+
+    rest.match_indices(':').find_map(|(pos, _)| {
+        let (line, content) = rest[pos + 1..].split_once(':')?;
+        if line.is_empty() || !line.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        Some(TodoItemState {
+            content: content.to_string(),
+            status,
+            line: line.parse().ok()?,
+        })
+    })
+}
+
 impl Default for State {
     fn default() -> Self {
         Self {
             version: "1.0".to_string(),
             last_updated: Utc::now(),
+            last_generated: None,
+            last_host: None,
             sources: HashMap::new(),
         }
     }
@@ -80,6 +214,13 @@ mod tests {
         assert_eq!(parsed.version, "1.0");
     }
 
+    #[test]
+    fn test_state_without_last_generated_loads() {
+        let json = r#"{"version":"1.0","last_updated":"2024-01-01T00:00:00Z","sources":{}}"#;
+        let parsed: State = serde_json::from_str(json).unwrap();
+        assert!(parsed.last_generated.is_none());
+    }
+
     #[test]
     fn test_source_state_git_serialization() {
         let mut branches = HashMap::new();
@@ -114,15 +255,64 @@ mod tests {
         let todo_state = SourceState::Todo {
             last_checked: Utc::now(),
             last_modified: Utc::now(),
-            item_hashes: vec!["hash1".to_string(), "hash2".to_string()],
+            items: vec![
+                TodoItemState {
+                    content: "Task 1".to_string(),
+                    status: TodoStatus::Pending,
+                    line: 1,
+                },
+                TodoItemState {
+                    content: "Task 2".to_string(),
+                    status: TodoStatus::Done,
+                    line: 2,
+                },
+            ],
         };
 
         let json = serde_json::to_string(&todo_state).unwrap();
         let parsed: SourceState = serde_json::from_str(&json).unwrap();
 
         match parsed {
-            SourceState::Todo { item_hashes, .. } => {
-                assert_eq!(item_hashes.len(), 2);
+            SourceState::Todo { items, .. } => {
+                assert_eq!(items.len(), 2);
+                assert_eq!(items[1].status, TodoStatus::Done);
+            }
+            _ => panic!("Expected Todo variant"),
+        }
+    }
+
+    #[test]
+    fn test_source_state_todo_migrates_legacy_hashes() {
+        let json = r#"{
+            "type": "todo",
+            "last_checked": "2024-01-01T00:00:00Z",
+            "last_modified": "2024-01-01T00:00:00Z",
+            "item_hashes": [
+                "Pending:todo.md:1:Buy milk",
+                "Done:C:\\notes\\todo.md:12:Call: the bank",
+                "garbage"
+            ]
+        }"#;
+
+        let parsed: SourceState = serde_json::from_str(json).unwrap();
+
+        match parsed {
+            SourceState::Todo { items, .. } => {
+                assert_eq!(
+                    items,
+                    vec![
+                        TodoItemState {
+                            content: "Buy milk".to_string(),
+                            status: TodoStatus::Pending,
+                            line: 1,
+                        },
+                        TodoItemState {
+                            content: "Call: the bank".to_string(),
+                            status: TodoStatus::Done,
+                            line: 12,
+                        },
+                    ]
+                );
             }
             _ => panic!("Expected Todo variant"),
         }
@@ -131,7 +321,13 @@ mod tests {
     #[test]
     fn test_source_state_notes_serialization() {
         let mut files = HashMap::new();
-        files.insert("note1.md".to_string(), Utc::now());
+        files.insert(
+            "note1.md".to_string(),
+            NoteFileState {
+                modified_at: Utc::now(),
+                content_hash: Some("abc".to_string()),
+            },
+        );
 
         let notes_state = SourceState::Notes {
             last_checked: Utc::now(),
@@ -144,6 +340,27 @@ mod tests {
         match parsed {
             SourceState::Notes { files, .. } => {
                 assert_eq!(files.len(), 1);
+                assert_eq!(files["note1.md"].content_hash.as_deref(), Some("abc"));
+            }
+            _ => panic!("Expected Notes variant"),
+        }
+    }
+
+    #[test]
+    fn test_legacy_note_timestamps_migrate() {
+        let json = r#"{
+            "type": "notes",
+            "last_checked": "2024-01-15T10:00:00Z",
+            "files": { "notes/idea.md": "2024-01-15T09:30:00Z" }
+        }"#;
+
+        let parsed: SourceState = serde_json::from_str(json).unwrap();
+
+        match parsed {
+            SourceState::Notes { files, .. } => {
+                let file = &files["notes/idea.md"];
+                assert_eq!(file.modified_at.to_rfc3339(), "2024-01-15T09:30:00+00:00");
+                assert_eq!(file.content_hash, None);
             }
             _ => panic!("Expected Notes variant"),
         }
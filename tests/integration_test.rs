@@ -9,6 +9,16 @@ fn path_to_toml_string(path: &std::path::Path) -> String {
     path.display().to_string().replace('\\', "/")
 }
 
+/// Helper to keep a generated config's state file inside the test's temp
+/// directory instead of the working directory
+fn with_temp_state(config: &str, temp_dir: &TempDir) -> String {
+    let state_file = temp_dir.path().join(".chronicle-state.json");
+    config.replace(
+        "state_file = \"./.chronicle-state.json\"",
+        &format!("state_file = \"{}\"", path_to_toml_string(&state_file)),
+    )
+}
+
 /// Helper to create a test Git repository with commits
 fn create_test_git_repo(path: &std::path::Path) {
     // Initialize Git repo
@@ -74,6 +84,76 @@ fn test_config_init() {
     assert!(config_path.exists());
 }
 
+#[test]
+fn test_config_init_interactive_needs_terminal() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("chronicle.toml");
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["config", "init", "--interactive", "--path"])
+        .arg(&config_path)
+        .current_dir(temp_dir.path())
+        .write_stdin("")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("needs a terminal"));
+
+    assert!(!config_path.exists());
+}
+
+#[test]
+fn test_config_show_prints_effective_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("chronicle.toml");
+
+    // Partial config: everything else comes from defaults
+    fs::write(&config_path, "[limits]\nmax_commits = 7\n").unwrap();
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["config", "show", "--config", config_path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("max_commits = 7"))
+        .stdout(predicate::str::contains("max_changed_files = 80"))
+        .stdout(predicate::str::contains("show_authors = true"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_config_edit_validates_after_editor() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("chronicle.toml");
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["config", "init", "--path", config_path.to_str().unwrap()])
+        .assert()
+        .success();
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["config", "edit", "--config", config_path.to_str().unwrap()])
+        .env_remove("VISUAL")
+        .env("EDITOR", "true")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Configuration is valid"));
+
+    // An editor that leaves the file invalid should fail the command
+    let editor = temp_dir.path().join("break-config.sh");
+    fs::write(&editor, "#!/bin/sh\necho 'limits = [' >> \"$1\"\n").unwrap();
+    StdCommand::new("chmod")
+        .args(["+x", editor.to_str().unwrap()])
+        .output()
+        .unwrap();
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["config", "edit", "--config", config_path.to_str().unwrap()])
+        .env_remove("VISUAL")
+        .env("EDITOR", editor.to_str().unwrap())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("TOML parsing error"));
+}
+
 #[test]
 fn test_state_reset() {
     let temp_dir = TempDir::new().unwrap();
@@ -132,7 +212,7 @@ fn test_gen_dry_run() {
         "repos = [\".\"]",
         &format!("repos = [\"{}\"]", path_to_toml_string(&repo_path)),
     );
-    fs::write(&config_path, updated_config).unwrap();
+    fs::write(&config_path, with_temp_state(&updated_config, &temp_dir)).unwrap();
 
     // Run gen with dry-run
     cargo::cargo_bin_cmd!("chronicle")
@@ -176,7 +256,7 @@ fn test_gen_and_show_latest() {
             "repos = [\".\"]",
             &format!("repos = [\"{}\"]", path_to_toml_string(&repo_path)),
         );
-    fs::write(&config_path, updated_config).unwrap();
+    fs::write(&config_path, with_temp_state(&updated_config, &temp_dir)).unwrap();
 
     // Run gen
     cargo::cargo_bin_cmd!("chronicle")
@@ -237,7 +317,7 @@ fn test_gen_with_todos() {
             "todo_files = []",
             &format!("todo_files = [\"{}\"]", path_to_toml_string(&todo_file)),
         );
-    fs::write(&config_path, updated_config).unwrap();
+    fs::write(&config_path, with_temp_state(&updated_config, &temp_dir)).unwrap();
 
     // Run gen
     cargo::cargo_bin_cmd!("chronicle")
@@ -256,6 +336,120 @@ fn test_gen_with_todos() {
         .stdout(predicate::str::contains("Task 3"));
 }
 
+#[test]
+fn test_gen_with_calendar() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("chronicle.toml");
+    let calendar_file = temp_dir.path().join("work.ics");
+
+    // One event an hour ago, one tomorrow
+    let start = |hours: i64| {
+        (chrono::Utc::now() + chrono::Duration::hours(hours))
+            .format("%Y%m%dT%H%M%SZ")
+            .to_string()
+    };
+    fs::write(
+        &calendar_file,
+        format!(
+            "BEGIN:VCALENDAR\nVERSION:2.0\nBEGIN:VEVENT\nDTSTART:{}\nSUMMARY:Standup\nEND:VEVENT\nBEGIN:VEVENT\nDTSTART:{}\nSUMMARY:Planning\nEND:VEVENT\nEND:VCALENDAR\n",
+            start(-1),
+            start(24)
+        ),
+    )
+    .unwrap();
+    fs::write(
+        &config_path,
+        format!(
+            "state_file = \"{}\"\nrepos = []\ncalendar_files = [\"{}\"]\n",
+            path_to_toml_string(&temp_dir.path().join("state.json")),
+            path_to_toml_string(&calendar_file)
+        ),
+    )
+    .unwrap();
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--only",
+            "calendar",
+            "--dry-run",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## Events"))
+        .stdout(predicate::str::contains("Standup"))
+        .stdout(predicate::str::contains("Planning").not());
+}
+
+#[test]
+fn test_gen_with_profile() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("chronicle.toml");
+    let todo_file = temp_dir.path().join("todo.md");
+    let base_dir = temp_dir.path().join("base");
+    let work_dir = temp_dir.path().join("work");
+
+    fs::write(&todo_file, "- [ ] Task 1\n").unwrap();
+    fs::write(
+        &config_path,
+        format!(
+            "output_dir = \"{}\"\nstate_file = \"{}\"\nrepos = []\ntodo_files = [\"{}\"]\n\n\
+             [profiles.work]\noutput_dir = \"{}\"\n",
+            path_to_toml_string(&base_dir),
+            path_to_toml_string(&temp_dir.path().join("state.json")),
+            path_to_toml_string(&todo_file),
+            path_to_toml_string(&work_dir),
+        ),
+    )
+    .unwrap();
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--profile",
+            "work",
+        ])
+        .assert()
+        .success();
+
+    assert!(work_dir.exists());
+    assert!(!base_dir.exists());
+
+    // Shows the merged result, not the profile tables
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "config",
+            "show",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--profile",
+            "work",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "output_dir = \"{}\"",
+            path_to_toml_string(&work_dir)
+        )))
+        .stdout(predicate::str::contains("[profiles").not());
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--profile",
+            "home",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown profile 'home'"));
+}
+
 #[test]
 fn test_incremental_updates() {
     let temp_dir = TempDir::new().unwrap();
@@ -283,7 +477,7 @@ fn test_incremental_updates() {
             "repos = [\".\"]",
             &format!("repos = [\"{}\"]", path_to_toml_string(&repo_path)),
         );
-    fs::write(&config_path, updated_config).unwrap();
+    fs::write(&config_path, with_temp_state(&updated_config, &temp_dir)).unwrap();
 
     // First gen
     cargo::cargo_bin_cmd!("chronicle")
@@ -322,6 +516,1573 @@ fn test_incremental_updates() {
     assert_eq!(files_count, 2);
 }
 
+#[test]
+fn test_gen_only_rejects_unknown_sources() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("chronicle.toml");
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["config", "init", "--path", config_path.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let config_content = fs::read_to_string(&config_path).unwrap();
+    fs::write(&config_path, with_temp_state(&config_content, &temp_dir)).unwrap();
+
+    // Substrings of a source name no longer enable it
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--only",
+            "todos,digits",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown source 'digits'"))
+        .stderr(predicate::str::contains("git, todos, notes"));
+}
+
+#[test]
+fn test_gen_skip_excludes_sources() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("chronicle.toml");
+    let todo_file = temp_dir.path().join("todo.md");
+
+    fs::write(&todo_file, "- [ ] Task 1\n").unwrap();
+    fs::write(
+        &config_path,
+        format!(
+            "output_dir = \"{}\"\nstate_file = \"{}\"\nrepos = []\ntodo_files = [\"{}\"]\n",
+            path_to_toml_string(&temp_dir.path().join("chronicles")),
+            path_to_toml_string(&temp_dir.path().join("state.json")),
+            path_to_toml_string(&todo_file),
+        ),
+    )
+    .unwrap();
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--dry-run",
+            "--skip",
+            "todos",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No activity to report."));
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--dry-run",
+            "--skip",
+            "todos",
+            "--fail-on-empty",
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("No activity to report"));
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--dry-run",
+            "--skip",
+            "git,notes",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Task 1"));
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--only",
+            "git",
+            "--skip",
+            "notes",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_gen_quiet_suppresses_status_output() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("chronicle.toml");
+    let chronicles_dir = temp_dir.path().join("chronicles");
+    let missing_todo = temp_dir.path().join("missing.md");
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["config", "init", "--path", config_path.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let todo_file = temp_dir.path().join("todo.md");
+    fs::write(&todo_file, "- [ ] Task 1\n").unwrap();
+
+    let config_content = fs::read_to_string(&config_path).unwrap();
+    let updated_config = config_content
+        .replace(
+            "output_dir = \"./chronicles\"",
+            &format!("output_dir = \"{}\"", path_to_toml_string(&chronicles_dir)),
+        )
+        .replace("repos = [\".\"]", "repos = []")
+        .replace(
+            "todo_files = []",
+            &format!(
+                "todo_files = [\"{}\", \"{}\"]",
+                path_to_toml_string(&todo_file),
+                path_to_toml_string(&missing_todo)
+            ),
+        );
+    fs::write(&config_path, with_temp_state(&updated_config, &temp_dir)).unwrap();
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["gen", "--quiet", "--config", config_path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::is_empty());
+
+    assert!(chronicles_dir.exists());
+}
+
+#[test]
+fn test_gen_dry_run_reports_skipped_writes() {
+    let temp_dir = TempDir::new().unwrap();
+    let repo_path = temp_dir.path().join("test-repo");
+    fs::create_dir(&repo_path).unwrap();
+    create_test_git_repo(&repo_path);
+
+    let chronicles_dir = temp_dir.path().join("chronicles");
+    let config_path = temp_dir.path().join("chronicle.toml");
+    fs::write(
+        &config_path,
+        format!(
+            "output_dir = \"{}\"\nstate_file = \"{}\"\nrepos = [\"{}\"]\n",
+            path_to_toml_string(&chronicles_dir),
+            path_to_toml_string(&temp_dir.path().join("state.json")),
+            path_to_toml_string(&repo_path),
+        ),
+    )
+    .unwrap();
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--date",
+            "2024-01-15",
+            "--dry-run",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("dry run").not())
+        .stderr(predicate::str::contains("dry run: would write"))
+        .stderr(predicate::str::contains(
+            "chronicle-2024-01-15.md and update 1 source",
+        ));
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--quiet",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--dry-run",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::is_empty());
+
+    assert!(!chronicles_dir.exists());
+}
+
+#[test]
+fn test_gen_timeline_view() {
+    let temp_dir = TempDir::new().unwrap();
+    let repo_path = temp_dir.path().join("test-repo");
+    fs::create_dir(&repo_path).unwrap();
+    create_test_git_repo(&repo_path);
+
+    let config_path = temp_dir.path().join("chronicle.toml");
+    fs::write(
+        &config_path,
+        format!(
+            "state_file = \"{}\"\nrepos = [\"{}\"]\n",
+            path_to_toml_string(&temp_dir.path().join("state.json")),
+            path_to_toml_string(&repo_path),
+        ),
+    )
+    .unwrap();
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--view",
+            "timeline",
+            "--dry-run",
+        ])
+        .env("NO_COLOR", "1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## Timeline"))
+        .stdout(predicate::str::contains("**test-repo**"))
+        .stdout(predicate::str::contains("## Git Activity").not());
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["gen", "--view", "calendar"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_gen_verbose_reports_source_counts() {
+    let temp_dir = TempDir::new().unwrap();
+    let repo_path = temp_dir.path().join("test-repo");
+    fs::create_dir(&repo_path).unwrap();
+    create_test_git_repo(&repo_path);
+
+    let config_path = temp_dir.path().join("chronicle.toml");
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["config", "init", "--path", config_path.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let config_content = fs::read_to_string(&config_path).unwrap();
+    let updated_config = config_content.replace(
+        "repos = [\".\"]",
+        &format!("repos = [\"{}\"]", path_to_toml_string(&repo_path)),
+    );
+    fs::write(&config_path, with_temp_state(&updated_config, &temp_dir)).unwrap();
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--verbose",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--dry-run",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "git: scanned 1 repositories, found 2 commits",
+        ))
+        .stderr(predicate::str::contains("todos: parsed 0 files"))
+        .stderr(predicate::str::contains("notes: scanned 0 directories"));
+}
+
+#[test]
+fn test_gen_append() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("chronicle.toml");
+    let output_dir = temp_dir.path().join("chronicles");
+    let todo_file = temp_dir.path().join("todo.md");
+
+    fs::write(&todo_file, "- [ ] Morning task\n").unwrap();
+    fs::write(
+        &config_path,
+        format!(
+            "output_dir = \"{}\"\nstate_file = \"{}\"\nrepos = []\ntodo_files = [\"{}\"]\n",
+            path_to_toml_string(&output_dir),
+            path_to_toml_string(&temp_dir.path().join("state.json")),
+            path_to_toml_string(&todo_file),
+        ),
+    )
+    .unwrap();
+
+    let gen_append = || {
+        cargo::cargo_bin_cmd!("chronicle")
+            .args(["gen", "--config", config_path.to_str().unwrap(), "--append"])
+            .assert()
+            .success()
+    };
+
+    // Without a chronicle for the day, --append writes a full one
+    gen_append().stdout(predicate::str::contains("Chronicle written to"));
+
+    fs::write(&todo_file, "- [x] Morning task\n- [ ] Evening task\n").unwrap();
+    gen_append().stdout(predicate::str::contains("Chronicle appended to"));
+
+    let chronicle = fs::read_dir(&output_dir)
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let content = fs::read_to_string(&chronicle).unwrap();
+    assert_eq!(content.matches("## Summary").count(), 1);
+    assert_eq!(content.matches("**Generated:**").count(), 2);
+    let (first, update) = content.split_once("\n\n---\n\n## Update\n\n").unwrap();
+    assert!(first.contains("- [ ] Morning task ← **NEW**"));
+    assert!(update.contains("- [x] Morning task ← **DONE**"));
+    assert!(update.contains("- [ ] Evening task ← **NEW**"));
+    assert!(update.contains("\n### TODOs\n"));
+    assert!(!update.contains("\n## TODOs\n"));
+
+    // Nothing new leaves the file alone
+    gen_append().stdout(predicate::str::contains("No activity to report."));
+    assert_eq!(fs::read_to_string(&chronicle).unwrap(), content);
+}
+
+#[test]
+fn test_gen_writes_multiple_formats() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("chronicle.toml");
+    let output_dir = temp_dir.path().join("chronicles");
+    let todo_file = temp_dir.path().join("todo.md");
+
+    fs::write(&todo_file, "- [ ] Write the report\n").unwrap();
+    fs::write(
+        &config_path,
+        format!(
+            "output_dir = \"{}\"\nstate_file = \"{}\"\nrepos = []\ntodo_files = [\"{}\"]\n",
+            path_to_toml_string(&output_dir),
+            path_to_toml_string(&temp_dir.path().join("state.json")),
+            path_to_toml_string(&todo_file),
+        ),
+    )
+    .unwrap();
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--format",
+            "md,json",
+        ])
+        .assert()
+        .success();
+
+    let mut files: Vec<_> = fs::read_dir(&output_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    files.sort();
+    assert_eq!(files.len(), 2);
+    assert!(fs::read_to_string(&files[1])
+        .unwrap()
+        .contains("- [ ] Write the report"));
+    let json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&files[0]).unwrap()).unwrap();
+    assert_eq!(json["todos"][0]["content"], "Write the report");
+
+    // An update section has no JSON counterpart
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--format",
+            "json",
+            "--append",
+        ])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_gen_open_without_a_file_or_desktop() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("chronicle.toml");
+    let todo_file = temp_dir.path().join("todo.md");
+
+    fs::write(&todo_file, "- [ ] Open the chronicle\n").unwrap();
+    fs::write(
+        &config_path,
+        format!(
+            "output_dir = \"{}\"\nstate_file = \"{}\"\nrepos = []\ntodo_files = [\"{}\"]\n",
+            path_to_toml_string(&temp_dir.path().join("chronicles")),
+            path_to_toml_string(&temp_dir.path().join("state.json")),
+            path_to_toml_string(&todo_file),
+        ),
+    )
+    .unwrap();
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--dry-run",
+            "--open",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Nothing to open"));
+
+    // Without a desktop session the file is still written
+    if cfg!(all(unix, not(target_os = "macos"))) {
+        for args in [vec!["gen"], vec!["show", "latest"]] {
+            cargo::cargo_bin_cmd!("chronicle")
+                .args(args)
+                .args(["--config", config_path.to_str().unwrap(), "--open"])
+                .env_remove("DISPLAY")
+                .env_remove("WAYLAND_DISPLAY")
+                .assert()
+                .success()
+                .stderr(predicate::str::contains("No graphical session"));
+        }
+    }
+}
+
+#[test]
+fn test_gen_write_empty() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("chronicle.toml");
+    let output_dir = temp_dir.path().join("chronicles");
+
+    fs::write(
+        &config_path,
+        format!(
+            "output_dir = \"{}\"\nstate_file = \"{}\"\nrepos = []\nwrite_empty = true\n",
+            path_to_toml_string(&output_dir),
+            path_to_toml_string(&temp_dir.path().join("state.json")),
+        ),
+    )
+    .unwrap();
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["gen", "--config", config_path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Chronicle written to"));
+
+    let chronicle = fs::read_dir(&output_dir)
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let content = fs::read_to_string(chronicle).unwrap();
+    assert!(content.starts_with("# Chronicle"));
+    assert!(content.contains("Quiet day."));
+    assert!(!content.contains("## Summary"));
+
+    // --fail-on-empty still fails
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--fail-on-empty",
+        ])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn test_gen_reports_collection_warnings() {
+    let temp_dir = TempDir::new().unwrap();
+    let repo_path = temp_dir.path().join("test-repo");
+    fs::create_dir(&repo_path).unwrap();
+    create_test_git_repo(&repo_path);
+    let missing_path = temp_dir.path().join("missing-repo");
+
+    let config_path = temp_dir.path().join("chronicle.toml");
+    fs::write(
+        &config_path,
+        format!(
+            "state_file = \"{}\"\nrepos = [\"{}\", \"{}\"]\n",
+            path_to_toml_string(&temp_dir.path().join("state.json")),
+            path_to_toml_string(&repo_path),
+            path_to_toml_string(&missing_path)
+        ),
+    )
+    .unwrap();
+
+    // A count by default, each warning with --verbose
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--dry-run",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("| Warnings | 1 |"))
+        .stderr(predicate::str::contains(
+            "Warning: 1 source skipped or partly read (run with --verbose for details)",
+        ));
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--verbose",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--dry-run",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Warning: Skipping repository"));
+
+    // --strict turns the skipped repository into an error, writing nothing
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["gen", "--config", config_path.to_str().unwrap(), "--strict"])
+        .current_dir(temp_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot read repository"))
+        .stderr(predicate::str::contains("(--strict)"));
+    assert!(!temp_dir.path().join("chronicles").exists());
+
+    // A repository read with a fallback is not a skipped source
+    fs::write(
+        &config_path,
+        format!(
+            "state_file = \"{}\"\n\n[[repos]]\npath = \"{}\"\ndefault_branch = \"missing\"\n",
+            path_to_toml_string(&temp_dir.path().join("state.json")),
+            path_to_toml_string(&repo_path)
+        ),
+    )
+    .unwrap();
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--strict",
+            "--dry-run",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("| Warnings | 1 |"));
+}
+
+#[test]
+fn test_quiet_and_verbose_conflict() {
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["gen", "--quiet", "--verbose"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_gen_since_last_run() {
+    let temp_dir = TempDir::new().unwrap();
+    let repo_path = temp_dir.path().join("test-repo");
+    fs::create_dir(&repo_path).unwrap();
+    create_test_git_repo(&repo_path);
+
+    let config_path = temp_dir.path().join("chronicle.toml");
+    fs::write(
+        &config_path,
+        format!(
+            "output_dir = \"{}\"\nstate_file = \"{}\"\nrepos = [\"{}\"]\n",
+            path_to_toml_string(&temp_dir.path().join("chronicles")),
+            path_to_toml_string(&temp_dir.path().join("state.json")),
+            path_to_toml_string(&repo_path),
+        ),
+    )
+    .unwrap();
+
+    // Without prior state, falls back to the default 24h window
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--since-last-run",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Chronicle written to"));
+
+    // The commits predate the last run, so nothing new is reported
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--since-last-run",
+            "--dry-run",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No activity to report."));
+}
+
+#[test]
+fn test_gen_today_starts_at_midnight_of_date() {
+    let temp_dir = TempDir::new().unwrap();
+    let repo_path = temp_dir.path().join("test-repo");
+    fs::create_dir(&repo_path).unwrap();
+    create_test_git_repo(&repo_path);
+
+    let config_path = temp_dir.path().join("chronicle.toml");
+    fs::write(
+        &config_path,
+        format!(
+            "output_dir = \"{}\"\nstate_file = \"{}\"\nrepos = [\"{}\"]\n",
+            path_to_toml_string(&temp_dir.path().join("chronicles")),
+            path_to_toml_string(&temp_dir.path().join("state.json")),
+            path_to_toml_string(&repo_path),
+        ),
+    )
+    .unwrap();
+
+    let gen_today = |date: &str| {
+        cargo::cargo_bin_cmd!("chronicle")
+            .args([
+                "gen",
+                "--config",
+                config_path.to_str().unwrap(),
+                "--today",
+                "--date",
+                date,
+                "--dry-run",
+            ])
+            .assert()
+            .success()
+    };
+
+    // The commits were made after midnight of a past date...
+    gen_today("2000-01-01").stdout(predicate::str::contains("Update file"));
+
+    // ...but before midnight of a future one
+    gen_today("2999-01-01").stdout(predicate::str::contains("No activity to report."));
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["gen", "--today", "--since-last-run"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_gen_since_relative_duration() {
+    let temp_dir = TempDir::new().unwrap();
+    let repo_path = temp_dir.path().join("test-repo");
+    fs::create_dir(&repo_path).unwrap();
+    create_test_git_repo(&repo_path);
+
+    let config_path = temp_dir.path().join("chronicle.toml");
+    fs::write(
+        &config_path,
+        format!(
+            "output_dir = \"{}\"\nstate_file = \"{}\"\nrepos = [\"{}\"]\n",
+            path_to_toml_string(&temp_dir.path().join("chronicles")),
+            path_to_toml_string(&temp_dir.path().join("state.json")),
+            path_to_toml_string(&repo_path),
+        ),
+    )
+    .unwrap();
+
+    let gen_since = |since: &str| {
+        cargo::cargo_bin_cmd!("chronicle")
+            .args([
+                "gen",
+                "--config",
+                config_path.to_str().unwrap(),
+                "--since",
+                since,
+                "--dry-run",
+            ])
+            .assert()
+    };
+
+    gen_since("1w")
+        .success()
+        .stdout(predicate::str::contains("Update file"));
+    gen_since("0m")
+        .success()
+        .stdout(predicate::str::contains("No activity to report."));
+    gen_since("2000-01-01T00:00:00Z")
+        .success()
+        .stdout(predicate::str::contains("Update file"));
+    gen_since("yesterday")
+        .failure()
+        .stderr(predicate::str::contains("Invalid since 'yesterday'"));
+}
+
+#[test]
+fn test_gen_per_source_since() {
+    let temp_dir = TempDir::new().unwrap();
+    let repo_path = temp_dir.path().join("test-repo");
+    let notes_dir = temp_dir.path().join("notes");
+    fs::create_dir(&repo_path).unwrap();
+    fs::create_dir(&notes_dir).unwrap();
+    create_test_git_repo(&repo_path);
+    fs::write(notes_dir.join("today.md"), "Wrote things down.").unwrap();
+
+    let config_path = temp_dir.path().join("chronicle.toml");
+    fs::write(
+        &config_path,
+        format!(
+            "output_dir = \"{}\"\nstate_file = \"{}\"\nrepos = [\"{}\"]\nnotes_dirs = [\"{}\"]\ngit_since = \"0m\"\n",
+            path_to_toml_string(&temp_dir.path().join("chronicles")),
+            path_to_toml_string(&temp_dir.path().join("state.json")),
+            path_to_toml_string(&repo_path),
+            path_to_toml_string(&notes_dir),
+        ),
+    )
+    .unwrap();
+
+    let gen = |args: &[&str]| {
+        cargo::cargo_bin_cmd!("chronicle")
+            .args([
+                "gen",
+                "--config",
+                config_path.to_str().unwrap(),
+                "--dry-run",
+            ])
+            .args(args)
+            .assert()
+    };
+
+    // git_since shortens the Git window; notes keep the default one
+    gen(&[])
+        .success()
+        .stdout(predicate::str::contains("today.md"))
+        .stdout(predicate::str::contains("Update file").not());
+
+    // A window on the command line applies to every source
+    gen(&["--since", "1w"])
+        .success()
+        .stdout(predicate::str::contains("today.md"))
+        .stdout(predicate::str::contains("Update file"));
+}
+
+#[test]
+fn test_gen_date_range() {
+    let temp_dir = TempDir::new().unwrap();
+    let repo_path = temp_dir.path().join("test-repo");
+    fs::create_dir(&repo_path).unwrap();
+    create_test_git_repo(&repo_path);
+
+    let config_path = temp_dir.path().join("chronicle.toml");
+    fs::write(
+        &config_path,
+        format!(
+            "output_dir = \"{}\"\nstate_file = \"{}\"\nrepos = [\"{}\"]\n",
+            path_to_toml_string(&temp_dir.path().join("chronicles")),
+            path_to_toml_string(&temp_dir.path().join("state.json")),
+            path_to_toml_string(&repo_path),
+        ),
+    )
+    .unwrap();
+
+    let gen_range = |from: &str, to: &str| {
+        cargo::cargo_bin_cmd!("chronicle")
+            .args([
+                "gen",
+                "--config",
+                config_path.to_str().unwrap(),
+                "--from",
+                from,
+                "--to",
+                to,
+                "--dry-run",
+            ])
+            .assert()
+    };
+
+    // The commits fall inside a range ending in the future...
+    gen_range("2000-01-01", "2999-01-01")
+        .success()
+        .stdout(predicate::str::contains("Update file"))
+        .stdout(predicate::str::contains("2000-01-01 – 2999-01-01"));
+
+    // ...but not inside one that ended long ago
+    gen_range("2000-01-01", "2000-01-02")
+        .success()
+        .stdout(predicate::str::contains("No activity to report."));
+
+    gen_range("2000-01-02", "2000-01-01")
+        .failure()
+        .stderr(predicate::str::contains("is after --to"));
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["gen", "--to", "2000-01-01"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_gen_limit_commits_overrides_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let repo_path = temp_dir.path().join("test-repo");
+    fs::create_dir(&repo_path).unwrap();
+    create_test_git_repo(&repo_path);
+
+    let config_path = temp_dir.path().join("chronicle.toml");
+    fs::write(
+        &config_path,
+        format!(
+            "output_dir = \"{}\"\nstate_file = \"{}\"\n\n[[repos]]\npath = \"{}\"\nmax_commits = 1\n",
+            path_to_toml_string(&temp_dir.path().join("chronicles")),
+            path_to_toml_string(&temp_dir.path().join("state.json")),
+            path_to_toml_string(&repo_path),
+        ),
+    )
+    .unwrap();
+
+    let gen = |args: &[&str]| {
+        cargo::cargo_bin_cmd!("chronicle")
+            .args([
+                "gen",
+                "--config",
+                config_path.to_str().unwrap(),
+                "--dry-run",
+            ])
+            .args(args)
+            .assert()
+    };
+
+    // The per-repository limit keeps only the newest commit...
+    gen(&[])
+        .success()
+        .stdout(predicate::str::contains("Update file"))
+        .stdout(predicate::str::contains("Initial commit").not());
+
+    // ...unless overridden for this run
+    gen(&["--limit-commits", "10"])
+        .success()
+        .stdout(predicate::str::contains("Initial commit"));
+
+    gen(&["--limit-commits", "0"]).failure();
+}
+
+#[test]
+fn test_gen_repo_overrides_configured_repos() {
+    let temp_dir = TempDir::new().unwrap();
+    let configured = temp_dir.path().join("configured-repo");
+    let other = temp_dir.path().join("other-repo");
+    for path in [&configured, &other] {
+        fs::create_dir(path).unwrap();
+        create_test_git_repo(path);
+    }
+
+    let config_path = temp_dir.path().join("chronicle.toml");
+    fs::write(
+        &config_path,
+        format!(
+            "output_dir = \"{}\"\nstate_file = \"{}\"\nrepos = [\"{}\"]\n",
+            path_to_toml_string(&temp_dir.path().join("chronicles")),
+            path_to_toml_string(&temp_dir.path().join("state.json")),
+            path_to_toml_string(&configured),
+        ),
+    )
+    .unwrap();
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--dry-run",
+            "--only",
+            "git",
+            "--repo",
+            other.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("other-repo"))
+        .stdout(predicate::str::contains("configured-repo").not());
+}
+
+#[test]
+fn test_gen_watch_regenerates_on_change() {
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let temp_dir = TempDir::new().unwrap();
+    let todo_file = temp_dir.path().join("todo.md");
+    fs::write(&todo_file, "- [ ] First task\n").unwrap();
+
+    let config_path = temp_dir.path().join("chronicle.toml");
+    fs::write(
+        &config_path,
+        format!(
+            "output_dir = \"{}\"\nstate_file = \"{}\"\nrepos = []\ntodo_files = [\"{}\"]\n",
+            path_to_toml_string(&temp_dir.path().join("chronicles")),
+            path_to_toml_string(&temp_dir.path().join("state.json")),
+            path_to_toml_string(&todo_file),
+        ),
+    )
+    .unwrap();
+
+    let mut child = StdCommand::new(cargo::cargo_bin!("chronicle"))
+        .args(["gen", "--watch", "--config", config_path.to_str().unwrap()])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let (sender, receiver) = mpsc::channel();
+    let stdout = BufReader::new(child.stdout.take().unwrap());
+    std::thread::spawn(move || {
+        for line in stdout.lines().map_while(Result::ok) {
+            let _ = sender.send(line);
+        }
+    });
+    let wait_for = |text: &str| {
+        while let Ok(line) = receiver.recv_timeout(Duration::from_secs(10)) {
+            if line.contains(text) {
+                return true;
+            }
+        }
+        false
+    };
+
+    let first = wait_for("First task");
+    if first {
+        // Give the watcher a moment after the first run before changing
+        std::thread::sleep(Duration::from_millis(500));
+        fs::write(&todo_file, "- [ ] First task\n- [ ] Second task\n").unwrap();
+    }
+    let second = first && wait_for("Second task");
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    assert!(first, "no chronicle printed on start");
+    assert!(second, "no chronicle printed after the change");
+    assert!(!temp_dir.path().join("chronicles").exists());
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["gen", "--save"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_show_streak() {
+    let temp_dir = TempDir::new().unwrap();
+    let chronicles_dir = temp_dir.path().join("chronicles");
+    fs::create_dir(&chronicles_dir).unwrap();
+
+    let config_path = temp_dir.path().join("chronicle.toml");
+    fs::write(
+        &config_path,
+        format!(
+            "output_dir = \"{}\"\nrepos = []\ntimezone = \"UTC\"\n",
+            path_to_toml_string(&chronicles_dir),
+        ),
+    )
+    .unwrap();
+
+    let show_streak = || {
+        cargo::cargo_bin_cmd!("chronicle")
+            .args(["show", "streak", "--config", config_path.to_str().unwrap()])
+            .assert()
+            .success()
+    };
+
+    show_streak().stdout(predicate::str::contains("No current streak"));
+
+    // Yesterday and the two days before, but nothing yet today
+    let today = chrono::Utc::now().date_naive();
+    for days_ago in [1, 2, 3, 5] {
+        let date = today - chrono::Duration::days(days_ago);
+        fs::write(
+            chronicles_dir.join(format!("chronicle-{}.md", date.format("%Y-%m-%d"))),
+            "",
+        )
+        .unwrap();
+    }
+
+    show_streak().stdout(predicate::str::contains("Current streak: 3 days"));
+}
+
+#[test]
+fn test_config_falls_back_to_xdg_config_home() {
+    let temp_dir = TempDir::new().unwrap();
+    let work_dir = temp_dir.path().join("work");
+    let xdg_dir = temp_dir.path().join("xdg");
+    fs::create_dir(&work_dir).unwrap();
+    fs::create_dir_all(xdg_dir.join("chronicle")).unwrap();
+
+    let config_path = xdg_dir.join("chronicle").join("chronicle.toml");
+    fs::write(&config_path, "output_dir = \"from-xdg\"\n").unwrap();
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["config", "show", "--verbose"])
+        .current_dir(&work_dir)
+        .env("XDG_CONFIG_HOME", &xdg_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("output_dir = \"from-xdg\""))
+        .stderr(predicate::str::contains(format!(
+            "Using config: {}",
+            config_path.display()
+        )));
+
+    // A config in the current directory takes precedence
+    fs::write(work_dir.join("chronicle.toml"), "output_dir = \"local\"\n").unwrap();
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["config", "show"])
+        .current_dir(&work_dir)
+        .env("XDG_CONFIG_HOME", &xdg_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("output_dir = \"local\""));
+}
+
+#[test]
+fn test_config_found_in_parent_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    let nested_dir = temp_dir.path().join("project").join("src").join("deep");
+    let xdg_dir = temp_dir.path().join("xdg");
+    fs::create_dir_all(&nested_dir).unwrap();
+    fs::create_dir_all(xdg_dir.join("chronicle")).unwrap();
+    fs::write(
+        xdg_dir.join("chronicle").join("chronicle.toml"),
+        "output_dir = \"from-xdg\"\n",
+    )
+    .unwrap();
+
+    let config_path = temp_dir.path().join("project").join("chronicle.toml");
+    fs::write(&config_path, "output_dir = \"from-project\"\n").unwrap();
+
+    // The nearest ancestor wins over the user config directory, and its
+    // relative paths are relative to it
+    let project_dir = config_path.parent().unwrap().canonicalize().unwrap();
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["config", "show", "--verbose"])
+        .current_dir(&nested_dir)
+        .env("XDG_CONFIG_HOME", &xdg_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "output_dir = \"{}\"",
+            path_to_toml_string(&project_dir.join("from-project"))
+        )))
+        .stderr(predicate::str::contains(format!(
+            "Using config: {}",
+            config_path.display()
+        )));
+}
+
+#[test]
+fn test_gen_from_subdirectory_resolves_paths_against_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    let nested_dir = project_dir.join("sub").join("deep");
+    fs::create_dir_all(&nested_dir).unwrap();
+    create_test_git_repo(&project_dir);
+    fs::write(project_dir.join("todo.md"), "- [ ] Task 1\n").unwrap();
+    fs::write(
+        project_dir.join("chronicle.toml"),
+        "output_dir = \"chronicles\"\nrepos = [\".\"]\ntodo_files = [\"todo.md\"]\n",
+    )
+    .unwrap();
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["gen"])
+        .current_dir(&nested_dir)
+        .env("XDG_CONFIG_HOME", temp_dir.path().join("xdg"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Chronicle written to"))
+        .stderr(predicate::str::contains("Skipping").not());
+
+    // Output and state land next to the config, not in the subdirectory
+    let chronicle = fs::read_dir(project_dir.join("chronicles"))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let content = fs::read_to_string(chronicle).unwrap();
+    assert!(content.contains("Update file"));
+    assert!(content.contains("Task 1"));
+    assert!(project_dir.join(".chronicle-state.json").exists());
+    assert!(!nested_dir.join("chronicles").exists());
+    assert!(!nested_dir.join(".chronicle-state.json").exists());
+}
+
+#[test]
+fn test_relative_state_file_is_next_to_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_dir = temp_dir.path().join("config");
+    let work_dir = temp_dir.path().join("work");
+    fs::create_dir(&config_dir).unwrap();
+    fs::create_dir(&work_dir).unwrap();
+
+    let todo_file = temp_dir.path().join("todo.md");
+    fs::write(&todo_file, "- [ ] Task 1\n").unwrap();
+
+    let config_path = config_dir.join("chronicle.toml");
+    fs::write(
+        &config_path,
+        format!(
+            "output_dir = \"{}\"\nstate_file = \"state.json\"\nrepos = []\ntodo_files = [\"{}\"]\n",
+            path_to_toml_string(&temp_dir.path().join("chronicles")),
+            path_to_toml_string(&todo_file),
+        ),
+    )
+    .unwrap();
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["gen", "--config", config_path.to_str().unwrap()])
+        .current_dir(&work_dir)
+        .assert()
+        .success();
+
+    assert!(config_dir.join("state.json").exists());
+    assert!(!work_dir.join("state.json").exists());
+}
+
+#[test]
+fn test_config_check() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("chronicle.toml");
+
+    fs::write(&config_path, "repos = []\n").unwrap();
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["config", "check", "--config", config_path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("OK:"));
+
+    // Missing paths are only warnings
+    fs::write(&config_path, "repos = []\ntodo_files = [\"missing.md\"]\n").unwrap();
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["config", "check", "--config", config_path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "warning: TODO file 'missing.md' does not exist",
+        ));
+
+    fs::write(
+        &config_path,
+        "repos = []\n[limits]\nmax_commits = 0\n[display.theme]\nheaders = \"mauve\"\n",
+    )
+    .unwrap();
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["config", "check", "--config", config_path.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "error: limits.max_commits must be at least 1",
+        ))
+        .stdout(predicate::str::contains("error: Unknown color 'mauve'"))
+        .stderr(predicate::str::contains("has 2 errors"));
+
+    // Profiles must load too
+    fs::write(
+        &config_path,
+        "repos = []\n[profiles.work]\nlimits = { max_commits = \"many\" }\n",
+    )
+    .unwrap();
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["config", "check", "--config", config_path.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Profile 'work' is invalid"));
+}
+
+#[test]
+fn test_state_show_reports_last_generated() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("chronicle.toml");
+    let todo_file = temp_dir.path().join("todo.md");
+
+    fs::write(&todo_file, "- [ ] Task 1\n").unwrap();
+    fs::write(
+        &config_path,
+        format!(
+            "output_dir = \"{}\"\nstate_file = \"{}\"\nrepos = []\ntodo_files = [\"{}\"]\n",
+            path_to_toml_string(&temp_dir.path().join("chronicles")),
+            path_to_toml_string(&temp_dir.path().join("state.json")),
+            path_to_toml_string(&todo_file),
+        ),
+    )
+    .unwrap();
+
+    // Dry runs don't touch state
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--dry-run",
+        ])
+        .assert()
+        .success();
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["state", "show", "--config", config_path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("State file does not exist"));
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["gen", "--config", config_path.to_str().unwrap()])
+        .assert()
+        .success();
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["state", "show", "--config", config_path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Last generated: 2"))
+        .stdout(predicate::str::contains("Sources: 1"))
+        .stdout(predicate::str::contains("[todo]"));
+}
+
+#[test]
+fn test_gen_shows_deltas_against_previous_chronicle() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("chronicle.toml");
+    let chronicles_dir = temp_dir.path().join("chronicles");
+    let todo_file = temp_dir.path().join("todo.md");
+
+    fs::create_dir(&chronicles_dir).unwrap();
+    fs::write(
+        chronicles_dir.join("chronicle-2020-01-01.md"),
+        "# Chronicle: 2020-01-01\n\n## Summary\n\n| Category | Count |\n\
+         |----------|-------|\n| New TODOs | 3 |\n",
+    )
+    .unwrap();
+    fs::write(&todo_file, "- [ ] Task 1\n").unwrap();
+    fs::write(
+        &config_path,
+        format!(
+            "output_dir = \"{}\"\nstate_file = \"{}\"\nrepos = []\ntodo_files = [\"{}\"]\n\n\
+             [display]\nshow_deltas = true\n",
+            path_to_toml_string(&chronicles_dir),
+            path_to_toml_string(&temp_dir.path().join("state.json")),
+            path_to_toml_string(&todo_file),
+        ),
+    )
+    .unwrap();
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "gen",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--dry-run",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Compared to 2020-01-01: -2 New TODOs",
+        ));
+}
+
+#[test]
+fn test_clean_prunes_old_chronicles() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("chronicle.toml");
+    let chronicles_dir = temp_dir.path().join("chronicles");
+
+    fs::create_dir(&chronicles_dir).unwrap();
+    for name in [
+        "chronicle-2020-01-01.md",
+        "chronicle-2020-01-02.md",
+        "chronicle-2020-01-03.md",
+        "notes.md",
+    ] {
+        fs::write(chronicles_dir.join(name), "# Chronicle\n").unwrap();
+    }
+    fs::write(
+        &config_path,
+        format!(
+            "output_dir = \"{}\"\nrepos = []\n",
+            path_to_toml_string(&chronicles_dir)
+        ),
+    )
+    .unwrap();
+
+    // Dry run lists but keeps files
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "clean",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--keep",
+            "1",
+            "--dry-run",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("chronicle-2020-01-01.md"))
+        .stdout(predicate::str::contains("chronicle-2020-01-02.md"))
+        .stdout(predicate::str::contains("chronicle-2020-01-03.md").not());
+    assert_eq!(fs::read_dir(&chronicles_dir).unwrap().count(), 4);
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "clean",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--keep",
+            "1",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed 2 chronicle file(s)."));
+    assert!(chronicles_dir.join("chronicle-2020-01-03.md").exists());
+    assert!(chronicles_dir.join("notes.md").exists());
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "clean",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--older-than",
+            "30d",
+        ])
+        .assert()
+        .success();
+    assert!(!chronicles_dir.join("chronicle-2020-01-03.md").exists());
+    assert!(chronicles_dir.join("notes.md").exists());
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "clean",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--older-than",
+            "90 days",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid age '90 days'"));
+
+    // An age reaching before the earliest date is rejected, not a panic
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "clean",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--older-than",
+            "99999999d",
+        ])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Invalid age '99999999d': too far back",
+        ));
+}
+
+#[test]
+fn test_merge_combines_range() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("chronicle.toml");
+    let chronicles_dir = temp_dir.path().join("chronicles");
+    let out = temp_dir.path().join("week.md");
+
+    fs::create_dir(&chronicles_dir).unwrap();
+    for (date, commits, todos) in [
+        ("2020-01-05", 9, 9),
+        ("2020-01-06", 2, 10),
+        ("2020-01-07", 3, 12),
+    ] {
+        fs::write(
+            chronicles_dir.join(format!("chronicle-{}.md", date)),
+            format!(
+                "# Chronicle: {}\n\n## Summary\n\n| Category | Count |\n|----------|-------|\n\
+                 | Commits | {} |\n| Open TODOs | {} |\n\n## TODOs\n\n- [ ] Task on {}",
+                date, commits, todos, date
+            ),
+        )
+        .unwrap();
+    }
+    fs::write(
+        &config_path,
+        format!(
+            "output_dir = \"{}\"\nrepos = []\n",
+            path_to_toml_string(&chronicles_dir)
+        ),
+    )
+    .unwrap();
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "merge",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--from",
+            "2020-01-06",
+            "--to",
+            "2020-01-08",
+            "--out",
+            out.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Merged 2 chronicle(s)"));
+
+    let merged = fs::read_to_string(&out).unwrap();
+    assert!(merged.starts_with("# Chronicles: 2020-01-06 – 2020-01-08\n"));
+    assert!(merged.contains("**Days:** 2"));
+    assert!(merged.contains("| Commits | 5 |"));
+    assert!(merged.contains("| Open TODOs | 12 |"));
+    assert!(merged.contains("## 2020-01-06\n\n### Summary"));
+    assert!(merged.contains("### TODOs\n\n- [ ] Task on 2020-01-07"));
+    assert!(!merged.contains("2020-01-05"));
+    assert!(!merged.contains("# Chronicle: "));
+    assert!(!merged.contains("| Lines Changed |"));
+    assert!(merged.contains("*2 days without a JSON sidecar"));
+}
+
+#[test]
+fn test_merge_counts_repositories_once() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("chronicle.toml");
+    let chronicles_dir = temp_dir.path().join("chronicles");
+
+    fs::create_dir(&chronicles_dir).unwrap();
+    for (date, repos) in [
+        ("2020-01-06", vec!["app", "cli"]),
+        ("2020-01-07", vec!["app"]),
+    ] {
+        let path = chronicles_dir.join(format!("chronicle-{}.md", date));
+        fs::write(&path, format!("# Chronicle: {}\n\n## Summary\n", date)).unwrap();
+        let repositories: Vec<_> = repos
+            .iter()
+            .map(|name| {
+                serde_json::json!({
+                    "path": format!("/work/{}", name),
+                    "name": name,
+                    "default_branch": "main",
+                    "branches": [],
+                    "wip": null,
+                    "lines_added": 10,
+                    "lines_removed": 2,
+                })
+            })
+            .collect();
+        let sidecar = serde_json::json!({
+            "date": date,
+            "since": format!("{}T00:00:00Z", date),
+            "generated_at": format!("{}T18:00:00Z", date),
+            "repositories": repositories,
+            "todos": [],
+            "notes": [],
+        });
+        fs::write(path.with_extension("json"), sidecar.to_string()).unwrap();
+    }
+    fs::write(
+        &config_path,
+        format!(
+            "output_dir = \"{}\"\nrepos = []\n",
+            path_to_toml_string(&chronicles_dir)
+        ),
+    )
+    .unwrap();
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args([
+            "merge",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--from",
+            "2020-01-06",
+            "--to",
+            "2020-01-07",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("| Repositories | 2 |"))
+        .stdout(predicate::str::contains("| Lines Changed | +30 / -6 |"))
+        .stdout(predicate::str::contains("without a JSON sidecar").not());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_gen_summary_command() {
+    let temp_dir = TempDir::new().unwrap();
+    let repo_path = temp_dir.path().join("test-repo");
+    fs::create_dir(&repo_path).unwrap();
+    create_test_git_repo(&repo_path);
+
+    let config_path = temp_dir.path().join("chronicle.toml");
+    let write_config = |command: &str, timeout: u32| {
+        fs::write(
+            &config_path,
+            format!(
+                "output_dir = \"{}\"\nstate_file = \"{}\"\nrepos = [\"{}\"]\nsummary_command = '{}'\nsummary_timeout_secs = {}\n",
+                path_to_toml_string(&temp_dir.path().join("chronicles")),
+                path_to_toml_string(&temp_dir.path().join("state.json")),
+                path_to_toml_string(&repo_path),
+                command,
+                timeout,
+            ),
+        )
+        .unwrap();
+    };
+    let gen = || {
+        cargo::cargo_bin_cmd!("chronicle")
+            .args([
+                "gen",
+                "--config",
+                config_path.to_str().unwrap(),
+                "--dry-run",
+            ])
+            .assert()
+    };
+
+    // The command sees the chronicle JSON and its output lands in the Summary
+    write_config(r#"grep -q "Update file" && echo "A productive day.""#, 10);
+    gen()
+        .success()
+        .stdout(predicate::str::contains("|\n\nA productive day."));
+
+    // Failures and timeouts only drop the paragraph
+    write_config("exit 3", 10);
+    gen()
+        .success()
+        .stdout(predicate::str::contains("## Summary"))
+        .stderr(predicate::str::contains("exited with exit status: 3"));
+
+    write_config("exec sleep 5", 1);
+    gen()
+        .success()
+        .stderr(predicate::str::contains("did not finish within 1s"));
+}
+
+#[test]
+fn test_completions() {
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["completions", "bash"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("_chronicle()"))
+        .stdout(predicate::str::contains("streak"));
+
+    cargo::cargo_bin_cmd!("chronicle")
+        .args(["completions", "fish"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("complete -c chronicle"));
+
+    // Hidden from the help output
+    cargo::cargo_bin_cmd!("chronicle")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("completions").not());
+}
+
 #[test]
 fn test_version_flag() {
     cargo::cargo_bin_cmd!("chronicle")